@@ -1,8 +1,11 @@
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{Constructors, NdArray, Reshape, StridedMemory, TensorDataType};
-use std::cell::RefCell;
+use crate::add_backwards::AddBackwards;
+use crate::autograd::util::constant;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::grad_mode::is_grad_enabled;
+use crate::ndarray::flags::NdArrayFlags;
+use crate::none_backwards::NoneBackwards;
+use crate::{Constructors, NdArray, StridedMemory, Tensor, TensorDataType};
 use std::hint::assert_unchecked;
-use std::rc::Rc;
 
 
 /// The default backwards node for leaf Tensors.
@@ -10,36 +13,64 @@ use std::rc::Rc;
 /// Accumulates the gradient of the function being differentiated with respect to `self`
 /// into the `tensor_grad` attribute of this struct.
 pub(crate) struct AccumulateGrad<T: TensorDataType> {
-    gradient: NdArray<'static, T>,
+    gradient: Tensor<'static, T>,
 }
 
 impl<T: TensorDataType> GradientFuncTrait<T> for AccumulateGrad<T> {
     /// Accumulates the gradient of the tensor being differentiated with respect to a leaf tensor
     /// into `tensor_grad`
     ///
+    /// The accumulated gradient is only differentiable if it was computed with gradient tracking enabled.
+    ///
     /// # Parameters
     ///
     /// - `grad`: the gradient of the function being differentiated with respect to `self`.
-    fn backward(&mut self, grad: &NdArray<T>) {
+    fn backward(&mut self, grad: Tensor<'static, T>, _: &mut GradientBuffer<T>) {
         // hint that we don't need broadcasting
         unsafe { assert_unchecked(self.gradient.shape() == grad.shape()) }
-        
-        self.gradient += grad;
+
+        if is_grad_enabled() && (self.gradient.requires_grad() || grad.requires_grad()) {
+            // the sum doesn't depend on the values of its operands,
+            // so we only need its gradient function
+            self.gradient.grad_fn = AddBackwards::new(&self.gradient, &grad);
+            self.gradient.flags |= NdArrayFlags::RequiresGrad;
+        } else {
+            self.detach_gradient();
+        }
+
+        *self.gradient.ndarray_mut() += grad.ndarray();
+    }
+
+    fn gradient(&self) -> Option<&Tensor<'static, T>> {
+        Some(&self.gradient)
     }
 
-    fn gradient(&self) -> Option<NdArray<T>> {
-        Some((&self.gradient).view())
+    fn gradient_mut(&mut self) -> Option<&mut Tensor<'static, T>> {
+        Some(&mut self.gradient)
     }
 
     fn zero_gradient(&mut self) {
-        self.gradient.zero();
+        self.detach_gradient();
+        self.gradient.ndarray_mut().zero();
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("gradient", &self.gradient)]
     }
 }
 
 impl<T: TensorDataType> AccumulateGrad<T> {
     pub(crate) fn new(shape: Vec<usize>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
-            gradient: NdArray::zeros(shape),
-        }))
+        GradientNode::new(Self {
+            gradient: constant(NdArray::zeros(shape)),
+        })
+    }
+
+    /// Removes the accumulated gradient from the graph it was computed in.
+    fn detach_gradient(&mut self) {
+        if self.gradient.requires_grad() {
+            self.gradient.grad_fn = NoneBackwards::new();
+            self.gradient.flags -= NdArrayFlags::RequiresGrad;
+        }
     }
 }
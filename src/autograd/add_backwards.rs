@@ -1,9 +1,7 @@
 use crate::autograd::util::reduce_gradient;
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
 use crate::identity_backwards::IdentityBackwards;
-use crate::{call_next_backward, FloatDataType, NdArray, StridedMemory, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
 
 /// Backwards function for pointwise tensor addition.
 ///
@@ -21,9 +19,13 @@ pub(crate) struct AddBackwards<T: FloatDataType> {
 pub(crate) struct AddScalarBackwards {}
 
 impl<T: FloatDataType> GradientFuncTrait<T> for AddBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(grad, &self.lhs_shape, self.next_functions[0]);
-        call_next_backward!(grad, &self.rhs_shape, self.next_functions[1]);
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, &grad, &self.lhs_shape, self.next_functions[0]);
+        call_next_backward!(gradients, grad, &self.rhs_shape, self.next_functions[1]);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
     }
 }
 
@@ -36,7 +38,7 @@ impl<T: FloatDataType> AddBackwards<T> {
             rhs_shape: rhs.shape().to_vec()
         };
 
-        Rc::new(RefCell::new(grad_fn))
+        GradientNode::new(grad_fn)
     }
 }
 
@@ -1,33 +1,39 @@
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, FloatDataType, NdArray, Reshape, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, Reshape, Tensor};
 
 
 pub(crate) struct BMMBackwards<T: FloatDataType> {
     pub(super) next_functions: [GradientFunction<T>; 2],
 
-    pub(super) lhs: Rc<NdArray<'static, T>>,
-    pub(super) rhs: Rc<NdArray<'static, T>>,
+    pub(super) lhs: Tensor<'static, T>,
+    pub(super) rhs: Tensor<'static, T>,
 }
 
 
 impl<T: FloatDataType> GradientFuncTrait<T> for BMMBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(grad.bmm(self.rhs.as_ref().transpose(1, 2)), 
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, grad.bmm((&self.rhs).transpose(1, 2)),
                             self.next_functions[0]);
         
-        call_next_backward!(self.lhs.as_ref().transpose(1, 2).bmm(grad), 
+        call_next_backward!(gradients, (&self.lhs).transpose(1, 2).bmm(&grad),
                             self.next_functions[1]);
     }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("lhs", &self.lhs), ("rhs", &self.rhs)]
+    }
 }
 
 impl<T: FloatDataType> BMMBackwards<T> {
     pub(crate) fn new(lhs: &Tensor<T>, rhs: &Tensor<T>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_functions: [lhs.grad_fn(), rhs.grad_fn()],
-            lhs: lhs.get_ndarray(),
-            rhs: rhs.get_ndarray()
-        }))
+            lhs: lhs.alias(),
+            rhs: rhs.alias()
+        })
     }
 }
@@ -0,0 +1,121 @@
+use crate::gradient_function::{run_grad, GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::grad_mode::{is_grad_enabled, EnableGradGuard, NoGradGuard};
+use crate::none_backwards::NoneBackwards;
+use crate::{call_next_backward, StridedMemory, Tensor, TensorDataType};
+
+
+/// Applies `func` to `inputs` without saving its intermediate results for the backward pass.
+///
+/// `func` is called with a tensor for each of `inputs`, in the same order. It runs once without
+/// gradient tracking in the forward pass, and once more during the backward pass to rebuild its graph
+/// just before it is needed. Only `inputs` are kept alive between the passes, so this trades compute
+/// for the memory otherwise held by the backward nodes of `func`.
+///
+/// `func` must compute the same result each time it is called with the same inputs.
+///
+/// # Panics
+/// - During `backward_create_graph()` or `grad()` with `create_graph` set,
+///   since the recomputed graph is not connected to the graph of `inputs`
+/// - If the output of `func` changes shape when it is recomputed
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut x = Tensor::new([1.0, 2.0]);
+/// x.set_requires_grad(true);
+///
+/// // the intermediate x^2 is recomputed rather than saved
+/// let y = checkpoint(|inputs| &inputs[0] * &inputs[0] * &inputs[0], &[&x]);
+/// y.sum().backward();
+///
+/// assert_eq!(y, Tensor::new([1.0, 8.0]));
+/// assert_eq!(x.gradient().unwrap(), NdArray::new([3.0, 12.0]));
+/// ```
+pub fn checkpoint<T, F>(func: F, inputs: &[&Tensor<T>]) -> Tensor<'static, T>
+where
+    T: TensorDataType,
+    F: Fn(&[Tensor<'static, T>]) -> Tensor<'static, T> + Send + Sync + 'static,
+{
+    let requires_grad = is_grad_enabled() && inputs.iter().any(|input| input.requires_grad());
+    let detached: Vec<_> = inputs.iter().map(|input| input.detached_alias()).collect();
+
+    let output = {
+        let _guard = NoGradGuard::new();
+        func(&detached)
+    };
+
+    let grad_fn = if requires_grad {
+        CheckpointBackwards::new(func, detached, inputs, output.shape())
+    } else {
+        NoneBackwards::new()
+    };
+    unsafe { Tensor::from_raw_parts(output.into_ndarray(), requires_grad, grad_fn) }
+}
+
+pub(crate) struct CheckpointBackwards<T: TensorDataType, F> {
+    next_functions: Vec<GradientFunction<T>>,
+    output_shape: Vec<usize>,
+
+    function: F,
+    inputs: Vec<Tensor<'static, T>>,
+}
+
+impl<T, F> GradientFuncTrait<T> for CheckpointBackwards<T, F>
+where
+    T: TensorDataType,
+    F: Fn(&[Tensor<'static, T>]) -> Tensor<'static, T> + Send + Sync + 'static,
+{
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        assert!(!is_grad_enabled(), "cannot create the graph of the gradient of a checkpointed function");
+
+        // fresh leaves so that the recomputed graph ends here rather than in the graph of the inputs
+        let inputs: Vec<_> = self.inputs.iter().zip(&self.next_functions)
+            .map(|(input, next_function)| {
+                let mut input = input.detached_alias();
+                input.set_requires_grad(!next_function.borrow().is_none());
+                input
+            })
+            .collect();
+
+        let output = {
+            let _guard = EnableGradGuard::new();
+            (self.function)(&inputs)
+        };
+        assert_eq!(output.shape(), self.output_shape, "a checkpointed function must compute the same output when recomputed");
+
+        let input_functions: Vec<_> = inputs.iter().map(|input| input.grad_fn()).collect();
+        let input_grads = run_grad(vec![(output.grad_fn(), grad)], &input_functions, false);
+
+        for (grad, next_function) in input_grads.into_iter().zip(&self.next_functions) {
+            if let Some(grad) = grad {
+                call_next_backward!(gradients, grad, next_function);
+            }
+        }
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        self.inputs.iter().map(|input| ("input", input)).collect()
+    }
+}
+
+impl<T, F> CheckpointBackwards<T, F>
+where
+    T: TensorDataType,
+    F: Fn(&[Tensor<'static, T>]) -> Tensor<'static, T> + Send + Sync + 'static,
+{
+    pub(crate) fn new(function: F,
+                      detached: Vec<Tensor<'static, T>>,
+                      inputs: &[&Tensor<T>],
+                      output_shape: &[usize]) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_functions: inputs.iter().map(|input| input.grad_fn()).collect(),
+            output_shape: output_shape.to_vec(),
+            function,
+            inputs: detached,
+        })
+    }
+}
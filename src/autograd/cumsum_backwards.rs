@@ -0,0 +1,36 @@
+use crate::autograd::util::expand_gradient;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
+
+
+pub(crate) struct CumsumBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+
+    shape: Vec<usize>,
+    axis: usize,
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for CumsumBackwards<T> {
+    /// The gradient of a cumulative sum is the reversed cumulative sum of `grad`,
+    /// i.e. `sum(grad) - cumsum(grad) + grad` along the same axis.
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        let total = grad.sum_along(self.axis as isize);
+        let total = expand_gradient(total, &[self.axis], &self.shape);
+
+        call_next_backward!(gradients, total - grad.cumsum(self.axis as isize) + grad, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+}
+
+impl<T: FloatDataType> CumsumBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>, axis: usize) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            shape: input.shape().to_vec(),
+            axis,
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use crate::autograd::util::reduce_gradient;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::grad_mode::{is_grad_enabled, NoGradGuard};
+use crate::none_backwards::NoneBackwards;
+use crate::{call_next_backward, StridedMemory, Tensor, TensorDataType};
+
+
+/// A user-defined differentiable operation.
+///
+/// `forward()` computes the output of the operation and `backward()` computes the gradients
+/// of its inputs from the gradient of its output. Once applied with `apply()`, the operation
+/// is recorded in the graph like any built-in operation.
+///
+/// If the gradients returned by `backward()` are computed using tensor operations on `grad` and
+/// the saved tensors, they can themselves be differentiated (see `Tensor::backward_create_graph()`).
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// struct Cube;
+///
+/// impl CustomFunction<f64> for Cube {
+///     fn forward(&self, ctx: &mut FunctionContext<f64>, inputs: &[&Tensor<f64>]) -> Tensor<'static, f64> {
+///         ctx.save_for_backward(inputs);
+///         inputs[0] * inputs[0] * inputs[0]
+///     }
+///
+///     fn backward(&self, ctx: &FunctionContext<f64>, grad: Tensor<'static, f64>) -> Vec<Option<Tensor<'static, f64>>> {
+///         let x = &ctx.saved_tensors()[0];
+///         vec![Some(x * x * 3.0 * grad)]
+///     }
+/// }
+///
+/// let mut x = Tensor::new([1.0, 2.0]);
+/// x.set_requires_grad(true);
+///
+/// let y = Cube.apply(&[&x]);
+/// y.backward();
+///
+/// assert_eq!(y, Tensor::new([1.0, 8.0]));
+/// assert_eq!(x.gradient().unwrap(), NdArray::new([3.0, 12.0]));
+/// ```
+pub trait CustomFunction<T: TensorDataType>: Send + Sync + 'static {
+    /// Computes the output of the operation.
+    ///
+    /// Gradient tracking is disabled while this runs, so the operations used here are not recorded.
+    /// Any tensors needed to compute the gradients should be saved with `ctx.save_for_backward()`.
+    fn forward(&self, ctx: &mut FunctionContext<T>, inputs: &[&Tensor<T>]) -> Tensor<'static, T>;
+
+    /// Computes the gradient of each input given the gradient of the output.
+    ///
+    /// Exactly one gradient must be returned for each input, in the same order as the inputs.
+    /// `None` indicates that an input has a gradient of 0, such as for non-differentiable inputs.
+    /// The gradient of an input which was broadcast in `forward()` may have the broadcast shape
+    /// and is summed over the broadcast axes.
+    fn backward(&self, ctx: &FunctionContext<T>, grad: Tensor<'static, T>) -> Vec<Option<Tensor<'static, T>>>;
+
+    /// Applies the operation to `inputs` and records it in the graph
+    /// if gradient tracking is enabled and any of the inputs require gradients.
+    fn apply(self, inputs: &[&Tensor<T>]) -> Tensor<'static, T>
+    where
+        Self: Sized,
+    {
+        let requires_grad = is_grad_enabled() && inputs.iter().any(|input| input.requires_grad());
+
+        let mut ctx = FunctionContext {
+            saved_tensors: Vec::new(),
+            needs_input_grad: inputs.iter().map(|input| input.requires_grad()).collect(),
+        };
+
+        let output = {
+            let _guard = NoGradGuard::new();
+            self.forward(&mut ctx, inputs)
+        };
+
+        let grad_fn = if requires_grad { CustomBackwards::new(self, ctx, inputs) } else { NoneBackwards::new() };
+        unsafe { Tensor::from_raw_parts(output.into_ndarray(), requires_grad, grad_fn) }
+    }
+}
+
+/// Holds the state of a `CustomFunction` between its forward and backward passes.
+pub struct FunctionContext<T: TensorDataType> {
+    saved_tensors: Vec<Tensor<'static, T>>,
+    needs_input_grad: Vec<bool>,
+}
+
+impl<T: TensorDataType> FunctionContext<T> {
+    /// Saves `tensors` so that they can be used in the backward pass.
+    ///
+    /// The tensors share their data with the originals and are not copied.
+    pub fn save_for_backward(&mut self, tensors: &[&Tensor<T>]) {
+        self.saved_tensors.extend(tensors.iter().map(|tensor| tensor.alias()));
+    }
+
+    /// Returns the tensors saved during the forward pass, in the order they were saved.
+    pub fn saved_tensors(&self) -> &[Tensor<'static, T>] {
+        &self.saved_tensors
+    }
+
+    /// Returns whether the input at `index` requires gradients.
+    ///
+    /// The gradients of inputs which do not require gradients are discarded,
+    /// so these need not be computed.
+    ///
+    /// # Panics
+    /// - If `index` is out of bounds
+    pub fn needs_input_grad(&self, index: usize) -> bool {
+        self.needs_input_grad[index]
+    }
+}
+
+pub(crate) struct CustomBackwards<T: TensorDataType, F: CustomFunction<T>> {
+    next_functions: Vec<GradientFunction<T>>,
+    shapes: Vec<Vec<usize>>,
+
+    function: F,
+    ctx: FunctionContext<T>,
+}
+
+impl<T: TensorDataType, F: CustomFunction<T>> GradientFuncTrait<T> for CustomBackwards<T, F> {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        let input_grads = self.function.backward(&self.ctx, grad);
+        assert_eq!(input_grads.len(), self.next_functions.len(),
+                   "a custom function must return exactly one gradient for each of its inputs");
+
+        for ((grad, next_function), shape) in input_grads.into_iter().zip(&self.next_functions).zip(&self.shapes) {
+            if let Some(grad) = grad {
+                call_next_backward!(gradients, grad, shape, next_function);
+            }
+        }
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        self.ctx.saved_tensors.iter().map(|tensor| ("saved", tensor)).collect()
+    }
+}
+
+impl<T: TensorDataType, F: CustomFunction<T>> CustomBackwards<T, F> {
+    pub(crate) fn new(function: F, ctx: FunctionContext<T>, inputs: &[&Tensor<T>]) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_functions: inputs.iter().map(|input| input.grad_fn()).collect(),
+            shapes: inputs.iter().map(|input| input.shape().to_vec()).collect(),
+            function,
+            ctx,
+        })
+    }
+}
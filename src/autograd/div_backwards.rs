@@ -1,14 +1,12 @@
 use crate::autograd::util::reduce_gradient;
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, Constructors, FloatDataType, NdArray, StridedMemory, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
 
 pub(crate) struct DivBackwards<T: FloatDataType> {
     next_functions: [GradientFunction<T>; 2],
 
-    lhs: Rc<NdArray<'static, T>>,
-    rhs: Rc<NdArray<'static, T>>,
+    lhs: Tensor<'static, T>,
+    rhs: Tensor<'static, T>,
 }
 
 pub(crate) struct DivScalarBackwards<T: FloatDataType> {
@@ -20,45 +18,52 @@ pub(crate) struct DivScalarBackwards<T: FloatDataType> {
 
 
 impl<T: FloatDataType> GradientFuncTrait<T> for DivBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        if self.next_functions[0].borrow().is_none() {
-            call_next_backward!(grad * -self.lhs.as_ref() / (self.rhs.as_ref() * self.rhs.as_ref()), 
-                                self.rhs.shape(), self.next_functions[1]);
-        } else {
-            let lhs_grad = grad * (NdArray::scalar(T::one()) / self.rhs.as_ref());
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        let lhs_grad = grad / &self.rhs;
 
-            call_next_backward!(&lhs_grad, self.lhs.shape(), self.next_functions[0]);
-            
-            call_next_backward!((&lhs_grad / self.rhs.as_ref()) * -self.lhs.as_ref(), 
-                                self.rhs.shape(), self.next_functions[1]);
-        }
+        call_next_backward!(gradients, (&lhs_grad / &self.rhs) * -&self.lhs,
+                            self.rhs.shape(), self.next_functions[1]);
+
+        call_next_backward!(gradients, lhs_grad, self.lhs.shape(), self.next_functions[0]);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("lhs", &self.lhs), ("rhs", &self.rhs)]
     }
 }
 
 impl<T: FloatDataType> GradientFuncTrait<T> for DivScalarBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
         let grad = grad * self.one_by_rhs;
-        call_next_backward!(grad, &self.lhs_shape, self.next_function);
+        call_next_backward!(gradients, grad, &self.lhs_shape, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
     }
 }
 
 
 impl<T: FloatDataType> DivBackwards<T> {
     pub(crate) fn new(lhs: &Tensor<T>, rhs: &Tensor<T>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_functions: [lhs.grad_fn(), rhs.grad_fn()],
-            lhs: lhs.get_ndarray(),
-            rhs: rhs.get_ndarray(),
-        }))
+            lhs: lhs.alias(),
+            rhs: rhs.alias(),
+        })
     }
 }
 
 impl<T: FloatDataType> DivScalarBackwards<T> {
     pub(crate) fn new(lhs: &Tensor<T>, rhs: T) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_function: lhs.grad_fn(),
             lhs_shape: lhs.shape().to_vec(),
             one_by_rhs: T::one() / rhs
-        }))
+        })
     }
 }
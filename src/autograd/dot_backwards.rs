@@ -1,33 +1,39 @@
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, FloatDataType, NdArray, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, Tensor};
 
 
 pub(crate) struct DotBackwards<T: FloatDataType> {
     next_functions: [GradientFunction<T>; 2],
 
-    lhs: Rc<NdArray<'static, T>>,
-    rhs: Rc<NdArray<'static, T>>,
+    lhs: Tensor<'static, T>,
+    rhs: Tensor<'static, T>,
 }
 
 
 impl<T: FloatDataType> GradientFuncTrait<T> for DotBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(self.rhs.as_ref() * grad,
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, &self.rhs * &grad,
                             self.next_functions[0]);
         
-        call_next_backward!(self.lhs.as_ref() * grad,
+        call_next_backward!(gradients, &self.lhs * &grad,
                             self.next_functions[1]);
     }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("lhs", &self.lhs), ("rhs", &self.rhs)]
+    }
 }
 
 impl<T: FloatDataType> DotBackwards<T> {
     pub(crate) fn new(lhs: &Tensor<T>, rhs: &Tensor<T>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_functions: [lhs.grad_fn(), rhs.grad_fn()],
-            lhs: lhs.get_ndarray(),
-            rhs: rhs.get_ndarray(),
-        }))
+            lhs: lhs.alias(),
+            rhs: rhs.alias(),
+        })
     }
 }
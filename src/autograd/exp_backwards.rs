@@ -0,0 +1,34 @@
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, Tensor};
+
+
+/// Backwards function for the elementwise exponential.
+///
+/// If `y = exp(x)`, then the gradient of `y` with respect to `x` is `exp(x)`.
+pub(crate) struct ExpBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+    input: Tensor<'static, T>,
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for ExpBackwards<T> {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, self.input.exp() * grad, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("input", &self.input)]
+    }
+}
+
+impl<T: FloatDataType> ExpBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            input: input.alias(),
+        })
+    }
+}
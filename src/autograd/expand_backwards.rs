@@ -0,0 +1,31 @@
+use crate::autograd::util::reduce_gradient;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
+
+
+/// Backwards function for broadcasting a tensor to a larger shape.
+///
+/// The gradient is summed over every axis along which the input was repeated.
+pub(crate) struct ExpandBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+    shape: Vec<usize>,
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for ExpandBackwards<T> {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, grad, &self.shape, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+}
+
+impl<T: FloatDataType> ExpandBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            shape: input.shape().to_vec(),
+        })
+    }
+}
@@ -0,0 +1,195 @@
+//! Functional transforms which differentiate Rust closures rather than existing computation graphs.
+//!
+//! Each transform evaluates the function on fresh leaf tensors (or, for `jvp`, dual tensors)
+//! created from its inputs and returns the derivatives as new arrays. The gradients of the caller's tensors are never
+//! read or modified, so these may be freely used within solvers and other numerical code.
+//!
+//! The functions here share their names with the graph-based functions of the `autograd` module,
+//! so they are not re-exported at the crate root and should be used through the module.
+//!
+//! # Example
+//! ```
+//! # use redstone_ml::*;
+//! use redstone_ml::functional;
+//!
+//! let x = NdArray::new([1.0, 2.0, 3.0]);
+//!
+//! // d/dx sum(x^2) = 2x
+//! let dx = functional::grad(|x| (&x[0] * &x[0]).sum(), &[&x]).remove(0);
+//! assert_eq!(dx, NdArray::new([2.0, 4.0, 6.0]));
+//! ```
+
+use crate::autograd::util::constant;
+use crate::grad_mode::{is_inference_mode_enabled, EnableGradGuard};
+use crate::higher_order::grad_with;
+use crate::{Constructors, DualTensor, NdArray, StridedMemory, Tensor, TensorDataType};
+
+
+/// Enables gradient tracking for the duration of a transform.
+///
+/// `EnableGradGuard` has no effect inside `inference_mode`, where the transforms
+/// would silently return zero gradients, so they refuse to run there instead.
+fn enable_grad() -> EnableGradGuard {
+    assert!(!is_inference_mode_enabled(), "functional transforms cannot differentiate inside inference_mode");
+    EnableGradGuard::new()
+}
+
+/// Creates a new leaf tensor with a copy of `array` which requires gradients.
+fn leaf<T: TensorDataType>(array: &NdArray<T>) -> Tensor<'static, T> {
+    let mut tensor = unsafe { Tensor::from_array_and_flags(array.clone(), false, true) };
+    tensor.set_requires_grad(true);
+    tensor
+}
+
+/// Computes the gradient of the scalar function `func` with respect to each of its `inputs`.
+///
+/// `func` is called with a leaf tensor for each of `inputs`, in the same order.
+///
+/// # Panics
+/// - If the output of `func` has more than one element
+/// - If called inside `inference_mode`
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// use redstone_ml::functional;
+///
+/// let x = NdArray::new([1.0, 2.0]);
+/// let y = NdArray::new([3.0, 4.0]);
+///
+/// // f(x, y) = x . y
+/// let grads = functional::grad(|inputs| inputs[0].dot(&inputs[1]), &[&x, &y]);
+/// assert_eq!(grads[0], y);
+/// assert_eq!(grads[1], x);
+/// ```
+pub fn grad<'b, T, F>(func: F, inputs: &[&NdArray<T>]) -> Vec<NdArray<'static, T>>
+where
+    T: TensorDataType,
+    F: FnOnce(&[Tensor<'static, T>]) -> Tensor<'b, T>,
+{
+    let _guard = enable_grad();
+
+    let inputs: Vec<_> = inputs.iter().map(|input| leaf(input)).collect();
+    let output = func(&inputs);
+    assert_eq!(output.size(), 1, "the gradient is only defined for scalar functions");
+
+    let seed = constant(NdArray::ones(output.shape()));
+    let inputs: Vec<_> = inputs.iter().collect();
+
+    grad_with(&[&output], &[&seed], &inputs, false).into_iter()
+        .map(|gradient| gradient.into_ndarray())
+        .collect()
+}
+
+/// Computes the output of `func` at `input` and the vector-Jacobian product of `vector`
+/// with the Jacobian of `func` at `input`.
+///
+/// This requires a single reverse-mode pass through `func`.
+///
+/// # Returns
+/// - A tuple of the output of `func` and the vector-Jacobian product,
+///   which has the same shape as `input`.
+///
+/// # Panics
+/// - If the shape of `vector` differs from the shape of the output of `func`
+/// - If called inside `inference_mode`
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// use redstone_ml::functional;
+///
+/// let x = NdArray::new([1.0, 2.0]);
+/// let v = NdArray::new([1.0, -1.0]);
+///
+/// let (y, vjp) = functional::vjp(|x| &x * &x, &x, &v);
+/// assert_eq!(y, NdArray::new([1.0, 4.0]));
+/// assert_eq!(vjp, NdArray::new([2.0, -4.0]));
+/// ```
+pub fn vjp<'b, T, F>(func: F, input: &NdArray<T>, vector: &NdArray<T>) -> (NdArray<'static, T>, NdArray<'static, T>)
+where
+    T: TensorDataType,
+    F: FnOnce(Tensor<'static, T>) -> Tensor<'b, T>,
+{
+    let _guard = enable_grad();
+
+    let input = leaf(input);
+    let output = func(input.alias());
+    assert_eq!(output.shape(), vector.shape(), "the vector must have the same shape as the output");
+
+    let vector = constant(vector.clone());
+    let vjp = grad_with(&[&output], &[&vector], &[&input], false).remove(0);
+
+    (output.detach(), vjp.into_ndarray())
+}
+
+/// Computes the output of `func` at `input` and the Jacobian-vector product of the Jacobian
+/// of `func` at `input` with the tangent `vector`.
+///
+/// This is the directional derivative of `func` at `input` along `vector`. `func` is called with
+/// a dual tensor pairing `input` with `vector`, so the product is computed in a single forward pass
+/// without recording a graph (see the `dual_tensor` module).
+///
+/// # Returns
+/// - A tuple of the output of `func` and the Jacobian-vector product,
+///   which has the same shape as the output.
+///
+/// # Panics
+/// - If the shape of `vector` differs from the shape of `input`
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// use redstone_ml::functional;
+///
+/// let x = NdArray::new([1.0, 2.0]);
+/// let t = NdArray::new([1.0, 0.0]);
+///
+/// // the derivative of sum(x^3) along the first axis is 3 x0^2
+/// let (y, jvp) = functional::jvp(|x| (&x * &x * &x).sum(), &x, &t);
+/// assert_eq!(y, NdArray::scalar(9.0));
+/// assert_eq!(jvp, NdArray::scalar(3.0));
+/// ```
+pub fn jvp<'b, T, F>(func: F, input: &NdArray<T>, vector: &NdArray<T>) -> (NdArray<'static, T>, NdArray<'static, T>)
+where
+    T: TensorDataType,
+    F: FnOnce(DualTensor<'static, T>) -> DualTensor<'b, T>,
+{
+    assert_eq!(input.shape(), vector.shape(), "the vector must have the same shape as the input");
+
+    let input = DualTensor::new(input.clone(), vector.clone());
+    let (output, jvp) = func(input).into_parts();
+
+    (output.into_owned(), jvp.into_owned())
+}
+
+/// Computes the Jacobian of `func` at `input`.
+///
+/// The shape of the Jacobian is the shape of the output of `func` followed by the shape of `input`.
+///
+/// # Panics
+/// - If called inside `inference_mode`
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// use redstone_ml::functional;
+///
+/// let x = NdArray::new([1.0, 2.0]);
+/// let a = Tensor::new([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+///
+/// let jacobian = functional::jacobian(|x| a.matmul(&x), &x);
+/// assert_eq!(jacobian, NdArray::new([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]));
+/// ```
+pub fn jacobian<'b, T, F>(func: F, input: &NdArray<T>) -> NdArray<'static, T>
+where
+    T: TensorDataType,
+    F: FnOnce(Tensor<'static, T>) -> Tensor<'b, T>,
+{
+    let _guard = enable_grad();
+
+    let input = leaf(input);
+    let output = func(input.alias());
+
+    crate::higher_order::jacobian(&output, &input)
+}
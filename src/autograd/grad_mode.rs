@@ -0,0 +1,176 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+thread_local! {
+    static GRAD_ENABLED: Cell<bool> = const { Cell::new(true) };
+    static INFERENCE_MODE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns whether operations on the current thread record gradient functions.
+///
+/// This is `false` inside `no_grad` and `inference_mode` scopes.
+pub fn is_grad_enabled() -> bool {
+    GRAD_ENABLED.with(Cell::get) && !is_inference_mode_enabled()
+}
+
+/// Returns whether the current thread is inside an `inference_mode` scope.
+pub fn is_inference_mode_enabled() -> bool {
+    INFERENCE_MODE.with(Cell::get)
+}
+
+/// Disables gradient tracking on the current thread until the guard is dropped.
+///
+/// Tensors created while the guard is alive do not require gradients, even if their
+/// sources do. Gradient tracking can be re-enabled inside using `EnableGradGuard`.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut a = Tensor::new([1.0f32, 2.0]);
+/// a.set_requires_grad(true);
+///
+/// {
+///     let _guard = NoGradGuard::new();
+///     assert!(!(&a * 2.0).requires_grad());
+/// }
+///
+/// assert!((&a * 2.0).requires_grad());
+/// ```
+pub struct NoGradGuard {
+    previous: bool,
+
+    // guards restore thread-local state and so must be dropped on the thread that created them
+    _marker: PhantomData<*const ()>,
+}
+
+impl NoGradGuard {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self { previous: GRAD_ENABLED.with(|enabled| enabled.replace(false)), _marker: PhantomData }
+    }
+}
+
+impl Drop for NoGradGuard {
+    fn drop(&mut self) {
+        GRAD_ENABLED.with(|enabled| enabled.set(self.previous));
+    }
+}
+
+/// Enables gradient tracking on the current thread until the guard is dropped.
+///
+/// This re-enables tracking inside a `no_grad` scope, but has no effect inside `inference_mode`.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut a = Tensor::new([1.0f32, 2.0]);
+/// a.set_requires_grad(true);
+///
+/// let _no_grad = NoGradGuard::new();
+/// {
+///     let _enable_grad = EnableGradGuard::new();
+///     assert!((&a * 2.0).requires_grad());
+/// }
+/// ```
+pub struct EnableGradGuard {
+    previous: bool,
+    _marker: PhantomData<*const ()>,
+}
+
+impl EnableGradGuard {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self { previous: GRAD_ENABLED.with(|enabled| enabled.replace(true)), _marker: PhantomData }
+    }
+}
+
+impl Drop for EnableGradGuard {
+    fn drop(&mut self) {
+        GRAD_ENABLED.with(|enabled| enabled.set(self.previous));
+    }
+}
+
+/// Enables inference mode on the current thread until the guard is dropped.
+///
+/// Like `NoGradGuard`, no gradient functions are allocated while the guard is alive.
+/// Unlike `NoGradGuard`, gradient tracking cannot be re-enabled inside using `EnableGradGuard`.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut a = Tensor::new([1.0f32, 2.0]);
+/// a.set_requires_grad(true);
+///
+/// let _inference = InferenceModeGuard::new();
+/// let _enable_grad = EnableGradGuard::new();
+/// assert!(!(&a * 2.0).requires_grad());
+/// ```
+pub struct InferenceModeGuard {
+    previous: bool,
+    _marker: PhantomData<*const ()>,
+}
+
+impl InferenceModeGuard {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self { previous: INFERENCE_MODE.with(|enabled| enabled.replace(true)), _marker: PhantomData }
+    }
+}
+
+impl Drop for InferenceModeGuard {
+    fn drop(&mut self) {
+        INFERENCE_MODE.with(|enabled| enabled.set(self.previous));
+    }
+}
+
+/// Runs `func` with gradient tracking disabled on the current thread and returns its result.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut weights = Tensor::new([1.0f32, 2.0]);
+/// weights.set_requires_grad(true);
+///
+/// let prediction = no_grad(|| &weights * 3.0);
+/// assert!(!prediction.requires_grad());
+/// ```
+pub fn no_grad<R>(func: impl FnOnce() -> R) -> R {
+    let _guard = NoGradGuard::new();
+    func()
+}
+
+/// Runs `func` with gradient tracking enabled on the current thread and returns its result.
+///
+/// This re-enables tracking inside a `no_grad` scope, but has no effect inside `inference_mode`.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut weights = Tensor::new([1.0f32, 2.0]);
+/// weights.set_requires_grad(true);
+///
+/// let prediction = no_grad(|| enable_grad(|| &weights * 3.0));
+/// assert!(prediction.requires_grad());
+/// ```
+pub fn enable_grad<R>(func: impl FnOnce() -> R) -> R {
+    let _guard = EnableGradGuard::new();
+    func()
+}
+
+/// Runs `func` in inference mode on the current thread and returns its result.
+///
+/// No gradient functions are allocated inside `func`, and `enable_grad` cannot re-enable them.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut weights = Tensor::new([1.0f32, 2.0]);
+/// weights.set_requires_grad(true);
+///
+/// let prediction = inference_mode(|| enable_grad(|| &weights * 3.0));
+/// assert!(!prediction.requires_grad());
+/// ```
+pub fn inference_mode<R>(func: impl FnOnce() -> R) -> R {
+    let _guard = InferenceModeGuard::new();
+    func()
+}
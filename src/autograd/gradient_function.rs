@@ -1,19 +1,37 @@
-use crate::{NdArray, TensorDataType};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::grad_mode::{EnableGradGuard, NoGradGuard};
+use crate::hooks::NodeHooks;
+use crate::{Tensor, TensorDataType};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 
-pub(crate) trait GradientFuncTrait<T: TensorDataType> {
-    /// Computes the gradient of this function with respect to its sources using the chain rule.
+pub(crate) trait GradientFuncTrait<T: TensorDataType>: Send + Sync {
+    /// Computes the gradient of this function with respect to its sources using the chain rule
+    /// and accumulates these into `gradients`.
+    ///
+    /// The gradients are computed using tensor operations, so if gradient tracking is enabled,
+    /// they have their own graph and can themselves be differentiated.
     ///
     /// # Parameters
     ///
     /// - `grad`: the gradient of the function being differentiated with respect to `self`.
-    fn backward(&mut self, grad: &NdArray<T>);
+    /// - `gradients`: the gradients flowing into each node of the graph in the current backward pass.
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>);
+
+    /// Returns the gradient functions of the sources of this function.
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &[]
+    }
 
     /// Returns the gradient of the function being differentiated with respect to `self`
     /// if this function is a leaf. Otherwise, returns `None`.
-    fn gradient(&self) -> Option<NdArray<T>> {
+    fn gradient(&self) -> Option<&Tensor<'static, T>> {
+        None
+    }
+
+    /// Returns a mutable reference to the accumulated gradient if this function is a leaf.
+    fn gradient_mut(&mut self) -> Option<&mut Tensor<'static, T>> {
         None
     }
 
@@ -24,28 +42,240 @@ pub(crate) trait GradientFuncTrait<T: TensorDataType> {
     fn is_none(&self) -> bool {
         false
     }
+
+    /// Returns the name of the type of this gradient function, e.g. `MulBackwards`.
+    fn name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap();
+        name.rsplit("::").next().unwrap()
+    }
+
+    /// Returns the tensors this function keeps alive for the backward pass along with their names.
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        Vec::new()
+    }
+}
+
+
+/// A node of the graph, which holds a gradient function
+/// along with the hooks registered on the tensor it computes the gradient of.
+///
+/// Nodes may be shared between threads, so the gradient function is behind a read-write lock.
+/// A thread must not try to lock the same node again while it holds a guard to it,
+/// and a thread which needs both locks must take the function lock before the hooks lock.
+pub(crate) struct GradientNode<T: TensorDataType, F: ?Sized + GradientFuncTrait<T> = dyn GradientFuncTrait<T>> {
+    hooks: Mutex<NodeHooks<T>>,
+    function: RwLock<F>,
+}
+
+pub(crate) type GradientFunction<T> = Arc<GradientNode<T>>;
+
+impl<T: TensorDataType, F: GradientFuncTrait<T> + 'static> GradientNode<T, F> {
+    pub(crate) fn new(function: F) -> GradientFunction<T> {
+        Arc::new(GradientNode {
+            hooks: Mutex::new(NodeHooks::new()),
+            function: RwLock::new(function),
+        })
+    }
+}
+
+impl<T: TensorDataType, F: ?Sized + GradientFuncTrait<T>> GradientNode<T, F> {
+    /// Locks the gradient function for reading.
+    ///
+    /// Poisoned locks are recovered since a panicking backward pass does not leave the node inconsistent.
+    pub(crate) fn borrow(&self) -> RwLockReadGuard<'_, F> {
+        self.function.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the gradient function for writing.
+    pub(crate) fn borrow_mut(&self) -> RwLockWriteGuard<'_, F> {
+        self.function.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the hooks registered on the tensor this node computes the gradient of.
+    pub(crate) fn hooks(&self) -> MutexGuard<'_, NodeHooks<T>> {
+        self.hooks.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Returns an identifier which is unique to the node `function` points to.
+pub(crate) fn node_id<T: TensorDataType>(function: &GradientFunction<T>) -> *const () {
+    Arc::as_ptr(function) as *const ()
+}
+
+/// Tensors which can be accumulated into a `GradientBuffer`.
+pub(crate) trait IntoGradient<T: TensorDataType> {
+    fn into_gradient(self) -> Tensor<'static, T>;
+}
+
+impl<T: TensorDataType> IntoGradient<T> for Tensor<'static, T> {
+    fn into_gradient(self) -> Tensor<'static, T> {
+        self
+    }
+}
+
+impl<T: TensorDataType> IntoGradient<T> for &Tensor<'_, T> {
+    fn into_gradient(self) -> Tensor<'static, T> {
+        self.alias()
+    }
+}
+
+/// The gradients flowing into each node of the graph during a backward pass.
+///
+/// Gradients arriving at the same node from different paths are summed, so each node
+/// only propagates once it has received the gradients from all of its uses.
+pub(crate) struct GradientBuffer<T: TensorDataType> {
+    gradients: HashMap<*const (), Tensor<'static, T>>,
+}
+
+impl<T: TensorDataType> GradientBuffer<T> {
+    fn new() -> Self {
+        Self { gradients: HashMap::new() }
+    }
+
+    /// Adds `grad` to the gradient flowing into `function`.
+    pub(crate) fn accumulate(&mut self, function: &GradientFunction<T>, grad: impl IntoGradient<T>) {
+        let grad = grad.into_gradient();
+
+        match self.gradients.entry(node_id(function)) {
+            Entry::Occupied(mut entry) => {
+                let total = entry.get_mut();
+
+                if total.requires_grad() || grad.requires_grad() {
+                    *total = &*total + &grad;
+                } else {
+                    *total.ndarray_mut() += grad.ndarray();
+                }
+            }
+            Entry::Vacant(entry) => { entry.insert(grad); }
+        }
+    }
+
+    /// Removes and returns the total gradient flowing into `function`.
+    fn take(&mut self, function: &GradientFunction<T>) -> Option<Tensor<'static, T>> {
+        self.gradients.remove(&node_id(function))
+    }
+}
+
+/// Returns the nodes of the graphs rooted at `roots` such that every node comes before its sources.
+///
+/// `NoneBackwards` nodes are excluded.
+fn topological_order<T: TensorDataType>(roots: &[GradientFunction<T>]) -> Vec<GradientFunction<T>> {
+    let mut order = Vec::new();
+    let mut visited = std::collections::HashSet::new();
+
+    // each node is pushed a second time (`expanded = true`) once its sources have been pushed
+    // so that it's appended to `order` only after all of its sources
+    let mut stack: Vec<_> = roots.iter().map(|root| (root.clone(), false)).collect();
+
+    while let Some((function, expanded)) = stack.pop() {
+        if expanded {
+            order.push(function);
+            continue;
+        }
+
+        if function.borrow().is_none() || !visited.insert(node_id(&function)) {
+            continue;
+        }
+
+        stack.push((function.clone(), true));
+
+        for next in function.borrow().next_functions() {
+            if !next.borrow().is_none() && !visited.contains(&node_id(next)) {
+                stack.push((next.clone(), false));
+            }
+        }
+    }
+
+    order.reverse();
+    order
 }
 
+/// Backpropagates each gradient through the graph rooted at its gradient function.
+///
+/// Nodes are visited in topological order and each node's backward function runs exactly once
+/// with the sum of the gradients flowing into it.
+///
+/// The hooks registered on each node run on its total gradient before it is propagated,
+/// and the post-accumulate hooks of each leaf run once its gradient has been accumulated.
+///
+/// If `create_graph` is set, the gradients are recorded in a graph of their own.
+/// If `inputs` is given, the total gradient flowing into each of these nodes is returned
+/// and leaf and retained gradients are left untouched. Otherwise, gradients are accumulated
+/// into the leaves and into the tensors which retain their gradients.
+fn execute<T: TensorDataType>(roots: Vec<(GradientFunction<T>, Tensor<'static, T>)>,
+                              inputs: Option<&[GradientFunction<T>]>,
+                              create_graph: bool) -> HashMap<*const (), Tensor<'static, T>> {
+    let _no_grad = (!create_graph).then(NoGradGuard::new);
+    let _enable_grad = create_graph.then(EnableGradGuard::new);
 
-pub(crate) type GradientFunction<T> = Rc<RefCell<dyn GradientFuncTrait<T>>>;
+    let mut gradients = GradientBuffer::new();
+    let mut captured = HashMap::new();
+
+    for (root, grad) in roots.iter() {
+        if !root.borrow().is_none() {
+            gradients.accumulate(root, grad);
+        }
+    }
+
+    let roots: Vec<_> = roots.into_iter().map(|(root, _)| root).collect();
+
+    for function in topological_order(&roots) {
+        let Some(grad) = gradients.take(&function) else { continue };
+        let grad = function.hooks().run(grad, inputs.is_none());
+
+        if let Some(inputs) = inputs {
+            if inputs.iter().any(|input| node_id(input) == node_id(&function)) {
+                captured.insert(node_id(&function), grad.alias());
+            }
+
+            if function.borrow().gradient().is_some() {
+                continue;
+            }
+        }
+
+        function.borrow_mut().backward(grad, &mut gradients);
+
+        let mut function_guard = function.borrow_mut();
+        if let Some(gradient) = function_guard.gradient_mut() {
+            let mut hooks = function.hooks();
+            if hooks.has_post_accumulate_grad_hooks() {
+                hooks.run_post_accumulate_grad_hooks(gradient.ndarray_mut());
+            }
+        }
+    }
+
+    captured
+}
+
+/// Backpropagates `grad` through the graph rooted at `root`, accumulating gradients into its leaves.
+pub(crate) fn run_backward<T: TensorDataType>(root: &GradientFunction<T>, grad: Tensor<'static, T>, create_graph: bool) {
+    execute(vec![(root.clone(), grad)], None, create_graph);
+}
+
+/// Backpropagates each gradient through the graph rooted at its gradient function
+/// and returns the total gradient flowing into each of `inputs`.
+///
+/// Inputs which the roots do not depend on are given a gradient of `None`.
+pub(crate) fn run_grad<T: TensorDataType>(roots: Vec<(GradientFunction<T>, Tensor<'static, T>)>,
+                                          inputs: &[GradientFunction<T>],
+                                          create_graph: bool) -> Vec<Option<Tensor<'static, T>>> {
+    let captured = execute(roots, Some(inputs), create_graph);
+    inputs.iter().map(|input| captured.get(&node_id(input)).map(Tensor::alias)).collect()
+}
 
 #[macro_export]
 macro_rules! call_next_backward {
-    ($grad:expr, $next:expr) => {
+    ($gradients:expr, $grad:expr, $next:expr) => {
         if !$next.borrow().is_none() {
-            $next.borrow_mut().backward(&$grad);
+            $gradients.accumulate(&$next, $grad);
         }
     };
-    
-    ($grad:expr, $shape:expr, $next:expr) => {
+
+    ($gradients:expr, $grad:expr, $shape:expr, $next:expr) => {
         if !$next.borrow().is_none() {
-            if $shape == $grad.shape() {
-                $next.borrow_mut().backward(&$grad);
-            } else {
-                let grad = $grad;
-                let next_grad = reduce_gradient(&grad, $shape);
-                $next.borrow_mut().backward(&next_grad);
-            };
+            let grad = $crate::gradient_function::IntoGradient::into_gradient($grad);
+            $gradients.accumulate(&$next, reduce_gradient(grad, $shape));
         }
     };
 }
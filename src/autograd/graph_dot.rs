@@ -0,0 +1,53 @@
+use crate::gradient_function::node_id;
+use crate::{StridedMemory, Tensor, TensorDataType};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+
+/// Renders the backward graph of `tensor` in the Graphviz DOT language.
+///
+/// Each node is labelled with the name of its gradient function followed by the shape and dtype
+/// of each tensor it saved for the backward pass. Edges point from a node to its sources,
+/// in the order the gradient flows. Sources which do not require gradients are drawn dashed.
+pub(crate) fn graph_dot<T: TensorDataType>(tensor: &Tensor<T>) -> String {
+    let dtype = T::DTYPE.name();
+    let root = tensor.grad_fn();
+
+    let mut dot = String::from("digraph {\n    node [shape=box];\n");
+    writeln!(dot, "    tensor [label=\"tensor\\n{:?} {dtype}\", shape=ellipse];", tensor.shape()).unwrap();
+    writeln!(dot, "    tensor -> 0;").unwrap();
+
+    let mut ids = HashMap::from([(node_id(&root), 0)]);
+    let mut stack = vec![root];
+
+    while let Some(function) = stack.pop() {
+        let id = ids[&node_id(&function)];
+
+        let (label, is_none, next_functions) = {
+            let function = function.borrow();
+
+            let mut label = function.name().to_string();
+            for (name, saved) in function.saved_tensors() {
+                write!(label, "\\n{name}: {:?} {dtype}", saved.shape()).unwrap();
+            }
+
+            (label, function.is_none(), function.next_functions().to_vec())
+        };
+
+        let style = if is_none { ", style=dashed" } else { "" };
+        writeln!(dot, "    {id} [label=\"{label}\"{style}];").unwrap();
+
+        for next in next_functions {
+            let next_id = ids.len();
+            let next_id = *ids.entry(node_id(&next)).or_insert_with(|| {
+                stack.push(next.clone());
+                next_id
+            });
+
+            writeln!(dot, "    {id} -> {next_id};").unwrap();
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
@@ -0,0 +1,188 @@
+use crate::autograd::util::constant;
+use crate::gradient_function::run_grad;
+use crate::{Constructors, NdArray, StridedMemory, Tensor, TensorDataType};
+
+
+/// Computes the gradients of the sum of `outputs` with respect to each of `inputs`.
+///
+/// Unlike `Tensor::backward()`, the gradients are returned rather than accumulated into the leaves
+/// of the graph, and `inputs` need not be leaves.
+///
+/// If `create_graph` is set, the returned gradients have their own graph and can themselves
+/// be differentiated. Inputs which the outputs do not depend on are given a gradient of 0.
+///
+/// # Panics
+/// - If any of `inputs` does not require gradients
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut x = Tensor::new([1.0, 2.0]);
+/// x.set_requires_grad(true);
+///
+/// let y = (&x * &x * &x).sum();
+///
+/// // dy/dx = 3x^2
+/// let dx = grad(&[&y], &[&x], true).remove(0);
+/// assert_eq!(dx, Tensor::new([3.0, 12.0]));
+///
+/// // gradient penalty: d(sum(dy/dx))/dx = 6x
+/// let penalty = dx.sum();
+/// let ddx = grad(&[&penalty], &[&x], false).remove(0);
+/// assert_eq!(ddx, Tensor::new([6.0, 12.0]));
+///
+/// // the leaves' gradients are not modified
+/// assert_eq!(x.gradient().unwrap(), NdArray::new([0.0, 0.0]));
+/// ```
+pub fn grad<T: TensorDataType>(outputs: &[&Tensor<T>],
+                               inputs: &[&Tensor<T>],
+                               create_graph: bool) -> Vec<Tensor<'static, T>> {
+    let grad_outputs: Vec<Tensor<T>> = outputs.iter().map(|output| Tensor::ones(output.shape())).collect();
+    let grad_outputs: Vec<&Tensor<T>> = grad_outputs.iter().collect();
+
+    grad_with(outputs, &grad_outputs, inputs, create_graph)
+}
+
+/// Computes the vector-Jacobian products of `outputs` with `grad_outputs`
+/// with respect to each of `inputs` and sums them.
+///
+/// `grad_outputs` are the gradients of the function being differentiated with respect to each output.
+/// Otherwise, this is identical to `grad()`.
+///
+/// # Panics
+/// - If the number of `outputs` and `grad_outputs` differ
+/// - If the shape of an output differs from the shape of its gradient
+/// - If any of `inputs` does not require gradients
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut x = Tensor::new([1.0, 2.0]);
+/// x.set_requires_grad(true);
+///
+/// let y = &x * &x;
+/// let v = Tensor::new([1.0, -1.0]);
+///
+/// // v^T J = v * 2x
+/// let vjp = grad_with(&[&y], &[&v], &[&x], false).remove(0);
+/// assert_eq!(vjp, Tensor::new([2.0, -4.0]));
+/// ```
+pub fn grad_with<T: TensorDataType>(outputs: &[&Tensor<T>],
+                                    grad_outputs: &[&Tensor<T>],
+                                    inputs: &[&Tensor<T>],
+                                    create_graph: bool) -> Vec<Tensor<'static, T>> {
+    assert_eq!(outputs.len(), grad_outputs.len(), "each output must have exactly one gradient");
+
+    let roots = outputs.iter().zip(grad_outputs)
+        .map(|(output, grad_output)| {
+            assert_eq!(output.shape(), grad_output.shape(), "the gradient of an output must have the same shape");
+            (output.grad_fn(), grad_output.alias())
+        })
+        .collect();
+
+    let input_functions: Vec<_> = inputs.iter()
+        .map(|input| {
+            assert!(input.requires_grad(), "cannot differentiate with respect to a tensor which does not require gradients");
+            input.grad_fn()
+        })
+        .collect();
+
+    run_grad(roots, &input_functions, create_graph).into_iter().zip(inputs)
+        .map(|(gradient, input)| gradient.unwrap_or_else(|| constant(NdArray::zeros(input.shape()))))
+        .collect()
+}
+
+/// Computes the Jacobian of `output` with respect to `input`.
+///
+/// The shape of the Jacobian is the shape of `output` followed by the shape of `input`,
+/// and each of its elements is the derivative of an element of `output` with respect to an element of `input`.
+///
+/// # Panics
+/// - If `input` does not require gradients
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut x = Tensor::new([1.0, 2.0]);
+/// x.set_requires_grad(true);
+///
+/// let y = &x * &x;
+/// assert_eq!(jacobian(&y, &x), NdArray::new([[2.0, 0.0], [0.0, 4.0]]));
+/// ```
+pub fn jacobian<T: TensorDataType>(output: &Tensor<T>, input: &Tensor<T>) -> NdArray<'static, T> {
+    let mut jacobian = Vec::with_capacity(output.size() * input.size());
+
+    // each row of the Jacobian is the vector-Jacobian product with a standard basis vector
+    for i in 0..output.size() {
+        let mut basis = vec![T::zero(); output.size()];
+        basis[i] = T::one();
+
+        let basis = constant(unsafe { NdArray::from_contiguous_owned_buffer(output.shape().to_vec(), basis) });
+        let row = grad_with(&[output], &[&basis], &[input], false).remove(0);
+
+        jacobian.extend(row.ndarray().flatiter());
+    }
+
+    let shape = [output.shape(), input.shape()].concat();
+    unsafe { NdArray::from_contiguous_owned_buffer(shape, jacobian) }
+}
+
+/// Computes the Hessian of the scalar `output` with respect to `input`.
+///
+/// The shape of the Hessian is the shape of `input` repeated twice.
+///
+/// # Panics
+/// - If `output` has more than one element
+/// - If `input` does not require gradients
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut x = Tensor::new([1.0, 2.0]);
+/// x.set_requires_grad(true);
+///
+/// // y = x0^2 x1
+/// let y = (&x * &x).dot(Tensor::new([1.0, 0.0])) * x.dot(Tensor::new([0.0, 1.0]));
+/// assert_eq!(hessian(&y, &x), NdArray::new([[4.0, 2.0], [2.0, 0.0]]));
+/// ```
+pub fn hessian<T: TensorDataType>(output: &Tensor<T>, input: &Tensor<T>) -> NdArray<'static, T> {
+    assert_eq!(output.size(), 1, "the Hessian is only defined for scalar outputs");
+
+    let gradient = grad(&[output], &[input], true).remove(0);
+    jacobian(&gradient, input)
+}
+
+/// Computes the product of the Hessian of the scalar `output` with respect to `input` and `vector`.
+///
+/// This is much cheaper than computing the full Hessian since it only requires 2 backward passes.
+///
+/// # Panics
+/// - If `output` has more than one element
+/// - If `input` does not require gradients
+/// - If the shape of `vector` differs from the shape of `input`
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut x = Tensor::new([1.0, 2.0]);
+/// x.set_requires_grad(true);
+///
+/// // the Hessian of sum(x^3) is diag(6x)
+/// let y = (&x * &x * &x).sum();
+/// let hvp = hessian_vector_product(&y, &x, NdArray::new([1.0, 1.0]));
+/// assert_eq!(hvp, NdArray::new([6.0, 12.0]));
+/// ```
+pub fn hessian_vector_product<'a, T: TensorDataType>(output: &Tensor<T>,
+                                                     input: &Tensor<T>,
+                                                     vector: impl AsRef<NdArray<'a, T>>) -> NdArray<'static, T> {
+    assert_eq!(output.size(), 1, "the Hessian is only defined for scalar outputs");
+
+    let vector = vector.as_ref();
+    assert_eq!(vector.shape(), input.shape(), "the vector must have the same shape as the input");
+
+    // the Hessian is symmetric, so the vector-Jacobian product of the gradient is the Hessian-vector product
+    let gradient = grad(&[output], &[input], true).remove(0);
+    let vector = constant(vector.clone());
+
+    grad_with(&[&gradient], &[&vector], &[input], false).remove(0).into_ndarray()
+}
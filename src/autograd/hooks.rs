@@ -0,0 +1,101 @@
+use crate::autograd::util::constant;
+use crate::{NdArray, StridedMemory, Tensor, TensorDataType};
+
+
+/// A hook which inspects the gradient flowing into a tensor and optionally replaces it.
+pub(crate) type GradientHook<T> = Box<dyn FnMut(&NdArray<T>) -> Option<NdArray<'static, T>> + Send>;
+
+/// A hook which runs after a gradient has been accumulated into a leaf tensor
+/// and may modify the accumulated gradient in place.
+pub(crate) type PostAccumulateGradHook<T> = Box<dyn FnMut(&mut NdArray<'static, T>) + Send>;
+
+/// The hooks registered on the tensor whose gradient is computed by a node of the graph.
+pub(crate) struct NodeHooks<T: TensorDataType> {
+    hooks: Vec<GradientHook<T>>,
+    post_accumulate_grad_hooks: Vec<PostAccumulateGradHook<T>>,
+
+    retains_grad: bool,
+    retained_grad: Option<Tensor<'static, T>>,
+}
+
+impl<T: TensorDataType> NodeHooks<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            hooks: Vec::new(),
+            post_accumulate_grad_hooks: Vec::new(),
+            retains_grad: false,
+            retained_grad: None,
+        }
+    }
+
+    pub(crate) fn register_hook(&mut self, hook: GradientHook<T>) {
+        self.hooks.push(hook);
+    }
+
+    pub(crate) fn register_post_accumulate_grad_hook(&mut self, hook: PostAccumulateGradHook<T>) {
+        self.post_accumulate_grad_hooks.push(hook);
+    }
+
+    pub(crate) fn has_post_accumulate_grad_hooks(&self) -> bool {
+        !self.post_accumulate_grad_hooks.is_empty()
+    }
+
+    pub(crate) fn retain_grad(&mut self) {
+        self.retains_grad = true;
+    }
+
+    pub(crate) fn retains_grad(&self) -> bool {
+        self.retains_grad
+    }
+
+    pub(crate) fn retained_grad(&self) -> Option<&Tensor<'static, T>> {
+        self.retained_grad.as_ref()
+    }
+
+    /// Sets the retained gradient, if any, to zero in place and removes it from the graph it was computed in.
+    pub(crate) fn zero_retained_grad(&mut self) {
+        if let Some(gradient) = self.retained_grad.as_mut() {
+            if gradient.requires_grad() {
+                *gradient = gradient.detached_alias();
+            }
+            gradient.ndarray_mut().zero();
+        }
+    }
+
+    /// Runs the hooks in the order they were registered on the total gradient flowing into the node
+    /// and returns the gradient to propagate.
+    ///
+    /// If `retain` is set and the tensor retains its gradient,
+    /// the resulting gradient is accumulated into the retained gradient.
+    ///
+    /// # Panics
+    /// - If a hook returns a gradient with a different shape
+    pub(crate) fn run(&mut self, mut grad: Tensor<'static, T>, retain: bool) -> Tensor<'static, T> {
+        for hook in self.hooks.iter_mut() {
+            if let Some(replacement) = hook(grad.ndarray()) {
+                assert_eq!(replacement.shape(), grad.shape(), "a gradient hook must not change the shape of the gradient");
+                grad = constant(replacement);
+            }
+        }
+
+        if retain && self.retains_grad {
+            self.retained_grad = Some(match self.retained_grad.take() {
+                None => grad.alias(),
+                Some(total) if total.requires_grad() || grad.requires_grad() => &total + &grad,
+                Some(mut total) => {
+                    *total.ndarray_mut() += grad.ndarray();
+                    total
+                }
+            });
+        }
+
+        grad
+    }
+
+    /// Runs the post-accumulate hooks in the order they were registered on the accumulated `gradient`.
+    pub(crate) fn run_post_accumulate_grad_hooks(&mut self, gradient: &mut NdArray<'static, T>) {
+        for hook in self.post_accumulate_grad_hooks.iter_mut() {
+            hook(gradient);
+        }
+    }
+}
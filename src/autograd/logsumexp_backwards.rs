@@ -0,0 +1,44 @@
+use crate::autograd::util::expand_gradient;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
+
+
+pub(crate) struct LogSumExpBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+
+    input: Tensor<'static, T>,
+    axes: Vec<usize>,
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for LogSumExpBackwards<T> {
+    /// The gradient of `logsumexp(x)` is `softmax(x) = exp(x - logsumexp(x))`.
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        let shape = self.input.shape();
+
+        // the output is recomputed from the input so that this gradient can itself be differentiated
+        let axes: Vec<isize> = self.axes.iter().map(|&axis| axis as isize).collect();
+        let output = expand_gradient(self.input.logsumexp_along(axes), &self.axes, shape);
+        let softmax = (&self.input - output).exp();
+
+        let grad = expand_gradient(grad, &self.axes, shape);
+        call_next_backward!(gradients, softmax * grad, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("input", &self.input)]
+    }
+}
+
+impl<T: FloatDataType> LogSumExpBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>, axes: Vec<usize>) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            input: input.alias(),
+            axes,
+        })
+    }
+}
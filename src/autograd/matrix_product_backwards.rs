@@ -1,25 +1,31 @@
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, FloatDataType, NdArray, Reshape, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, Reshape, Tensor};
 
 
 pub(crate) struct MatrixProductBackwards<T: FloatDataType> {
     pub(super) next_functions: [GradientFunction<T>; 2],
 
-    pub(super) lhs: Rc<NdArray<'static, T>>,
-    pub(super) rhs: Rc<NdArray<'static, T>>,
+    pub(super) lhs: Tensor<'static, T>,
+    pub(super) rhs: Tensor<'static, T>,
 }
 
 
 impl<T: FloatDataType> GradientFuncTrait<T> for MatrixProductBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(grad.matmul(self.rhs.as_ref().T()),
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, grad.matmul((&self.rhs).T()),
                             self.next_functions[0]);
 
-        call_next_backward!(self.lhs.as_ref().T().matmul(grad),
+        call_next_backward!(gradients, (&self.lhs).T().matmul(&grad),
                             self.next_functions[1]);
     }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("lhs", &self.lhs), ("rhs", &self.rhs)]
+    }
 }
 
 impl<T: FloatDataType> MatrixProductBackwards<T> {
@@ -27,10 +33,10 @@ impl<T: FloatDataType> MatrixProductBackwards<T> {
         let grad_fn = Self {
             next_functions: [lhs.grad_fn(), rhs.grad_fn()],
 
-            lhs: lhs.get_ndarray(),
-            rhs: rhs.get_ndarray(),
+            lhs: lhs.alias(),
+            rhs: rhs.alias(),
         };
 
-        Rc::new(RefCell::new(grad_fn))
+        GradientNode::new(grad_fn)
     }
 }
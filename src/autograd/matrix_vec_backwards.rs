@@ -1,34 +1,40 @@
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, FloatDataType, NdArray, Reshape, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, Reshape, Tensor};
 
 
 pub(crate) struct MatrixVecBackwards<T: FloatDataType> {
     pub(super) next_functions: [GradientFunction<T>; 2],
 
-    pub(super) matrix: Rc<NdArray<'static, T>>,
-    pub(super) vector: Rc<NdArray<'static, T>>,
+    pub(super) matrix: Tensor<'static, T>,
+    pub(super) vector: Tensor<'static, T>,
 }
 
 
 impl<T: FloatDataType> GradientFuncTrait<T> for MatrixVecBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(grad.unsqueeze(1) * self.vector.as_ref(),
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, (&grad).unsqueeze(1) * &self.vector,
                             self.next_functions[0]);
 
-        call_next_backward!(self.matrix.as_ref().T().matmul(grad),
+        call_next_backward!(gradients, (&self.matrix).T().matmul(&grad),
                             self.next_functions[1]);
     }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("matrix", &self.matrix), ("vector", &self.vector)]
+    }
 }
 
 impl<T: FloatDataType> MatrixVecBackwards<T> {
     pub(crate) fn new(matrix: &Tensor<T>, vector: &Tensor<T>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_functions: [matrix.grad_fn(), vector.grad_fn()],
 
-            matrix: matrix.get_ndarray(),
-            vector: vector.get_ndarray(),
-        }))
+            matrix: matrix.alias(),
+            vector: vector.alias(),
+        })
     }
 }
@@ -3,6 +3,11 @@
 pub(super) mod util;
 
 pub mod gradient_function;
+pub(crate) mod hooks;
+pub(crate) mod graph_dot;
+
+pub mod grad_mode;
+pub use grad_mode::*;
 
 pub mod none_backwards;
 pub mod identity_backwards;
@@ -20,4 +25,25 @@ pub mod matrix_product_backwards;
 pub mod bmm_backwards;
 
 pub mod reshape_backwards;
-pub mod transpose_backwards;
\ No newline at end of file
+pub mod transpose_backwards;
+pub mod expand_backwards;
+
+pub mod sum_backwards;
+pub mod var_backwards;
+pub mod cumsum_backwards;
+pub mod logsumexp_backwards;
+pub mod norm_backwards;
+
+pub mod exp_backwards;
+pub mod powf_backwards;
+
+pub mod higher_order;
+pub use higher_order::*;
+
+pub mod functional;
+
+pub mod custom_function;
+pub use custom_function::*;
+
+pub mod checkpoint;
+pub use checkpoint::*;
@@ -1,15 +1,13 @@
 use crate::autograd::util::reduce_gradient;
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, FloatDataType, NdArray, StridedMemory, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
 
 
 pub(crate) struct MulBackwards<T: FloatDataType> {
     next_functions: [GradientFunction<T>; 2],
 
-    lhs: Rc<NdArray<'static, T>>,
-    rhs: Rc<NdArray<'static, T>>,
+    lhs: Tensor<'static, T>,
+    rhs: Tensor<'static, T>,
 }
 
 pub(crate) struct MulScalarBackwards<T: FloatDataType> {
@@ -21,39 +19,51 @@ pub(crate) struct MulScalarBackwards<T: FloatDataType> {
 
 
 impl<T: FloatDataType> GradientFuncTrait<T> for MulBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(self.rhs.as_ref() * grad,
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, &self.rhs * &grad,
                             self.lhs.shape(), self.next_functions[0]);
 
-        call_next_backward!(self.lhs.as_ref() *
-                            grad, self.rhs.shape(), self.next_functions[1]);
+        call_next_backward!(gradients, &self.lhs * &grad,
+                            self.rhs.shape(), self.next_functions[1]);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("lhs", &self.lhs), ("rhs", &self.rhs)]
     }
 }
 
 impl<T: FloatDataType> GradientFuncTrait<T> for MulScalarBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(grad * self.scalar,
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, grad * self.scalar,
                             &self.shape, self.next_function);
     }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
 }
 
 
 impl<T: FloatDataType> MulBackwards<T> {
     pub(crate) fn new(lhs: &Tensor<T>, rhs: &Tensor<T>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_functions: [lhs.grad_fn(), rhs.grad_fn()],
-            lhs: lhs.get_ndarray(),
-            rhs: rhs.get_ndarray(),
-        }))
+            lhs: lhs.alias(),
+            rhs: rhs.alias(),
+        })
     }
 }
 
 impl<T: FloatDataType> MulScalarBackwards<T> {
     pub(crate) fn new(lhs: &Tensor<T>, rhs: T) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_function: lhs.grad_fn(),
             shape: lhs.shape().to_vec(),
             scalar: rhs
-        }))
+        })
     }
 }
@@ -1,8 +1,6 @@
 use crate::autograd::util::reduce_gradient;
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, FloatDataType, NdArray, StridedMemory, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
 
 pub(crate) struct NegBackwards<T: FloatDataType> {
     next_function: GradientFunction<T>,
@@ -10,16 +8,20 @@ pub(crate) struct NegBackwards<T: FloatDataType> {
 }
 
 impl<T: FloatDataType> GradientFuncTrait<T> for NegBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(-grad, &self.shape, self.next_function);
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, -grad, &self.shape, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
     }
 }
 
 impl<T: FloatDataType> NegBackwards<T> {
     pub(crate) fn new(rhs: &Tensor<T>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_function: rhs.grad_fn(),
             shape: rhs.shape().to_vec(),
-        }))
+        })
     }
 }
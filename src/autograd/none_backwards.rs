@@ -1,7 +1,5 @@
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{NdArray, TensorDataType};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{Tensor, TensorDataType};
 
 
 /// The default backwards node for non-leaf NdArrays.
@@ -9,7 +7,7 @@ pub(crate) struct NoneBackwards {}
 
 impl<T: TensorDataType> GradientFuncTrait<T> for NoneBackwards {
     /// Backwards method for ndarray with `requires_grad = false`, does nothing.
-    fn backward(&mut self, _: &NdArray<T>) {}
+    fn backward(&mut self, _: Tensor<'static, T>, _: &mut GradientBuffer<T>) {}
 
     fn is_none(&self) -> bool {
         true
@@ -18,6 +16,6 @@ impl<T: TensorDataType> GradientFuncTrait<T> for NoneBackwards {
 
 impl NoneBackwards {
     pub(crate) fn new<T: TensorDataType>() -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {}))
+        GradientNode::new(Self {})
     }
 }
@@ -0,0 +1,99 @@
+use crate::autograd::util::{constant, expand_gradient};
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, Constructors, FloatDataType, NdArray, Norm, StridedMemory, Tensor};
+
+
+pub(crate) struct NormBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+
+    input: Tensor<'static, T>,
+    ord: Norm<T>,
+    axes: Vec<usize>,
+}
+
+/// Returns the sign of `x` with `sign(0) = 0`.
+fn sign<T: FloatDataType>(x: T) -> T {
+    if x == T::zero() { T::zero() } else { x.signum() }
+}
+
+impl<T: FloatDataType> NormBackwards<T> {
+    /// Computes the derivative of the norm with respect to each element of the input.
+    ///
+    /// Where the norm is 0, the (sub)gradient is taken to be 0.
+    /// For the infinity norm, the gradient is split evenly between all maximal elements.
+    fn local_gradient(&self) -> Tensor<'static, T> {
+        let shape = self.input.shape();
+        let axes: Vec<isize> = self.axes.iter().map(|&axis| axis as isize).collect();
+
+        // the L1 and infinity norms are piecewise linear,
+        // so their local gradients are piecewise constant
+        let signs = self.input.ndarray().map(sign);
+
+        match self.ord {
+            Norm::L1 => constant(signs),
+            Norm::Inf => {
+                let norm = self.input.ndarray().norm_keepdims(Norm::Inf, axes.clone());
+                let maximal = self.input.ndarray().flatiter().zip(norm.broadcast_to(shape).flatiter())
+                    .map(|(x, norm)| norm != T::zero() && x.abs() == norm);
+
+                let local = signs.flatiter().zip(maximal)
+                    .map(|(sign, maximal)| if maximal { sign } else { T::zero() })
+                    .collect();
+
+                let local = unsafe { NdArray::from_contiguous_owned_buffer(shape.to_vec(), local) };
+                let ties = local.map(|x| x.abs()).sum_keepdims(axes).map(|n| n.max(T::one()));
+
+                constant(local / ties)
+            }
+            Norm::L2 => {
+                let (norm, zeros) = self.nonzero_norm(axes);
+                &self.input / (norm + zeros)
+            }
+            Norm::Lp(p) => {
+                let (norm, zeros) = self.nonzero_norm(axes);
+                let signs = constant(signs);
+
+                // where the norm is 0, the ratio is replaced by 1 so that its power stays finite
+                let ratio = &self.input * &signs / (norm + &zeros) + zeros;
+                ratio.powf(p - T::one()) * signs
+            }
+        }
+    }
+
+    /// Returns the norm expanded to the shape of the input along with a constant tensor
+    /// which is 1 wherever the norm is 0 and 0 elsewhere.
+    fn nonzero_norm(&self, axes: Vec<isize>) -> (Tensor<'static, T>, Tensor<'static, T>) {
+        let shape = self.input.shape();
+
+        let norm = expand_gradient(self.input.norm_along(self.ord, axes), &self.axes, shape);
+        let zeros = constant(norm.ndarray().map(|norm| if norm == T::zero() { T::one() } else { T::zero() }));
+
+        (norm, zeros)
+    }
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for NormBackwards<T> {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        let grad = expand_gradient(grad, &self.axes, self.input.shape());
+        call_next_backward!(gradients, self.local_gradient() * grad, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("input", &self.input)]
+    }
+}
+
+impl<T: FloatDataType> NormBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>, ord: Norm<T>, axes: Vec<usize>) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            input: input.alias(),
+            ord,
+            axes,
+        })
+    }
+}
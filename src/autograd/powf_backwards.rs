@@ -0,0 +1,38 @@
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, Tensor};
+
+
+/// Backwards function for raising each element to a constant power.
+///
+/// If `y = x^p`, then the gradient of `y` with respect to `x` is `p * x^(p - 1)`.
+pub(crate) struct PowfBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+
+    input: Tensor<'static, T>,
+    exponent: T,
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for PowfBackwards<T> {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        let local = self.input.powf(self.exponent - T::one()) * self.exponent;
+        call_next_backward!(gradients, local * grad, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("input", &self.input)]
+    }
+}
+
+impl<T: FloatDataType> PowfBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>, exponent: T) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            input: input.alias(),
+            exponent,
+        })
+    }
+}
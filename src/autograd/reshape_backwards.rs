@@ -1,8 +1,6 @@
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
 use crate::util::to_vec::ToVec;
-use crate::{call_next_backward, FloatDataType, NdArray, Reshape, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::{call_next_backward, FloatDataType, Reshape, StridedMemory, Tensor};
 
 pub(crate) struct ReshapeBackwards<T: FloatDataType> {
     next_function: GradientFunction<T>,
@@ -10,17 +8,25 @@ pub(crate) struct ReshapeBackwards<T: FloatDataType> {
 }
 
 impl<T: FloatDataType> GradientFuncTrait<T> for ReshapeBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(grad.reshape(&self.shape),
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        // the gradient may be a non-uniformly strided view (e.g. of a transpose)
+        // which cannot be reshaped without first copying it
+        let grad = if grad.is_uniformly_strided() { grad } else { grad.expand(grad.shape()) };
+
+        call_next_backward!(gradients, grad.reshape(&self.shape),
                             self.next_function);
     }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
 }
 
 impl<T: FloatDataType> ReshapeBackwards<T> {
     pub(crate) fn new(tensor: &Tensor<T>, old_shape: impl ToVec<usize>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_function: tensor.grad_fn(),
             shape: old_shape.to_vec(),
-        }))
+        })
     }
 }
@@ -1,8 +1,6 @@
 use crate::autograd::util::reduce_gradient;
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, FloatDataType, NdArray, StridedMemory, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
 
 
 pub(crate) struct SubBackwards<T: FloatDataType> {
@@ -14,20 +12,24 @@ pub(crate) struct SubBackwards<T: FloatDataType> {
 
 
 impl<T: FloatDataType> GradientFuncTrait<T> for SubBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(grad, &self.lhs_shape, self.next_functions[0]);
-        call_next_backward!(-grad, &self.rhs_shape, self.next_functions[1]);
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, &grad, &self.lhs_shape, self.next_functions[0]);
+        call_next_backward!(gradients, -grad, &self.rhs_shape, self.next_functions[1]);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        &self.next_functions
     }
 }
 
 
 impl<T: FloatDataType> SubBackwards<T> {
     pub(crate) fn new(lhs: &Tensor<T>, rhs: &Tensor<T>) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_functions: [lhs.grad_fn(), rhs.grad_fn()],
 
             lhs_shape: lhs.shape().to_vec(),
             rhs_shape: rhs.shape().to_vec()
-        }))
+        })
     }
 }
@@ -0,0 +1,35 @@
+use crate::autograd::util::expand_gradient;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
+
+
+/// Backwards function for summing along axes.
+///
+/// Each element contributes to its sum with a gradient of 1,
+/// so the gradient of the sum is repeated along the reduced axes.
+pub(crate) struct SumBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+
+    shape: Vec<usize>,
+    axes: Vec<usize>,
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for SumBackwards<T> {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, expand_gradient(grad, &self.axes, &self.shape), self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+}
+
+impl<T: FloatDataType> SumBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>, axes: Vec<usize>) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            shape: input.shape().to_vec(),
+            axes,
+        })
+    }
+}
@@ -1,7 +1,5 @@
-use crate::gradient_function::{GradientFuncTrait, GradientFunction};
-use crate::{call_next_backward, AxisType, FloatDataType, NdArray, Reshape, Tensor};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, AxisType, FloatDataType, Reshape, Tensor};
 
 pub(crate) struct TransposeBackwards<T: FloatDataType> {
     next_function: GradientFunction<T>,
@@ -11,18 +9,22 @@ pub(crate) struct TransposeBackwards<T: FloatDataType> {
 }
 
 impl<T: FloatDataType> GradientFuncTrait<T> for TransposeBackwards<T> {
-    fn backward(&mut self, grad: &NdArray<T>) {
-        call_next_backward!(grad.transpose(self.axis1, self.axis2),
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        call_next_backward!(gradients, grad.transpose(self.axis1, self.axis2),
                             self.next_function);
     }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
 }
 
 impl<T: FloatDataType> TransposeBackwards<T> {
     pub(crate) fn new(tensor: &Tensor<T>, axis1: impl AxisType, axis2: impl AxisType) -> GradientFunction<T> {
-        Rc::new(RefCell::new(Self {
+        GradientNode::new(Self {
             next_function: tensor.grad_fn(),
             axis1: axis1.isize(),
             axis2: axis2.isize(),
-        }))
+        })
     }
 }
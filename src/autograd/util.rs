@@ -1,11 +1,12 @@
 use crate::broadcast::get_broadcasted_axes;
-use crate::{FloatDataType, NdArray, Reshape, StridedMemory};
+use crate::none_backwards::NoneBackwards;
+use crate::{FloatDataType, NdArray, Reshape, StridedMemory, Tensor};
 
 
-pub(super) fn reduce_gradient<'a, T: FloatDataType>(grad: &'a NdArray<'a, T>,
-                                                    original_shape: &[usize]) -> NdArray<'a, T> {
+pub(super) fn reduce_gradient<T: FloatDataType>(grad: Tensor<'static, T>,
+                                                original_shape: &[usize]) -> Tensor<'static, T> {
     if grad.shape() == original_shape {
-        return grad.view()
+        return grad;
     }
 
     let axes = get_broadcasted_axes(grad.shape(), original_shape);
@@ -13,3 +14,27 @@ pub(super) fn reduce_gradient<'a, T: FloatDataType>(grad: &'a NdArray<'a, T>,
 
     grad.reshape(original_shape)
 }
+
+/// Expands the gradient of a reduction back to the shape of the reduction's input.
+///
+/// `axes` are the (absolute) axes that were reduced away. The gradient is repeated along these axes.
+pub(super) fn expand_gradient<T: FloatDataType>(grad: Tensor<'static, T>,
+                                                axes: &[usize],
+                                                original_shape: &[usize]) -> Tensor<'static, T> {
+    let mut grad_stride = grad.stride().iter();
+
+    // reinsert the reduced axes with length 1 so that the gradient broadcasts to the original shape
+    let (shape, stride) = original_shape.iter().enumerate()
+        .map(|(axis, &length)| {
+            if axes.contains(&axis) { (1, 0) } else { (length, *grad_stride.next().unwrap()) }
+        })
+        .unzip();
+
+    let grad = unsafe { grad.reshaped_view(shape, stride) };
+    grad.expand(original_shape)
+}
+
+/// Wraps `array` in a tensor which does not require gradients.
+pub(crate) fn constant<T: FloatDataType>(array: NdArray<'static, T>) -> Tensor<'static, T> {
+    unsafe { Tensor::from_raw_parts(array, false, NoneBackwards::new()) }
+}
@@ -0,0 +1,99 @@
+use crate::autograd::util::expand_gradient;
+use crate::gradient_function::{GradientBuffer, GradientFuncTrait, GradientFunction, GradientNode};
+use crate::{call_next_backward, FloatDataType, StridedMemory, Tensor};
+use num::NumCast;
+
+
+pub(crate) struct VarBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+
+    input: Tensor<'static, T>,
+    axes: Vec<usize>,
+    ddof: usize,
+}
+
+pub(crate) struct StdBackwards<T: FloatDataType> {
+    next_function: GradientFunction<T>,
+
+    input: Tensor<'static, T>,
+    axes: Vec<usize>,
+    ddof: usize,
+}
+
+/// Returns `(x - mean(x), N - ddof)` where the mean is taken along `axes`
+/// and `N` is the number of elements reduced into each output.
+fn centered_input<T: FloatDataType>(input: &Tensor<T>, axes: &[usize], ddof: usize) -> (Tensor<'static, T>, T) {
+    let n: usize = axes.iter().map(|&axis| input.shape()[axis]).product();
+    let n: T = NumCast::from(n).unwrap();
+    let ddof: T = NumCast::from(ddof).unwrap();
+
+    let mean = input.sum_along(axes_as_isize(axes)) / n;
+    let centered = input - expand_gradient(mean, axes, input.shape());
+
+    (centered, n - ddof)
+}
+
+fn axes_as_isize(axes: &[usize]) -> Vec<isize> {
+    axes.iter().map(|&axis| axis as isize).collect()
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for VarBackwards<T> {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        let (centered, divisor) = centered_input(&self.input, &self.axes, self.ddof);
+        let grad = expand_gradient(grad, &self.axes, self.input.shape());
+
+        let two = T::one() + T::one();
+        call_next_backward!(gradients, centered * grad * (two / divisor), self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("input", &self.input)]
+    }
+}
+
+impl<T: FloatDataType> GradientFuncTrait<T> for StdBackwards<T> {
+    fn backward(&mut self, grad: Tensor<'static, T>, gradients: &mut GradientBuffer<T>) {
+        let (centered, divisor) = centered_input(&self.input, &self.axes, self.ddof);
+
+        let std = self.input.std_along(axes_as_isize(&self.axes), self.ddof);
+        let grad = grad / std;
+        let grad = expand_gradient(grad, &self.axes, self.input.shape());
+
+        call_next_backward!(gradients, centered * grad / divisor, self.next_function);
+    }
+
+    fn next_functions(&self) -> &[GradientFunction<T>] {
+        std::slice::from_ref(&self.next_function)
+    }
+
+    fn saved_tensors(&self) -> Vec<(&'static str, &Tensor<'static, T>)> {
+        vec![("input", &self.input)]
+    }
+}
+
+
+impl<T: FloatDataType> VarBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>, axes: Vec<usize>, ddof: usize) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            input: input.alias(),
+            axes,
+            ddof,
+        })
+    }
+}
+
+impl<T: FloatDataType> StdBackwards<T> {
+    pub(crate) fn new(input: &Tensor<T>, axes: Vec<usize>, ddof: usize) -> GradientFunction<T> {
+        GradientNode::new(Self {
+            next_function: input.grad_fn(),
+            input: input.alias(),
+            axes,
+            ddof,
+        })
+    }
+}
@@ -2,7 +2,7 @@ use crate::util::flatten::Flatten;
 use crate::util::nested::Nested;
 use crate::util::shape::Shape;
 use crate::util::to_vec::ToVec;
-use crate::{FloatDataType, NumericDataType, RawDataType, StridedMemory};
+use crate::{FloatDataType, FromBool, NumericDataType, RawDataType, StridedMemory};
 use num::NumCast;
 
 pub trait Constructors<T: RawDataType>: StridedMemory {
@@ -92,9 +92,9 @@ pub trait Constructors<T: RawDataType>: StridedMemory {
     /// ```
     fn zeros(shape: impl ToVec<usize>) -> Self
     where
-        T: From<bool>
+        T: FromBool
     {
-        Self::full(false.into(), shape)
+        Self::full(T::from_bool(false), shape)
     }
 
     /// Creates a new ndarray filled with ones with the given shape.
@@ -114,9 +114,9 @@ pub trait Constructors<T: RawDataType>: StridedMemory {
     /// ```
     fn ones(shape: impl ToVec<usize>) -> Self
     where
-        T: From<bool>
+        T: FromBool
     {
-        Self::full(true.into(), shape)
+        Self::full(T::from_bool(true), shape)
     }
 
     /// Creates a 0-dimensional (shapeless) ndarray containing a single value.
@@ -0,0 +1,171 @@
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::sync::{Mutex, PoisonError};
+
+
+/// A seedable source of random numbers for the random constructors.
+///
+/// Generators use the ChaCha12 algorithm, so the numbers generated from a given seed
+/// are identical across runs and platforms.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut generator = Generator::new(42);
+/// let a = NdArray::<f32>::randn_with(&mut generator, [2, 3]);
+///
+/// let mut generator = Generator::new(42);
+/// let b = NdArray::<f32>::randn_with(&mut generator, [2, 3]);
+///
+/// assert_eq!(a, b);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Generator {
+    rng: ChaCha12Rng,
+    seed: u64,
+}
+
+/// A snapshot of the state of a `Generator`.
+///
+/// Restoring a state with `Generator::set_state()` resumes the sequence of random numbers
+/// from the point at which the state was saved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GeneratorState {
+    key: [u8; 32],
+    stream: u64,
+    word_pos: u128,
+    seed: u64,
+}
+
+impl Generator {
+    /// Creates a generator seeded with `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { rng: ChaCha12Rng::seed_from_u64(seed), seed }
+    }
+
+    /// Creates a generator with a seed taken from the operating system's source of randomness.
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// Reseeds the generator, restarting its sequence of random numbers.
+    pub fn manual_seed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    /// Returns the seed the generator was created or last reseeded with.
+    pub fn initial_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns a snapshot of the state of the generator.
+    pub fn get_state(&self) -> GeneratorState {
+        GeneratorState {
+            key: self.rng.get_seed(),
+            stream: self.rng.get_stream(),
+            word_pos: self.rng.get_word_pos(),
+            seed: self.seed,
+        }
+    }
+
+    /// Restores the generator to a state previously returned by `get_state()`.
+    pub fn set_state(&mut self, state: &GeneratorState) {
+        let mut rng = ChaCha12Rng::from_seed(state.key);
+        rng.set_stream(state.stream);
+        rng.set_word_pos(state.word_pos);
+
+        self.rng = rng;
+        self.seed = state.seed;
+    }
+}
+
+impl GeneratorState {
+    /// The number of bytes in the serialized form of a state.
+    pub const SIZE: usize = 64;
+
+    /// Serializes the state into little-endian bytes, e.g. to store it in a checkpoint.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[..32].copy_from_slice(&self.key);
+        bytes[32..40].copy_from_slice(&self.stream.to_le_bytes());
+        bytes[40..56].copy_from_slice(&self.word_pos.to_le_bytes());
+        bytes[56..].copy_from_slice(&self.seed.to_le_bytes());
+        bytes
+    }
+
+    /// Deserializes a state from bytes returned by `to_bytes()`.
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        Self {
+            key: bytes[..32].try_into().unwrap(),
+            stream: u64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+            word_pos: u128::from_le_bytes(bytes[40..56].try_into().unwrap()),
+            seed: u64::from_le_bytes(bytes[56..].try_into().unwrap()),
+        }
+    }
+}
+
+impl RngCore for Generator {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// The generator used by the random constructors when no generator is given.
+///
+/// It is seeded from the operating system's source of randomness until `manual_seed()` is called.
+static DEFAULT_GENERATOR: Mutex<Option<Generator>> = Mutex::new(None);
+
+/// Runs `func` with exclusive access to the default generator, which is shared by all threads.
+pub(crate) fn with_default_generator<R>(func: impl FnOnce(&mut Generator) -> R) -> R {
+    let mut generator = DEFAULT_GENERATOR.lock().unwrap_or_else(PoisonError::into_inner);
+    func(generator.get_or_insert_with(Generator::from_entropy))
+}
+
+/// Seeds the default generator used by the random constructors
+/// so that subsequent random arrays are reproducible.
+///
+/// The default generator is shared by all threads, so the numbers each thread receives
+/// depend on how the threads are scheduled. Use a `Generator` per thread for reproducible
+/// multithreaded code.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// manual_seed(7);
+/// let a = NdArray::<f64>::rand([4]);
+///
+/// manual_seed(7);
+/// let b = NdArray::<f64>::rand([4]);
+///
+/// assert_eq!(a, b);
+/// ```
+pub fn manual_seed(seed: u64) {
+    with_default_generator(|generator| generator.manual_seed(seed));
+}
+
+/// Returns the seed of the default generator.
+pub fn initial_seed() -> u64 {
+    with_default_generator(|generator| generator.initial_seed())
+}
+
+/// Returns a snapshot of the state of the default generator.
+pub fn get_rng_state() -> GeneratorState {
+    with_default_generator(|generator| generator.get_state())
+}
+
+/// Restores the default generator to a state previously returned by `get_rng_state()`.
+pub fn set_rng_state(state: &GeneratorState) {
+    with_default_generator(|generator| generator.set_state(state));
+}
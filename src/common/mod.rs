@@ -4,6 +4,9 @@ pub use constructors::*;
 pub mod random;
 pub use random::*;
 
+pub mod generator;
+pub use generator::*;
+
 pub mod methods;
 pub use methods::*;
 
@@ -11,3 +14,6 @@ pub mod reshape;
 pub use reshape::*;
 
 pub mod other;
+
+#[cfg(feature = "serde")]
+mod serialize;
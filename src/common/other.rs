@@ -1,4 +1,4 @@
-use crate::{NdArray, RawDataType, Tensor, TensorDataType};
+use crate::{DualTensor, NdArray, RawDataType, Tensor, TensorDataType};
 
 impl<'a, T: RawDataType> AsRef<NdArray<'a, T>> for NdArray<'a, T> {
     fn as_ref(&self) -> &NdArray<'a, T> {
@@ -11,3 +11,9 @@ impl<'a, T: TensorDataType> AsRef<Tensor<'a, T>> for Tensor<'a, T> {
         self
     }
 }
+
+impl<'a, T: TensorDataType> AsRef<DualTensor<'a, T>> for DualTensor<'a, T> {
+    fn as_ref(&self) -> &DualTensor<'a, T> {
+        self
+    }
+}
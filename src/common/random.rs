@@ -1,9 +1,12 @@
+use crate::generator::with_default_generator;
 use crate::util::to_vec::ToVec;
-use crate::{Constructors, FloatDataType, NdArray, NumericDataType, RawDataType, Tensor, TensorDataType};
+use crate::dtype::FromBool;
+use crate::{Constructors, Generator, FloatDataType, NdArray, NumericDataType, RawDataType, Tensor, TensorDataType};
 use num::{Float, NumCast};
-use rand::distributions::{Distribution, Uniform};
-use rand::thread_rng;
-use rand_distr::Normal;
+use rand::distributions::{Bernoulli, Distribution, Uniform};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_distr::{Beta, Exp, Gamma, Normal, Poisson};
 
 pub trait RandomConstructors<T: RawDataType>: Constructors<T> {
     /// Samples an `NdArray` with the specified shape
@@ -20,14 +23,31 @@ pub trait RandomConstructors<T: RawDataType>: Constructors<T> {
     where
         T: FloatDataType
     {
-        let mut rng = thread_rng();
+        with_default_generator(|generator| Self::randn_with(generator, shape))
+    }
+
+    /// Samples an `NdArray` with the specified shape from a standard normal distribution
+    /// using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<f64>::randn_with(&mut generator, [2, 3]);
+    /// println!("{:?}", ndarray);
+    /// ```
+    fn randn_with(generator: &mut Generator, shape: impl ToVec<usize>) -> Self
+    where
+        T: FloatDataType
+    {
         let shape = shape.to_vec();
         let n = shape.iter().product();
 
         let normal = Normal::new(0.0, 1.0).unwrap();
 
         let random_numbers: Vec<T> = (0..n)
-            .map(|_| <T as NumCast>::from(normal.sample(&mut rng)).unwrap())
+            .map(|_| <T as NumCast>::from(normal.sample(generator)).unwrap())
             .collect();
 
         unsafe { Self::from_contiguous_owned_buffer(shape, random_numbers) }
@@ -47,13 +67,30 @@ pub trait RandomConstructors<T: RawDataType>: Constructors<T> {
     where
         T: FloatDataType
     {
-        let mut rng = thread_rng();
+        with_default_generator(|generator| Self::rand_with(generator, shape))
+    }
+
+    /// Samples an `NdArray` with the specified shape with values uniformly distributed in [0, 1)
+    /// using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<f64>::rand_with(&mut generator, [2, 3]);
+    /// println!("{:?}", ndarray);
+    /// ```
+    fn rand_with(generator: &mut Generator, shape: impl ToVec<usize>) -> Self
+    where
+        T: FloatDataType
+    {
         let shape = shape.to_vec();
         let n = shape.iter().product();
 
         let uniform = Uniform::new(0.0, 1.0);
         let random_numbers = (0..n)
-            .map(|_| <T as NumCast>::from(uniform.sample(&mut rng)).unwrap())
+            .map(|_| <T as NumCast>::from(uniform.sample(generator)).unwrap())
             .collect();
 
         unsafe { Self::from_contiguous_owned_buffer(shape, random_numbers) }
@@ -73,13 +110,30 @@ pub trait RandomConstructors<T: RawDataType>: Constructors<T> {
     where
         T: FloatDataType
     {
-        let mut rng = thread_rng();
+        with_default_generator(|generator| Self::uniform_with(generator, shape, low, high))
+    }
+
+    /// Samples an `NdArray` with the specified shape with values uniformly distributed
+    /// in [`low`, `high`) using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<f64>::uniform_with(&mut generator, [2, 3], -5.0, 3.0);
+    /// println!("{:?}", ndarray);
+    /// ```
+    fn uniform_with(generator: &mut Generator, shape: impl ToVec<usize>, low: T, high: T) -> Self
+    where
+        T: FloatDataType
+    {
         let shape = shape.to_vec();
         let n = shape.iter().product();
 
         let uniform = Uniform::new(low, high);
         let random_numbers = (0..n)
-            .map(|_| <T as NumCast>::from(uniform.sample(&mut rng)).unwrap())
+            .map(|_| <T as NumCast>::from(uniform.sample(generator)).unwrap())
             .collect();
 
         unsafe { Self::from_contiguous_owned_buffer(shape, random_numbers) }
@@ -96,23 +150,445 @@ pub trait RandomConstructors<T: RawDataType>: Constructors<T> {
     /// println!("{:?}", ndarray);
     /// ```
     fn randint(shape: impl ToVec<usize>, low: T, high: T) -> Self
+    where
+        T: NumericDataType
+    {
+        with_default_generator(|generator| Self::randint_with(generator, shape, low, high))
+    }
+
+    /// Samples an `NdArray` with the specified shape with integer values uniformly distributed
+    /// between `low` (inclusive) and `high` (exclusive) using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<isize>::randint_with(&mut generator, [2, 3], -5, 3);
+    /// println!("{:?}", ndarray);
+    /// ```
+    fn randint_with(generator: &mut Generator, shape: impl ToVec<usize>, low: T, high: T) -> Self
     where
         T: NumericDataType
     {
         assert!(low < high, "randint: low must be less than high");
 
-        let mut rng = thread_rng();
         let shape = shape.to_vec();
         let n = shape.iter().product();
 
         let uniform = Uniform::new(low.to_float(), high.to_float());
         let random_numbers = (0..n)
-            .map(|_| <T as NumCast>::from(uniform.sample(&mut rng).round()).unwrap())
+            .map(|_| <T as NumCast>::from(uniform.sample(generator).round()).unwrap())
+            .collect();
+
+        unsafe { Self::from_contiguous_owned_buffer(shape, random_numbers) }
+    }
+
+    /// Samples an `NdArray` with the specified shape
+    /// from a normal distribution with the given `mean` and standard deviation `std`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let ndarray = NdArray::<f64>::normal([2, 3], 1.0, 0.5);
+    /// println!("{:?}", ndarray);
+    /// ```
+    fn normal(shape: impl ToVec<usize>, mean: T, std: T) -> Self
+    where
+        T: FloatDataType
+    {
+        with_default_generator(|generator| Self::normal_with(generator, shape, mean, std))
+    }
+
+    /// Samples an `NdArray` with the specified shape
+    /// from a normal distribution with the given `mean` and standard deviation `std`
+    /// using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<f64>::normal_with(&mut generator, [2, 3], 1.0, 0.5);
+    /// println!("{:?}", ndarray);
+    /// ```
+    fn normal_with(generator: &mut Generator, shape: impl ToVec<usize>, mean: T, std: T) -> Self
+    where
+        T: FloatDataType
+    {
+        let normal = Normal::new(mean.to_f64().unwrap(), std.to_f64().unwrap())
+            .expect("normal: std must be finite and non-negative");
+
+        sample_into(generator, shape, normal)
+    }
+
+    /// Samples an `NdArray` with the specified shape from a normal distribution with the given `mean`
+    /// and standard deviation `std`, truncated to [`low`, `high`].
+    ///
+    /// Values are drawn by inverting the cumulative distribution function, so the bounds may lie
+    /// arbitrarily far in the tails of the distribution.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// // a common initialization for the weights of a layer
+    /// let ndarray = NdArray::<f32>::truncated_normal([2, 3], 0.0, 0.02, -0.04, 0.04);
+    /// assert!(ndarray.flatiter().all(|x| x.abs() <= 0.04));
+    /// ```
+    fn truncated_normal(shape: impl ToVec<usize>, mean: T, std: T, low: T, high: T) -> Self
+    where
+        T: FloatDataType
+    {
+        with_default_generator(|generator| Self::truncated_normal_with(generator, shape, mean, std, low, high))
+    }
+
+    /// Samples an `NdArray` with the specified shape from a normal distribution with the given `mean`
+    /// and standard deviation `std`, truncated to [`low`, `high`], using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<f32>::truncated_normal_with(&mut generator, [2, 3], 0.0, 0.02, -0.04, 0.04);
+    /// assert!(ndarray.flatiter().all(|x| x.abs() <= 0.04));
+    /// ```
+    fn truncated_normal_with(generator: &mut Generator, shape: impl ToVec<usize>, mean: T, std: T, low: T, high: T) -> Self
+    where
+        T: FloatDataType
+    {
+        assert!(low < high, "truncated_normal: low must be less than high");
+
+        let (mean, std) = (mean.to_f64().unwrap(), std.to_f64().unwrap());
+        assert!(std.is_finite() && std >= 0.0, "truncated_normal: std must be finite and non-negative");
+
+        let (low, high) = (low.to_f64().unwrap(), high.to_f64().unwrap());
+
+        let shape = shape.to_vec();
+        let random_numbers = (0..shape.iter().product())
+            .map(|_| sample_truncated_normal(generator, mean, std, low, high))
+            .map(|x| <T as NumCast>::from(x).unwrap())
             .collect();
 
         unsafe { Self::from_contiguous_owned_buffer(shape, random_numbers) }
     }
+
+    /// Samples an `NdArray` with the specified shape whose values are 1 (or `true`) with probability `p`
+    /// and 0 (or `false`) otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mask = NdArray::<bool>::bernoulli([2, 3], 0.8);
+    /// println!("{:?}", mask);
+    /// ```
+    fn bernoulli(shape: impl ToVec<usize>, p: f64) -> Self
+    where
+        T: FromBool
+    {
+        with_default_generator(|generator| Self::bernoulli_with(generator, shape, p))
+    }
+
+    /// Samples an `NdArray` with the specified shape whose values are 1 (or `true`) with probability `p`
+    /// and 0 (or `false`) otherwise, using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let mask = NdArray::<f32>::bernoulli_with(&mut generator, [2, 3], 0.8);
+    /// println!("{:?}", mask);
+    /// ```
+    fn bernoulli_with(generator: &mut Generator, shape: impl ToVec<usize>, p: f64) -> Self
+    where
+        T: FromBool
+    {
+        let bernoulli = Bernoulli::new(p).expect("bernoulli: p must be in [0, 1]");
+
+        let shape = shape.to_vec();
+        let random_numbers = bernoulli.sample_iter(generator).take(shape.iter().product())
+            .map(T::from_bool)
+            .collect();
+
+        unsafe { Self::from_contiguous_owned_buffer(shape, random_numbers) }
+    }
+
+    /// Samples an `NdArray` with the specified shape from an exponential distribution with the given `rate`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let ndarray = NdArray::<f64>::exponential([2, 3], 2.0);
+    /// assert!(ndarray.flatiter().all(|x| x >= 0.0));
+    /// ```
+    fn exponential(shape: impl ToVec<usize>, rate: T) -> Self
+    where
+        T: FloatDataType
+    {
+        with_default_generator(|generator| Self::exponential_with(generator, shape, rate))
+    }
+
+    /// Samples an `NdArray` with the specified shape from an exponential distribution with the given `rate`
+    /// using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<f64>::exponential_with(&mut generator, [2, 3], 2.0);
+    /// assert!(ndarray.flatiter().all(|x| x >= 0.0));
+    /// ```
+    fn exponential_with(generator: &mut Generator, shape: impl ToVec<usize>, rate: T) -> Self
+    where
+        T: FloatDataType
+    {
+        let exponential = Exp::new(rate.to_f64().unwrap()).expect("exponential: rate must be non-negative");
+        sample_into(generator, shape, exponential)
+    }
+
+    /// Samples an `NdArray` with the specified shape from a gamma distribution
+    /// with the given `concentration` (shape parameter) and `scale`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let ndarray = NdArray::<f64>::gamma([2, 3], 2.0, 0.5);
+    /// assert!(ndarray.flatiter().all(|x| x >= 0.0));
+    /// ```
+    fn gamma(shape: impl ToVec<usize>, concentration: T, scale: T) -> Self
+    where
+        T: FloatDataType
+    {
+        with_default_generator(|generator| Self::gamma_with(generator, shape, concentration, scale))
+    }
+
+    /// Samples an `NdArray` with the specified shape from a gamma distribution
+    /// with the given `concentration` (shape parameter) and `scale`
+    /// using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<f64>::gamma_with(&mut generator, [2, 3], 2.0, 0.5);
+    /// assert!(ndarray.flatiter().all(|x| x >= 0.0));
+    /// ```
+    fn gamma_with(generator: &mut Generator, shape: impl ToVec<usize>, concentration: T, scale: T) -> Self
+    where
+        T: FloatDataType
+    {
+        let gamma = Gamma::new(concentration.to_f64().unwrap(), scale.to_f64().unwrap())
+            .expect("gamma: concentration and scale must be positive");
+
+        sample_into(generator, shape, gamma)
+    }
+
+    /// Samples an `NdArray` with the specified shape from a beta distribution
+    /// with the given `alpha` and `beta` parameters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let ndarray = NdArray::<f64>::beta([2, 3], 2.0, 5.0);
+    /// assert!(ndarray.flatiter().all(|x| (0.0..=1.0).contains(&x)));
+    /// ```
+    fn beta(shape: impl ToVec<usize>, alpha: T, beta: T) -> Self
+    where
+        T: FloatDataType
+    {
+        with_default_generator(|generator| Self::beta_with(generator, shape, alpha, beta))
+    }
+
+    /// Samples an `NdArray` with the specified shape from a beta distribution
+    /// with the given `alpha` and `beta` parameters
+    /// using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let ndarray = NdArray::<f64>::beta_with(&mut generator, [2, 3], 2.0, 5.0);
+    /// assert!(ndarray.flatiter().all(|x| (0.0..=1.0).contains(&x)));
+    /// ```
+    fn beta_with(generator: &mut Generator, shape: impl ToVec<usize>, alpha: T, beta: T) -> Self
+    where
+        T: FloatDataType
+    {
+        let beta = Beta::new(alpha.to_f64().unwrap(), beta.to_f64().unwrap())
+            .expect("beta: alpha and beta must be positive");
+
+        sample_into(generator, shape, beta)
+    }
+
+    /// Samples an `NdArray` with the specified shape from a Poisson distribution with the given `rate`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let counts = NdArray::<u32>::poisson([2, 3], 4.0);
+    /// println!("{:?}", counts);
+    /// ```
+    fn poisson(shape: impl ToVec<usize>, rate: f64) -> Self
+    where
+        T: NumericDataType
+    {
+        with_default_generator(|generator| Self::poisson_with(generator, shape, rate))
+    }
+
+    /// Samples an `NdArray` with the specified shape from a Poisson distribution with the given `rate`
+    /// using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let counts = NdArray::<u32>::poisson_with(&mut generator, [2, 3], 4.0);
+    /// println!("{:?}", counts);
+    /// ```
+    fn poisson_with(generator: &mut Generator, shape: impl ToVec<usize>, rate: f64) -> Self
+    where
+        T: NumericDataType
+    {
+        let poisson = Poisson::new(rate).expect("poisson: rate must be positive");
+        sample_into(generator, shape, poisson)
+    }
+
+    /// Returns a random permutation of the integers in [0, `n`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let permutation = NdArray::<i32>::permutation(5);
+    /// assert_eq!(permutation.sort(0), NdArray::new([0, 1, 2, 3, 4]));
+    /// ```
+    fn permutation(n: usize) -> Self
+    where
+        T: NumericDataType
+    {
+        with_default_generator(|generator| Self::permutation_with(generator, n))
+    }
+
+    /// Returns a random permutation of the integers in [0, `n`)
+    /// using the random numbers of `generator`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut generator = Generator::new(0);
+    /// let permutation = NdArray::<usize>::permutation_with(&mut generator, 5);
+    /// println!("{:?}", permutation);
+    /// ```
+    fn permutation_with(generator: &mut Generator, n: usize) -> Self
+    where
+        T: NumericDataType
+    {
+        let mut permutation: Vec<T> = (0..n).map(|i| <T as NumCast>::from(i).unwrap()).collect();
+        permutation.shuffle(generator);
+
+        unsafe { Self::from_contiguous_owned_buffer(vec![n], permutation) }
+    }
 }
 
 impl<'a, T: RawDataType> RandomConstructors<T> for NdArray<'a, T> {}
 impl<'a, T: TensorDataType> RandomConstructors<T> for Tensor<'a, T> {}
+
+/// Draws a sample from a normal distribution with the given `mean` and `std`, truncated to [`low`, `high`].
+///
+/// A uniform sample between the cumulative probabilities of the bounds is mapped through the inverse
+/// of the cumulative distribution function. Bounds in the upper tail are mirrored into the lower tail,
+/// where the probabilities are small rather than close to 1 and so do not lose precision.
+fn sample_truncated_normal(generator: &mut Generator, mean: f64, std: f64, low: f64, high: f64) -> f64 {
+    if std == 0.0 {
+        return mean.clamp(low, high);
+    }
+
+    let (mut a, mut b) = ((low - mean) / std, (high - mean) / std);
+    let mirrored = a > 0.0;
+    if mirrored {
+        (a, b) = (-b, -a);
+    }
+
+    let (pa, pb) = (normal_cdf(a), normal_cdf(b));
+    let p = pa + (pb - pa) * generator.gen::<f64>();
+
+    let x = normal_cdf_inverse(p).clamp(a, b);
+    let x = if mirrored { -x } else { x };
+
+    (mean + std * x).clamp(low, high)
+}
+
+/// The cumulative distribution function of the standard normal distribution.
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * libm::erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// The inverse of the cumulative distribution function of the standard normal distribution.
+///
+/// Uses Acklam's rational approximation followed by a step of Halley's method,
+/// which gives full double precision.
+fn normal_cdf_inverse(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+                         1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00];
+    const B: [f64; 5] = [-5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+                         6.680131188771972e+01, -1.328068155288572e+01];
+    const C: [f64; 6] = [-7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+                         -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00];
+    const D: [f64; 4] = [7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+                         3.754408661907416e+00];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let x = if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+            ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q /
+            (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        let q = (-2.0 * (1.0 - p).ln()).sqrt();
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+            ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    // refine the approximation with one step of Halley's method
+    let error = normal_cdf(x) - p;
+    let u = error * (2.0 * std::f64::consts::PI).sqrt() * (x * x / 2.0).exp();
+    x - u / (1.0 + x * u / 2.0)
+}
+
+/// Draws `shape.product()` samples from `distribution` into a buffer of type `T`.
+fn sample_into<T, R>(generator: &mut Generator, shape: impl ToVec<usize>, distribution: impl Distribution<f64>) -> R
+where
+    T: RawDataType + NumCast,
+    R: Constructors<T>,
+{
+    let shape = shape.to_vec();
+    let random_numbers = distribution.sample_iter(generator).take(shape.iter().product())
+        .map(|x| <T as NumCast>::from(x).unwrap())
+        .collect();
+
+    unsafe { R::from_contiguous_owned_buffer(shape, random_numbers) }
+}
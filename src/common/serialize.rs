@@ -0,0 +1,90 @@
+//! `serde` support for `NdArray` and `Tensor`, enabled by the `serde` feature.
+//!
+//! Arrays are represented as a struct with the name of their `dtype`, their `shape`
+//! and their elements in logical (row-major) order as `data`. Tensors additionally
+//! record whether they require gradients, but not their gradients or graph.
+
+use crate::{Constructors, NdArray, RawDataType, StridedMemory, Tensor, TensorDataType};
+use serde::de::Error;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+
+/// The elements of an array in logical order, serialized as a sequence without being copied.
+struct FlatData<'a, 'b, T: RawDataType>(&'a NdArray<'b, T>);
+
+impl<T: RawDataType + Serialize> Serialize for FlatData<'_, '_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.flatiter())
+    }
+}
+
+impl<T: RawDataType + Serialize> Serialize for NdArray<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("NdArray", 3)?;
+        state.serialize_field("dtype", T::DTYPE.name())?;
+        state.serialize_field("shape", self.shape())?;
+        state.serialize_field("data", &FlatData(self))?;
+        state.end()
+    }
+}
+
+impl<T: TensorDataType + Serialize> Serialize for Tensor<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Tensor", 4)?;
+        state.serialize_field("dtype", T::DTYPE.name())?;
+        state.serialize_field("shape", self.shape())?;
+        state.serialize_field("data", &FlatData(self.ndarray()))?;
+        state.serialize_field("requires_grad", &self.requires_grad())?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "NdArray")]
+struct NdArrayRepr<T> {
+    dtype: String,
+    shape: Vec<usize>,
+    data: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Tensor")]
+struct TensorRepr<T> {
+    dtype: String,
+    shape: Vec<usize>,
+    data: Vec<T>,
+
+    #[serde(default)]
+    requires_grad: bool,
+}
+
+/// Validates the deserialized fields of an array and constructs it.
+fn into_ndarray<T: RawDataType, E: Error>(dtype: &str, shape: Vec<usize>, data: Vec<T>) -> Result<NdArray<'static, T>, E> {
+    if dtype != T::DTYPE.name() {
+        return Err(E::custom(format!("expected an array of dtype {} but found {dtype}", T::DTYPE.name())));
+    }
+
+    let size = shape.iter().try_fold(1usize, |size, &dim| size.checked_mul(dim));
+    if size != Some(data.len()) {
+        return Err(E::custom(format!("an array of shape {shape:?} cannot have {} elements", data.len())));
+    }
+
+    Ok(unsafe { NdArray::from_contiguous_owned_buffer(shape, data) })
+}
+
+impl<'de, T: RawDataType + Deserialize<'de>> Deserialize<'de> for NdArray<'_, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = NdArrayRepr::<T>::deserialize(deserializer)?;
+        into_ndarray(&repr.dtype, repr.shape, repr.data)
+    }
+}
+
+impl<'de, T: TensorDataType + Deserialize<'de>> Deserialize<'de> for Tensor<'_, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = TensorRepr::<T>::deserialize(deserializer)?;
+        let array = into_ndarray(&repr.dtype, repr.shape, repr.data)?;
+
+        Ok(unsafe { Tensor::from_array_and_flags(array, repr.requires_grad, true) })
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{DualTensor, TensorDataType};
+
+impl<'a, T: TensorDataType> DualTensor<'a, T> {
+    /// Calculates the dot product of two 1D dual tensors.
+    ///
+    /// # Panics
+    /// - Panics if either dual tensor is not 1D
+    /// - Panics if the lengths of the two dual tensors are not equal
+    ///
+    /// # Examples
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([1.0, 2.0, 3.0]), NdArray::new([1.0, 0.0, 0.0]));
+    /// let result = x.dot(&x);
+    ///
+    /// assert_eq!(result.value(), 14.0);
+    /// assert_eq!(result.tangent_value(), 2.0);
+    /// ```
+    pub fn dot<'b, 'r>(&self, other: impl AsRef<DualTensor<'b, T>>) -> DualTensor<'r, T> {
+        let other = other.as_ref();
+
+        let primal = self.primal.dot(&other.primal);
+        let tangent = self.tangent.dot(&other.primal) + self.primal.dot(&other.tangent);
+
+        DualTensor::new(primal, tangent)
+    }
+
+    /// Calculates the matrix product of two dual tensors.
+    ///
+    /// - If both dual tensors are 1D, then their dot product is returned.
+    /// - If both dual tensors are 2D, then their matrix product is returned.
+    /// - If the first dual tensor is 2D and the second is 1D, then the matrix-vector product is returned.
+    ///
+    /// # Panics
+    /// - If the dimensions/shape of the dual tensors are incompatible
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let a = DualTensor::new(NdArray::new([[1.0, 2.0], [3.0, 4.0]]), NdArray::new([[1.0, 0.0], [0.0, 0.0]]));
+    /// let x = DualTensor::constant(NdArray::new([5.0, 6.0]));
+    ///
+    /// let result = a.matmul(&x);
+    /// assert_eq!(result.primal(), &NdArray::new([17.0, 39.0]));
+    /// assert_eq!(result.tangent(), &NdArray::new([5.0, 0.0]));
+    /// ```
+    pub fn matmul<'b, 'r>(&self, other: impl AsRef<DualTensor<'b, T>>) -> DualTensor<'r, T> {
+        let other = other.as_ref();
+
+        let primal = self.primal.matmul(&other.primal);
+        let tangent = self.tangent.matmul(&other.primal) + self.primal.matmul(&other.tangent);
+
+        DualTensor::new(primal, tangent)
+    }
+
+    /// Performs batch matrix multiplication on 3D dual tensors.
+    ///
+    /// The shape of the result will be `[batch_size, self.shape()[1], other.shape()[2]]`,
+    /// where `batch_size` is the shared first dimension of both input dual tensors.
+    ///
+    /// # Panics
+    /// - If either dual tensor is not 3D
+    /// - If the dual tensors do not have dimensions compatible for batch matrix multiplication.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let a = DualTensor::constant(NdArray::<f32>::rand([3, 2, 4]));
+    /// let b = DualTensor::new(NdArray::<f32>::rand([3, 4, 5]), NdArray::ones([3, 4, 5]));
+    ///
+    /// let result = a.bmm(&b);
+    /// assert_eq!(result.shape(), [3, 2, 5]);
+    /// ```
+    pub fn bmm<'b, 'r>(&self, other: impl AsRef<DualTensor<'b, T>>) -> DualTensor<'r, T> {
+        let other = other.as_ref();
+
+        let primal = self.primal.bmm(&other.primal);
+        let tangent = self.tangent.bmm(&other.primal) + self.primal.bmm(&other.tangent);
+
+        DualTensor::new(primal, tangent)
+    }
+}
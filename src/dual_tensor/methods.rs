@@ -0,0 +1,136 @@
+use crate::ndarray::flags::NdArrayFlags;
+use crate::{Constructors, DualTensor, NdArray, StridedMemory, TensorDataType};
+
+impl<'a, T: TensorDataType> DualTensor<'a, T> {
+    /// Constructs a new dual tensor from its primal value and its tangent.
+    ///
+    /// # Panics
+    /// - If the shapes of `primal` and `tangent` differ
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([1.0, 2.0]), NdArray::new([1.0, 1.0]));
+    /// let y = &x * &x;
+    ///
+    /// assert_eq!(y.tangent(), &NdArray::new([2.0, 4.0]));
+    /// ```
+    pub fn new(primal: NdArray<'a, T>, tangent: NdArray<'a, T>) -> Self {
+        assert_eq!(primal.shape(), tangent.shape(), "the primal and tangent must have the same shape");
+
+        // views of a dual tensor are taken with the stride of its primal,
+        // so the primal and tangent must share the same memory layout
+        if primal.stride() != tangent.stride() {
+            return Self { primal: primal.clone(), tangent: tangent.clone() };
+        }
+
+        Self { primal, tangent }
+    }
+
+    /// Constructs a new dual tensor with a tangent of 0,
+    /// i.e. a constant with respect to the direction of differentiation.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::constant(NdArray::new([1.0, 2.0]));
+    /// assert_eq!(x.tangent(), &NdArray::new([0.0, 0.0]));
+    /// ```
+    pub fn constant(primal: NdArray<'a, T>) -> Self {
+        let tangent = NdArray::zeros(primal.shape());
+        Self::new(primal, tangent)
+    }
+
+    /// Returns a reference to the primal value of this dual tensor.
+    #[inline]
+    pub fn primal(&self) -> &NdArray<'a, T> {
+        &self.primal
+    }
+
+    /// Returns a reference to the tangent of this dual tensor.
+    #[inline]
+    pub fn tangent(&self) -> &NdArray<'a, T> {
+        &self.tangent
+    }
+
+    /// Consumes this dual tensor and returns its primal value and tangent.
+    pub fn into_parts(self) -> (NdArray<'a, T>, NdArray<'a, T>) {
+        (self.primal, self.tangent)
+    }
+
+    /// Returns the primal value of this scalar dual tensor.
+    ///
+    /// # Panics
+    /// - If the dual tensor is not a scalar
+    pub fn value(&self) -> T {
+        self.primal.value()
+    }
+
+    /// Returns the tangent of this scalar dual tensor.
+    ///
+    /// # Panics
+    /// - If the dual tensor is not a scalar
+    pub fn tangent_value(&self) -> T {
+        self.tangent.value()
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<T: TensorDataType> StridedMemory for DualTensor<'_, T> {
+    /// Returns the dimensions of the dual tensor along each axis.
+    ///
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let a = DualTensor::constant(NdArray::new([3.0, 4.0, 5.0]));
+    /// assert_eq!(a.shape(), &[3]);
+    /// ```
+    #[inline]
+    fn shape(&self) -> &[usize] {
+        self.primal.shape()
+    }
+
+    /// Returns the stride of the dual tensor.
+    ///
+    /// The primal value and tangent always share the same stride.
+    #[inline]
+    fn stride(&self) -> &[usize] {
+        self.primal.stride()
+    }
+
+    /// Returns flags containing information about various dual tensor metadata.
+    #[inline]
+    fn flags(&self) -> NdArrayFlags {
+        self.primal.flags()
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<T: TensorDataType> StridedMemory for &DualTensor<'_, T> {
+    /// Returns the dimensions of the dual tensor along each axis.
+    ///
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let a = DualTensor::constant(NdArray::new([3.0, 4.0, 5.0]));
+    /// assert_eq!((&a).shape(), &[3]);
+    /// ```
+    #[inline]
+    fn shape(&self) -> &[usize] {
+        self.primal.shape()
+    }
+
+    /// Returns the stride of the dual tensor.
+    ///
+    /// The primal value and tangent always share the same stride.
+    #[inline]
+    fn stride(&self) -> &[usize] {
+        self.primal.stride()
+    }
+
+    /// Returns flags containing information about various dual tensor metadata.
+    #[inline]
+    fn flags(&self) -> NdArrayFlags {
+        self.primal.flags()
+    }
+}
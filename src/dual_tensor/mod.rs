@@ -0,0 +1,35 @@
+//! # Forward-Mode Automatic Differentiation with Dual Tensors
+//!
+//! A `DualTensor` pairs a primal value with a tangent of the same shape. Every operation on dual
+//! tensors computes its result along with the directional derivative of that result in the
+//! direction of the input tangents, so a single forward pass yields a Jacobian-vector product.
+//!
+//! Forward mode is far cheaper than reverse mode for functions with few inputs and many outputs,
+//! and requires no computation graph to be stored.
+//!
+//! ```rust
+//! # use redstone_ml::*;
+//! // differentiate with respect to x along the direction [1, 0]
+//! let x = DualTensor::new(NdArray::new([3.0, 4.0]), NdArray::new([1.0, 0.0]));
+//! let a = DualTensor::constant(NdArray::new([[1.0, 2.0], [3.0, 4.0]]));
+//!
+//! let y = a.matmul(&x) * &x;
+//!
+//! assert_eq!(y.primal(), &NdArray::new([33.0, 100.0]));
+//! assert_eq!(y.tangent(), &NdArray::new([14.0, 12.0]));
+//! ```
+
+pub mod methods;
+pub mod ops;
+pub mod matrix_ops;
+pub mod reshape;
+pub mod reduce;
+pub mod statistics;
+pub mod print;
+
+use crate::{NdArray, TensorDataType};
+
+pub struct DualTensor<'a, T: TensorDataType> {
+    primal: NdArray<'a, T>,
+    tangent: NdArray<'a, T>,
+}
@@ -0,0 +1,101 @@
+use crate::{DualTensor, NdArray, TensorDataType};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+
+impl<T: TensorDataType> Neg for DualTensor<'_, T> {
+    type Output = DualTensor<'static, T>;
+
+    fn neg(self) -> Self::Output { -&self }
+}
+
+impl<T: TensorDataType> Neg for &DualTensor<'_, T> {
+    type Output = DualTensor<'static, T>;
+
+    fn neg(self) -> Self::Output {
+        DualTensor::new(-&self.primal, -&self.tangent)
+    }
+}
+
+// the tangent of each binary operation, given both of its operands
+
+fn add_tangent<T: TensorDataType>(lhs: &DualTensor<T>, rhs: &DualTensor<T>) -> NdArray<'static, T> {
+    &lhs.tangent + &rhs.tangent
+}
+
+fn sub_tangent<T: TensorDataType>(lhs: &DualTensor<T>, rhs: &DualTensor<T>) -> NdArray<'static, T> {
+    &lhs.tangent - &rhs.tangent
+}
+
+fn mul_tangent<T: TensorDataType>(lhs: &DualTensor<T>, rhs: &DualTensor<T>) -> NdArray<'static, T> {
+    &lhs.tangent * &rhs.primal + &lhs.primal * &rhs.tangent
+}
+
+fn div_tangent<T: TensorDataType>(lhs: &DualTensor<T>, rhs: &DualTensor<T>) -> NdArray<'static, T> {
+    // d(a / b) = (da - (a / b) db) / b
+    (&lhs.tangent - (&lhs.primal / &rhs.primal) * &rhs.tangent) / &rhs.primal
+}
+
+// the tangent of each binary operation with a constant scalar
+
+fn add_scalar_tangent<T: TensorDataType>(lhs: &DualTensor<T>, _: T) -> NdArray<'static, T> {
+    lhs.tangent.clone()
+}
+
+fn mul_scalar_tangent<T: TensorDataType>(lhs: &DualTensor<T>, rhs: T) -> NdArray<'static, T> {
+    &lhs.tangent * rhs
+}
+
+fn div_scalar_tangent<T: TensorDataType>(lhs: &DualTensor<T>, rhs: T) -> NdArray<'static, T> {
+    &lhs.tangent / rhs
+}
+
+macro_rules! implement_binary_ops {
+    ($($trait_: ident, $operator:tt, $method: ident, $tangent:ident, $tangent_scalar:ident;)* ) => { $(
+        impl<T: TensorDataType> $trait_<DualTensor<'_, T>> for DualTensor<'_, T> {
+            type Output = DualTensor<'static, T>;
+
+            fn $method(self, rhs: DualTensor<T>) -> Self::Output { &self $operator &rhs }
+        }
+
+        impl<T: TensorDataType> $trait_<&DualTensor<'_, T>> for DualTensor<'_, T> {
+            type Output = DualTensor<'static, T>;
+
+            fn $method(self, rhs: &DualTensor<T>) -> Self::Output { &self $operator rhs }
+        }
+
+        impl<T: TensorDataType> $trait_<DualTensor<'_, T>> for &DualTensor<'_, T> {
+            type Output = DualTensor<'static, T>;
+
+            fn $method(self, rhs: DualTensor<T>) -> Self::Output { self $operator &rhs }
+        }
+
+        impl<T: TensorDataType> $trait_<&DualTensor<'_, T>> for &DualTensor<'_, T> {
+            type Output = DualTensor<'static, T>;
+
+            fn $method(self, rhs: &DualTensor<T>) -> Self::Output {
+                DualTensor::new(&self.primal $operator &rhs.primal, $tangent(self, rhs))
+            }
+        }
+
+        impl<T: TensorDataType> $trait_<T> for DualTensor<'_, T> {
+            type Output = DualTensor<'static, T>;
+
+            fn $method(self, rhs: T) -> Self::Output { &self $operator rhs }
+        }
+
+        impl<T: TensorDataType> $trait_<T> for &DualTensor<'_, T> {
+            type Output = DualTensor<'static, T>;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                DualTensor::new(&self.primal $operator rhs, $tangent_scalar(self, rhs))
+            }
+        }
+    )*};
+}
+
+implement_binary_ops!(
+    Add, +, add, add_tangent, add_scalar_tangent;
+    Sub, -, sub, sub_tangent, add_scalar_tangent;
+    Mul, *, mul, mul_tangent, mul_scalar_tangent;
+    Div, /, div, div_tangent, div_scalar_tangent;
+);
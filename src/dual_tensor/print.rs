@@ -0,0 +1,11 @@
+use crate::{DualTensor, TensorDataType};
+use std::fmt;
+
+impl<T: TensorDataType> fmt::Debug for DualTensor<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DualTensor")
+            .field("primal", &self.primal)
+            .field("tangent", &self.tangent)
+            .finish()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::ndarray::reduce::keepdims_shape;
+use crate::util::to_vec::ToVec;
+use crate::{DualTensor, Reshape, StridedMemory, TensorDataType};
+
+impl<T: TensorDataType> DualTensor<'_, T> {
+    /// Computes the sum of all elements in the dual tensor.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([[1.0, 2.0], [3.0, 4.0]]), NdArray::new([[1.0, 1.0], [0.0, 1.0]]));
+    ///
+    /// let sum = x.sum();
+    /// assert_eq!(sum.value(), 10.0);
+    /// assert_eq!(sum.tangent_value(), 3.0);
+    /// ```
+    pub fn sum(&self) -> DualTensor<'static, T> {
+        self.sum_along(self.all_axes())
+    }
+
+    /// Computes the sum along the specified axes.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([[1.0, 2.0], [3.0, 4.0]]), NdArray::new([[1.0, 1.0], [0.0, 1.0]]));
+    ///
+    /// let sum = x.sum_along(0);
+    /// assert_eq!(sum.primal(), &NdArray::new([4.0, 6.0]));
+    /// assert_eq!(sum.tangent(), &NdArray::new([1.0, 2.0]));
+    /// ```
+    pub fn sum_along(&self, axes: impl ToVec<isize>) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        DualTensor::new(self.primal.sum_along(axes.clone()), self.tangent.sum_along(axes))
+    }
+
+    /// Computes the sum along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::constant(NdArray::new([[1.0, 2.0], [3.0, 4.0]]));
+    /// assert_eq!(x.sum_keepdims(1).primal(), &NdArray::new([[3.0], [7.0]]));
+    /// ```
+    pub fn sum_keepdims(&self, axes: impl ToVec<isize>) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        self.sum_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    pub(super) fn all_axes(&self) -> Vec<isize> {
+        (0..self.ndims() as isize).collect()
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{DualTensor, Reshape, TensorDataType};
+
+
+impl<'a, T: TensorDataType> Reshape<T> for &'a DualTensor<'a, T> {
+    type Output = DualTensor<'a, T>;
+
+    /// Provides a non-owning view of the dual tensor with the specified shape and stride.
+    /// The data pointed to by the view is shared with the original dual tensor.
+    ///
+    /// # Safety
+    /// - Ensure the memory layout referenced by `shape`, and `stride` is valid and owned
+    ///   by the original dual tensor.
+    unsafe fn reshaped_view(self, shape: Vec<usize>, stride: Vec<usize>) -> Self::Output {
+        DualTensor {
+            primal: (&self.primal).reshaped_view(shape.clone(), stride.clone()),
+            tangent: (&self.tangent).reshaped_view(shape, stride),
+        }
+    }
+}
+
+impl<T: TensorDataType> Reshape<T> for DualTensor<'_, T> {
+    type Output = DualTensor<'static, T>;
+
+    /// Provides a non-owning view of the dual tensor with the specified shape and stride.
+    /// The data pointed to by the view is shared with the original dual tensor.
+    ///
+    /// # Safety
+    /// - Ensure the memory layout referenced by `shape`, and `stride` is valid and owned
+    ///   by the original dual tensor.
+    unsafe fn reshaped_view(self, shape: Vec<usize>, stride: Vec<usize>) -> Self::Output {
+        DualTensor {
+            primal: self.primal.reshaped_view(shape.clone(), stride.clone()),
+            tangent: self.tangent.reshaped_view(shape, stride),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use crate::ndarray::reduce::keepdims_shape;
+use crate::util::to_vec::ToVec;
+use crate::{AxisType, Constructors, DualTensor, NdArray, Norm, Reshape, StridedMemory, TensorDataType};
+use num::NumCast;
+
+/// Returns the sign of `x` with `sign(0) = 0`.
+fn sign<T: TensorDataType>(x: T) -> T {
+    if x == T::zero() { T::zero() } else { x.signum() }
+}
+
+impl<T: TensorDataType> DualTensor<'_, T> {
+    /// Computes the variance of all elements in the dual tensor.
+    ///
+    /// `ddof` is the "delta degrees of freedom" and the divisor used is `N - ddof`.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([1.0, 3.0]), NdArray::new([0.0, 1.0]));
+    ///
+    /// let var = x.var(0);
+    /// assert_eq!(var.value(), 1.0);
+    /// assert_eq!(var.tangent_value(), 1.0);
+    /// ```
+    pub fn var(&self, ddof: usize) -> DualTensor<'static, T> {
+        self.var_along(self.all_axes(), ddof)
+    }
+
+    /// Computes the variance along the specified axes.
+    ///
+    /// `ddof` is the "delta degrees of freedom" and the divisor used is `N - ddof`.
+    pub fn var_along(&self, axes: impl ToVec<isize>, ddof: usize) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        DualTensor::new(self.primal.var_along(axes.clone(), ddof), self.var_tangent(&axes, ddof))
+    }
+
+    /// Computes the variance along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// `ddof` is the "delta degrees of freedom" and the divisor used is `N - ddof`.
+    pub fn var_keepdims(&self, axes: impl ToVec<isize>, ddof: usize) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        self.var_along(axes.clone(), ddof).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the standard deviation of all elements in the dual tensor.
+    ///
+    /// `ddof` is the "delta degrees of freedom" and the divisor used is `N - ddof`.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([1.0, 3.0]), NdArray::new([0.0, 1.0]));
+    ///
+    /// let std = x.std(0);
+    /// assert_eq!(std.value(), 1.0);
+    /// assert_eq!(std.tangent_value(), 0.5);
+    /// ```
+    pub fn std(&self, ddof: usize) -> DualTensor<'static, T> {
+        self.std_along(self.all_axes(), ddof)
+    }
+
+    /// Computes the standard deviation along the specified axes.
+    ///
+    /// `ddof` is the "delta degrees of freedom" and the divisor used is `N - ddof`.
+    pub fn std_along(&self, axes: impl ToVec<isize>, ddof: usize) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        let std = self.primal.std_along(axes.clone(), ddof);
+
+        let two = T::one() + T::one();
+        let tangent = self.var_tangent(&axes, ddof) / (&std * two);
+
+        DualTensor::new(std, tangent)
+    }
+
+    /// Computes the standard deviation along the specified axes,
+    /// retaining the reduced axes with length 1.
+    ///
+    /// `ddof` is the "delta degrees of freedom" and the divisor used is `N - ddof`.
+    pub fn std_keepdims(&self, axes: impl ToVec<isize>, ddof: usize) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        self.std_along(axes.clone(), ddof).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the cumulative sum of elements along the specified axis.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([1.0, 2.0, 3.0]), NdArray::new([1.0, 0.0, 1.0]));
+    ///
+    /// let cumsum = x.cumsum(0);
+    /// assert_eq!(cumsum.primal(), &NdArray::new([1.0, 3.0, 6.0]));
+    /// assert_eq!(cumsum.tangent(), &NdArray::new([1.0, 1.0, 2.0]));
+    /// ```
+    pub fn cumsum(&self, axis: impl AxisType) -> DualTensor<'static, T> {
+        let axis = axis.as_absolute(self.ndims()) as isize;
+        DualTensor::new(self.primal.cumsum(axis), self.tangent.cumsum(axis))
+    }
+
+    /// Computes `log(sum(exp(x)))` over all elements in a numerically stable way.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([0.0, 0.0]), NdArray::new([1.0, 0.0]));
+    ///
+    /// let logsumexp = x.logsumexp();
+    /// assert_eq!(logsumexp.value(), 2.0f64.ln());
+    /// assert_eq!(logsumexp.tangent_value(), 0.5);
+    /// ```
+    pub fn logsumexp(&self) -> DualTensor<'static, T> {
+        self.logsumexp_along(self.all_axes())
+    }
+
+    /// Computes `log(sum(exp(x)))` along the specified axes in a numerically stable way.
+    pub fn logsumexp_along(&self, axes: impl ToVec<isize>) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        let logsumexp = self.primal.logsumexp_keepdims(axes.clone());
+
+        // the tangent is the average of the input tangents weighted by the softmax of the input
+        let softmax = (&self.primal - &logsumexp).map(|x| x.exp());
+        let tangent = (softmax * &self.tangent).sum_along(axes.clone());
+
+        DualTensor::new(self.primal.logsumexp_along(axes), tangent)
+    }
+
+    /// Computes `log(sum(exp(x)))` along the specified axes in a numerically stable way,
+    /// retaining the reduced axes with length 1.
+    pub fn logsumexp_keepdims(&self, axes: impl ToVec<isize>) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        self.logsumexp_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the norm of the flattened dual tensor.
+    ///
+    /// Where the norm is 0, its derivative is taken to be 0.
+    /// For the infinity norm, the derivative is averaged over all maximal elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let x = DualTensor::new(NdArray::new([3.0, 4.0]), NdArray::new([1.0, 0.0]));
+    ///
+    /// let norm = x.norm(Norm::L2);
+    /// assert_eq!(norm.value(), 5.0);
+    /// assert_eq!(norm.tangent_value(), 0.6);
+    /// ```
+    pub fn norm(&self, ord: Norm<T>) -> DualTensor<'static, T> {
+        self.norm_along(ord, self.all_axes())
+    }
+
+    /// Computes the norm along the specified axes.
+    ///
+    /// Where the norm is 0, its derivative is taken to be 0.
+    /// For the infinity norm, the derivative is averaged over all maximal elements.
+    pub fn norm_along(&self, ord: Norm<T>, axes: impl ToVec<isize>) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        let tangent = (self.norm_local_gradient(ord, &axes) * &self.tangent).sum_along(axes.clone());
+
+        DualTensor::new(self.primal.norm_along(ord, axes), tangent)
+    }
+
+    /// Computes the norm along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// Where the norm is 0, its derivative is taken to be 0.
+    /// For the infinity norm, the derivative is averaged over all maximal elements.
+    pub fn norm_keepdims(&self, ord: Norm<T>, axes: impl ToVec<isize>) -> DualTensor<'static, T> {
+        let axes = axes.to_vec();
+        self.norm_along(ord, axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the tangent of the variance along `axes`, which is
+    /// `2 sum((x - mean(x)) dx) / (N - ddof)`.
+    fn var_tangent(&self, axes: &[isize], ddof: usize) -> NdArray<'static, T> {
+        let n: usize = axes.iter().map(|axis| self.shape()[axis.as_absolute(self.ndims())]).product();
+        let n: T = NumCast::from(n).unwrap();
+        let ddof: T = NumCast::from(ddof).unwrap();
+
+        let centered = &self.primal - self.primal.mean_keepdims(axes.to_vec());
+        let two = T::one() + T::one();
+
+        (centered * &self.tangent).sum_along(axes.to_vec()) * (two / (n - ddof))
+    }
+
+    /// Computes the derivative of the norm along `axes` with respect to each element of the primal.
+    fn norm_local_gradient(&self, ord: Norm<T>, axes: &[isize]) -> NdArray<'static, T> {
+        let shape = self.shape();
+        let norm = self.primal.norm_keepdims(ord, axes.to_vec());
+        let norm = norm.broadcast_to(shape);
+
+        let p = match ord {
+            Norm::L1 => return self.primal.map(sign),
+            Norm::Inf => {
+                let local = self.primal.flatiter().zip(norm.flatiter())
+                    .map(|(x, norm)| if norm != T::zero() && x.abs() == norm { sign(x) } else { T::zero() })
+                    .collect();
+
+                let local = unsafe { NdArray::from_contiguous_owned_buffer(shape.to_vec(), local) };
+                let ties = local.map(|x| x.abs()).sum_keepdims(axes.to_vec()).map(|n| n.max(T::one()));
+
+                return local / ties;
+            }
+            Norm::L2 => T::one() + T::one(),
+            Norm::Lp(p) => p,
+        };
+
+        // d|x|_p / dx = sign(x) (|x| / |x|_p)^(p - 1)
+        let local = self.primal.flatiter().zip(norm.flatiter())
+            .map(|(x, norm)| {
+                if norm == T::zero() { T::zero() } else { sign(x) * (x.abs() / norm).powf(p - T::one()) }
+            })
+            .collect();
+
+        unsafe { NdArray::from_contiguous_owned_buffer(shape.to_vec(), local) }
+    }
+}
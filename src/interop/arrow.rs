@@ -0,0 +1,418 @@
+//! Conversions between `NdArray` and Apache Arrow arrays and tensors, enabled by the `arrow` feature.
+//!
+//! Primitive Arrow arrays are viewed as vectors, and (nested) `FixedSizeList` arrays of primitives
+//! as arrays with one dimension per level of nesting. Arrow buffers are always aligned and
+//! store their elements contiguously, so columns without nulls are viewed without copying.
+//! Columns with nulls are converted with `NdArray::from_arrow_with_mask()`, which also returns
+//! a mask of the elements which are null.
+//!
+//! Arrays are also written to and read from the Arrow Tensor IPC format,
+//! an encapsulated Arrow message whose header describes the dtype, shape and strides of a tensor.
+//!
+//! # Example
+//! ```
+//! # use redstone_ml::*;
+//! use arrow_array::{types::Float32Type, Array, FixedSizeListArray};
+//!
+//! let embeddings = FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>([
+//!     Some([Some(1.0), Some(2.0), Some(3.0)]),
+//!     Some([Some(4.0), Some(5.0), Some(6.0)]),
+//! ], 3);
+//!
+//! let array = NdArray::<f32>::from_arrow(&embeddings).unwrap();
+//! assert_eq!(array, NdArray::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+//! ```
+
+use crate::interop::ConversionError;
+use crate::io::safetensors::{from_le_bytes, to_le_bytes};
+use crate::ndarray::constructors::stride_from_shape;
+use crate::{Constructors, DType, NdArray, RawDataType, StridedMemory};
+use arrow_array::types::*;
+use arrow_array::{Array, ArrayRef, ArrowPrimitiveType, FixedSizeListArray, PrimitiveArray};
+use arrow_buffer::{ArrowNativeType, NullBuffer, ScalarBuffer};
+use arrow_schema::Field;
+use arrow_ipc::{FloatingPoint, FloatingPointArgs, Int, IntArgs, Message, MessageArgs, MessageHeader,
+                MetadataVersion, Precision, TensorArgs, TensorDim, TensorDimArgs, Type};
+use flatbuffers::{FlatBufferBuilder, UnionWIPOffset, WIPOffset};
+use half::f16;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+/// Marks the start of an encapsulated Arrow IPC message.
+const CONTINUATION_MARKER: u32 = 0xFFFF_FFFF;
+
+/// A datatype which Arrow stores as a primitive array of the type `ArrowType`.
+pub trait ArrowDataType: RawDataType + ArrowNativeType {
+    type ArrowType: ArrowPrimitiveType<Native=Self>;
+}
+
+macro_rules! impl_arrow_dtype {
+    ($($dtype:ty => $arrow_type:ty),* $(,)?) => {
+        $(impl ArrowDataType for $dtype { type ArrowType = $arrow_type; })*
+    };
+}
+
+impl_arrow_dtype!(
+    u8 => UInt8Type, u16 => UInt16Type, u32 => UInt32Type, u64 => UInt64Type,
+    i8 => Int8Type, i16 => Int16Type, i32 => Int32Type, i64 => Int64Type,
+    f16 => Float16Type, f32 => Float32Type, f64 => Float64Type,
+);
+
+/// The values of a (nested) `FixedSizeList` array with the nulls of each level of nesting.
+struct ArrowColumn<'a, T: ArrowDataType> {
+    shape: Vec<usize>,
+    values: &'a PrimitiveArray<T::ArrowType>,
+    nulls: Vec<&'a NullBuffer>,
+}
+
+impl<'a, T: ArrowDataType> ArrowColumn<'a, T> {
+    /// Unwraps the `FixedSizeList` levels of `array` down to its primitive values.
+    ///
+    /// # Errors
+    /// - If `array` is not a primitive array or `FixedSizeList` of primitives of dtype `T`
+    fn new(mut array: &'a dyn Array) -> Result<Self, ConversionError> {
+        let mut shape = vec![array.len()];
+        let mut nulls = Vec::new();
+
+        loop {
+            if let Some(level_nulls) = array.nulls().filter(|nulls| nulls.null_count() > 0) {
+                nulls.push(level_nulls);
+            }
+
+            if let Some(values) = array.as_any().downcast_ref::<PrimitiveArray<T::ArrowType>>() {
+                return Ok(Self { shape, values, nulls });
+            }
+
+            let list = array.as_any().downcast_ref::<FixedSizeListArray>().ok_or_else(|| {
+                ConversionError::DtypeMismatch { expected: T::DTYPE, found: array.data_type().to_string() }
+            })?;
+
+            shape.push(list.value_length() as usize);
+            array = list.values().as_ref();
+        }
+    }
+
+    /// Returns a mask of the shape of the column which is `true` where values are null.
+    fn null_mask(&self) -> Vec<bool> {
+        let mut mask = vec![false; self.values.len()];
+
+        // a null at any level of nesting covers a block of values
+        for nulls in &self.nulls {
+            let block = self.values.len() / nulls.len();
+            for index in nulls.iter().enumerate().filter(|(_, valid)| !valid).map(|(index, _)| index) {
+                mask[index * block..(index + 1) * block].fill(true);
+            }
+        }
+
+        mask
+    }
+}
+
+impl<'a, T: ArrowDataType> NdArray<'a, T> {
+    /// Views an Arrow primitive array as a vector, or a (nested) `FixedSizeList` array
+    /// of primitives as an array with a dimension for each level of nesting, without copying.
+    ///
+    /// # Errors
+    /// - If `array` is not a primitive array or `FixedSizeList` of primitives of dtype `T`
+    /// - If `array` contains nulls, in which case `from_arrow_with_mask()` can be used instead
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// use arrow_array::Int64Array;
+    ///
+    /// let column = Int64Array::from(vec![1, 2, 3, 4]);
+    /// let array = NdArray::<i64>::from_arrow(&column).unwrap();
+    /// assert_eq!(array.sum(), NdArray::scalar(10));
+    /// ```
+    pub fn from_arrow(array: &'a dyn Array) -> Result<Self, ConversionError> {
+        let column = ArrowColumn::<T>::new(array)?;
+
+        if !column.nulls.is_empty() {
+            return Err(ConversionError::NullValues);
+        }
+
+        Ok(NdArray::from_contiguous_borrowed_buffer(column.shape, column.values.values()))
+    }
+
+    /// Views an Arrow array which may contain nulls like `from_arrow()`,
+    /// and returns it with a mask of the same shape which is `true` where values are null.
+    ///
+    /// The values of the array where the mask is `true` are unspecified.
+    ///
+    /// # Errors
+    /// - If `array` is not a primitive array or `FixedSizeList` of primitives of dtype `T`
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// use arrow_array::Float64Array;
+    ///
+    /// let column = Float64Array::from(vec![Some(1.0), None, Some(3.0)]);
+    ///
+    /// let (array, mask) = NdArray::<f64>::from_arrow_with_mask(&column).unwrap();
+    /// assert_eq!(mask, NdArray::new([false, true, false]));
+    /// assert_eq!(array.slice_along(Axis(0), 2), NdArray::scalar(3.0));
+    /// ```
+    pub fn from_arrow_with_mask(array: &'a dyn Array) -> Result<(Self, NdArray<'static, bool>), ConversionError> {
+        let column = ArrowColumn::<T>::new(array)?;
+        let mask = column.null_mask();
+
+        let array = NdArray::from_contiguous_borrowed_buffer(column.shape.clone(), column.values.values());
+        let mask = unsafe { NdArray::from_contiguous_owned_buffer(column.shape, mask) };
+        Ok((array, mask))
+    }
+
+    /// Reads an array from an encapsulated Arrow Tensor IPC message, as written by `write_arrow_tensor()`.
+    ///
+    /// The tensor data is viewed without copying if it is aligned and the platform is little-endian.
+    ///
+    /// # Errors
+    /// - If `bytes` does not start with a valid Arrow Tensor message
+    /// - If the tensor does not have dtype `T`
+    /// - If the tensor has negative strides or strides which are not a multiple of the element size
+    pub fn from_arrow_tensor(bytes: &'a [u8]) -> Result<Self, ConversionError> {
+        let invalid = |reason: &str| ConversionError::InvalidData(reason.to_string());
+
+        let read_u32 = |offset: usize| -> Result<u32, ConversionError> {
+            let word = bytes.get(offset..offset + 4).ok_or_else(|| invalid("unexpected end of message"))?;
+            Ok(u32::from_le_bytes(word.try_into().unwrap()))
+        };
+
+        // messages written before the continuation marker was introduced start with their length
+        let start = if read_u32(0)? == CONTINUATION_MARKER { 8 } else { 4 };
+        let metadata_len = read_u32(start - 4)? as usize;
+
+        let metadata = bytes.get(start..start + metadata_len).ok_or_else(|| invalid("unexpected end of message"))?;
+        let message = arrow_ipc::root_as_message(metadata).map_err(|err| ConversionError::InvalidData(err.to_string()))?;
+        let tensor = message.header_as_tensor().ok_or_else(|| invalid("the message does not contain a tensor"))?;
+
+        let dtype = tensor_dtype(&tensor);
+        if dtype != Some(T::DTYPE) {
+            let found = dtype.map_or_else(|| format!("{:?}", tensor.type_type()), |dtype| dtype.name().to_string());
+            return Err(ConversionError::DtypeMismatch { expected: T::DTYPE, found });
+        }
+
+        let shape = tensor.shape().iter()
+            .map(|dim| usize::try_from(dim.size_()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid("the tensor has a negative dimension"))?;
+
+        let size = size_of::<T>();
+        let stride = match tensor.strides() {
+            Some(strides) => strides.iter()
+                .map(|stride| usize::try_from(stride).ok().filter(|stride| stride % size == 0).map(|stride| stride / size))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(ConversionError::IncompatibleLayout)?,
+            None => stride_from_shape(&shape),
+        };
+
+        if stride.len() != shape.len() {
+            return Err(invalid("the tensor strides do not match its shape"));
+        }
+
+        // the number of elements spanned by the tensor
+        let len = match shape.contains(&0) {
+            true => 0,
+            false => shape.iter().zip(&stride)
+                .try_fold(1usize, |len, (&dim, &stride)| (dim - 1).checked_mul(stride)?.checked_add(len))
+                .ok_or_else(|| invalid("the tensor is too large"))?,
+        };
+
+        let data = tensor.data();
+        let body = &bytes[start + metadata_len..];
+        let data = usize::try_from(data.offset()).ok()
+            .zip(len.checked_mul(size))
+            .and_then(|(offset, nbytes)| body.get(offset..offset.checked_add(nbytes)?))
+            .ok_or_else(|| invalid("the tensor data extends past the end of the message"))?;
+
+        if len == 0 {
+            return Ok(unsafe { NdArray::from_contiguous_owned_buffer(shape, Vec::new()) });
+        }
+
+        if cfg!(target_endian = "little") && data.as_ptr().cast::<T>().is_aligned() {
+            return Ok(unsafe { NdArray::from_raw_view(data.as_ptr() as *mut T, shape, stride, false) });
+        }
+
+        let mut buffer = from_le_bytes::<T>(data, len);
+        let view = unsafe { NdArray::from_raw_view(buffer.as_mut_ptr(), shape.clone(), stride, false) };
+        let data = view.flatiter().collect();
+        Ok(unsafe { NdArray::from_contiguous_owned_buffer(shape, data) })
+    }
+}
+
+impl<T: ArrowDataType> NdArray<'_, T> {
+    /// Converts this array into an Arrow array: a primitive array if it is a vector,
+    /// or a `FixedSizeList` array nested once for every further dimension.
+    ///
+    /// The data is only copied if the array is a view or is not contiguous.
+    ///
+    /// # Errors
+    /// - If the array is a scalar
+    /// - If a dimension other than the first is larger than `i32::MAX`
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// use arrow_array::{Array, FixedSizeListArray};
+    ///
+    /// let array = NdArray::new([[1.0f32, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    /// let column = array.into_arrow().unwrap();
+    ///
+    /// let list = column.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+    /// assert_eq!(list.len(), 3);
+    /// assert_eq!(list.value_length(), 2);
+    /// ```
+    pub fn into_arrow(self) -> Result<ArrayRef, ConversionError> {
+        self.into_arrow_with_nulls(None)
+    }
+
+    /// Converts this array into an Arrow array like `into_arrow()`,
+    /// with nulls where `mask` is `true`.
+    ///
+    /// # Errors
+    /// - If the array is a scalar
+    /// - If a dimension other than the first is larger than `i32::MAX`
+    ///
+    /// # Panics
+    /// - If `mask` does not have the same shape as the array
+    pub fn into_arrow_with_mask(self, mask: &NdArray<bool>) -> Result<ArrayRef, ConversionError> {
+        assert_eq!(self.shape(), mask.shape(), "the mask does not have the same shape as the array");
+
+        let nulls = NullBuffer::from_iter(mask.flatiter().map(|null| !null));
+        self.into_arrow_with_nulls(Some(nulls))
+    }
+
+    fn into_arrow_with_nulls(self, nulls: Option<NullBuffer>) -> Result<ArrayRef, ConversionError> {
+        if self.ndims() == 0 {
+            return Err(ConversionError::Dimensionality { expected: 1, found: 0 });
+        }
+
+        let list_sizes = self.shape()[1..].iter()
+            .map(|&dim| i32::try_from(dim))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ConversionError::IncompatibleLayout)?;
+
+        let data = if self.is_view() || !self.is_contiguous() {
+            self.flatiter().collect()
+        } else {
+            self.into_data_vector()
+        };
+
+        let mut array: ArrayRef = Arc::new(PrimitiveArray::<T::ArrowType>::new(ScalarBuffer::from(data), nulls));
+
+        for &size in list_sizes.iter().rev() {
+            let field = Arc::new(Field::new_list_field(array.data_type().clone(), true));
+            array = Arc::new(FixedSizeListArray::new(field, size, array, None));
+        }
+
+        Ok(array)
+    }
+
+    /// Writes this array to `writer` as an encapsulated Arrow Tensor IPC message.
+    ///
+    /// The tensor is written contiguously in row-major order.
+    ///
+    /// # Errors
+    /// - If writing to `writer` fails
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([[1, 2, 3], [4, 5, 6]]);
+    ///
+    /// let mut bytes = Vec::new();
+    /// array.write_arrow_tensor(&mut bytes).unwrap();
+    ///
+    /// let tensor = NdArray::<i32>::from_arrow_tensor(&bytes).unwrap();
+    /// assert_eq!(tensor, array);
+    /// ```
+    pub fn write_arrow_tensor(&self, mut writer: impl Write) -> io::Result<()> {
+        let data: Vec<T> = self.flatiter().collect();
+        let body = to_le_bytes(&data);
+
+        let mut builder = FlatBufferBuilder::new();
+        let (type_type, type_) = tensor_type(&mut builder, T::DTYPE);
+
+        let dims: Vec<_> = self.shape().iter()
+            .map(|&size| TensorDim::create(&mut builder, &TensorDimArgs { size_: size as i64, name: None }))
+            .collect();
+        let shape = builder.create_vector(&dims);
+
+        let strides: Vec<i64> = stride_from_shape(self.shape()).iter()
+            .map(|&stride| (stride * size_of::<T>()) as i64)
+            .collect();
+        let strides = builder.create_vector(&strides);
+
+        let buffer = arrow_ipc::Buffer::new(0, body.len() as i64);
+        let tensor = arrow_ipc::Tensor::create(&mut builder, &TensorArgs {
+            type_type,
+            type_: Some(type_),
+            shape: Some(shape),
+            strides: Some(strides),
+            data: Some(&buffer),
+        });
+
+        let body_len = body.len().next_multiple_of(8);
+        let message = Message::create(&mut builder, &MessageArgs {
+            version: MetadataVersion::V5,
+            header_type: MessageHeader::Tensor,
+            header: Some(tensor.as_union_value()),
+            bodyLength: body_len as i64,
+            custom_metadata: None,
+        });
+        builder.finish(message, None);
+
+        // the metadata is padded so that the body starts at a multiple of 8 bytes
+        let metadata = builder.finished_data();
+        let metadata_len = (metadata.len() + 8).next_multiple_of(8) - 8;
+
+        writer.write_all(&CONTINUATION_MARKER.to_le_bytes())?;
+        writer.write_all(&(metadata_len as u32).to_le_bytes())?;
+        writer.write_all(metadata)?;
+        writer.write_all(&vec![0; metadata_len - metadata.len()])?;
+        writer.write_all(&body)?;
+        writer.write_all(&vec![0; body_len - body.len()])
+    }
+}
+
+/// Builds the Arrow type of a tensor with the given dtype.
+fn tensor_type(builder: &mut FlatBufferBuilder, dtype: DType) -> (Type, WIPOffset<UnionWIPOffset>) {
+    let precision = match dtype {
+        DType::F16 => Precision::HALF,
+        DType::F32 => Precision::SINGLE,
+        DType::F64 => Precision::DOUBLE,
+        _ => {
+            let is_signed = matches!(dtype, DType::I8 | DType::I16 | DType::I32 | DType::I64);
+            let args = IntArgs { bitWidth: 8 * dtype.size() as i32, is_signed };
+            return (Type::Int, Int::create(builder, &args).as_union_value());
+        }
+    };
+
+    let args = FloatingPointArgs { precision };
+    (Type::FloatingPoint, FloatingPoint::create(builder, &args).as_union_value())
+}
+
+/// Returns the dtype of an Arrow tensor, if it is supported.
+fn tensor_dtype(tensor: &arrow_ipc::Tensor) -> Option<DType> {
+    if let Some(int) = tensor.type_as_int() {
+        return match (int.bitWidth(), int.is_signed()) {
+            (8, false) => Some(DType::U8),
+            (16, false) => Some(DType::U16),
+            (32, false) => Some(DType::U32),
+            (64, false) => Some(DType::U64),
+            (8, true) => Some(DType::I8),
+            (16, true) => Some(DType::I16),
+            (32, true) => Some(DType::I32),
+            (64, true) => Some(DType::I64),
+            _ => None,
+        };
+    }
+
+    match tensor.type_as_floating_point()?.precision() {
+        Precision::HALF => Some(DType::F16),
+        Precision::SINGLE => Some(DType::F32),
+        Precision::DOUBLE => Some(DType::F64),
+        _ => None,
+    }
+}
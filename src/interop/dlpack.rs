@@ -0,0 +1,316 @@
+//! Exchanging arrays with other libraries in the same process through the
+//! [DLPack](https://dmlc.github.io/dlpack/latest/) C ABI, without copying.
+//!
+//! `NdArray::to_dlpack()` hands an array to a consumer as a `DLManagedTensor`, which keeps the array
+//! alive until the consumer calls its deleter. Arrays which own their data free it then,
+//! while views leave it to the array they borrow from.
+//!
+//! Tensors received from a producer are wrapped in a `DLPackTensor`, which calls the producer's
+//! deleter when dropped, and are viewed as arrays with `NdArray::from_dlpack()`.
+//!
+//! # Example
+//! ```
+//! # use redstone_ml::*;
+//! let array = NdArray::new([[1.0f32, 2.0], [3.0, 4.0]]);
+//! let managed = array.to_dlpack();
+//!
+//! // the pointer can be handed to any DLPack consumer
+//! let tensor = unsafe { DLPackTensor::from_raw(managed) };
+//! let view = NdArray::<f32>::from_dlpack(&tensor).unwrap();
+//! assert_eq!(view, NdArray::new([[1.0, 2.0], [3.0, 4.0]]));
+//! ```
+
+use crate::interop::ConversionError;
+use crate::ndarray::constructors::stride_from_shape;
+use crate::{DType, NdArray, RawDataType, StridedMemory};
+use std::ffi::c_void;
+use std::ptr::NonNull;
+
+const DL_CPU: i32 = 1;
+const DL_CUDA_HOST: i32 = 3;
+const DL_ROCM_HOST: i32 = 11;
+const DL_CUDA_MANAGED: i32 = 13;
+
+const DL_INT: u8 = 0;
+const DL_UINT: u8 = 1;
+const DL_FLOAT: u8 = 2;
+const DL_BFLOAT: u8 = 4;
+const DL_COMPLEX: u8 = 5;
+const DL_BOOL: u8 = 6;
+
+/// The device on which the data of a `DLTensor` resides.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DLDevice {
+    /// The `DLDeviceType` of the device, e.g. `1` for the CPU.
+    pub device_type: i32,
+
+    /// The index of the device among devices of its type.
+    pub device_id: i32,
+}
+
+/// The datatype of the elements of a `DLTensor`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DLDataType {
+    /// The `DLDataTypeCode` of the datatype, e.g. `2` for floating-point numbers.
+    pub code: u8,
+
+    /// The number of bits of each lane of an element.
+    pub bits: u8,
+
+    /// The number of lanes of an element, which is 1 for scalar elements.
+    pub lanes: u16,
+}
+
+/// A non-owning description of a strided array.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLTensor {
+    /// The start of the allocation holding the data.
+    pub data: *mut c_void,
+    pub device: DLDevice,
+    pub ndim: i32,
+    pub dtype: DLDataType,
+
+    /// The `ndim` dimensions of the array.
+    pub shape: *mut i64,
+
+    /// The `ndim` strides of the array in units of elements, or null if it is contiguous and row-major.
+    pub strides: *mut i64,
+
+    /// The offset in bytes from `data` to the first element.
+    pub byte_offset: u64,
+}
+
+/// A `DLTensor` together with the means for its consumer to release it.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLManagedTensor {
+    pub dl_tensor: DLTensor,
+
+    /// The producer's context, which is opaque to the consumer.
+    pub manager_ctx: *mut c_void,
+
+    /// Called by the consumer once it no longer needs the tensor.
+    pub deleter: Option<unsafe extern "C" fn(*mut DLManagedTensor)>,
+}
+
+/// Returns the DLPack datatype of elements of the given `dtype`.
+fn dl_data_type(dtype: DType) -> DLDataType {
+    let code = match dtype {
+        DType::U8 | DType::U16 | DType::U32 | DType::U64 | DType::U128 | DType::Usize => DL_UINT,
+        DType::I8 | DType::I16 | DType::I32 | DType::I64 | DType::I128 | DType::Isize => DL_INT,
+        DType::F16 | DType::F32 | DType::F64 => DL_FLOAT,
+        DType::BF16 => DL_BFLOAT,
+        DType::Complex32 | DType::Complex64 => DL_COMPLEX,
+        DType::Bool => DL_BOOL,
+    };
+
+    DLDataType { code, bits: 8 * dtype.size() as u8, lanes: 1 }
+}
+
+/// Returns a description of a DLPack datatype for error messages.
+fn dl_data_type_name(dtype: DLDataType) -> String {
+    let name = match dtype.code {
+        DL_INT => "int",
+        DL_UINT => "uint",
+        DL_FLOAT => "float",
+        DL_BFLOAT => "bfloat",
+        DL_COMPLEX => "complex",
+        DL_BOOL => "bool",
+        code => return format!("type code {code} with {} bits and {} lanes", dtype.bits, dtype.lanes),
+    };
+
+    match dtype.lanes {
+        1 => format!("{name}{}", dtype.bits),
+        lanes => format!("{name}{}x{lanes}", dtype.bits),
+    }
+}
+
+/// An array shared with a DLPack consumer, which owns the `DLManagedTensor` describing it.
+#[repr(C)]
+struct ManagedArray<T: RawDataType> {
+    managed: DLManagedTensor,
+    array: NdArray<'static, T>,
+    shape: Vec<i64>,
+    strides: Vec<i64>,
+}
+
+/// Drops the `ManagedArray` which `managed` is the first field of.
+unsafe extern "C" fn delete_managed_array<T: RawDataType>(managed: *mut DLManagedTensor) {
+    if !managed.is_null() {
+        drop(Box::from_raw(managed as *mut ManagedArray<T>));
+    }
+}
+
+impl<T: RawDataType> NdArray<'static, T> {
+    /// Shares this array with a DLPack consumer without copying.
+    ///
+    /// The array is kept alive until the consumer calls the tensor's deleter, at which point
+    /// its data is freed if it owns it. The consumer must not write to arrays which are not writeable.
+    ///
+    /// Only arrays which do not borrow their data from another array can be shared,
+    /// since the consumer may keep the tensor for arbitrarily long.
+    pub fn to_dlpack(self) -> *mut DLManagedTensor {
+        let shape = self.shape().iter().map(|&dim| dim as i64).collect();
+        let strides = self.stride().iter().map(|&stride| stride as i64).collect();
+
+        let mut context = Box::new(ManagedArray {
+            managed: DLManagedTensor {
+                dl_tensor: DLTensor {
+                    data: unsafe { self.mut_ptr() } as *mut c_void,
+                    device: DLDevice { device_type: DL_CPU, device_id: 0 },
+                    ndim: self.ndims() as i32,
+                    dtype: dl_data_type(T::DTYPE),
+                    shape: std::ptr::null_mut(),
+                    strides: std::ptr::null_mut(),
+                    byte_offset: 0,
+                },
+                manager_ctx: std::ptr::null_mut(),
+                deleter: Some(delete_managed_array::<T>),
+            },
+            array: self,
+            shape,
+            strides,
+        });
+
+        context.managed.dl_tensor.shape = context.shape.as_mut_ptr();
+        context.managed.dl_tensor.strides = context.strides.as_mut_ptr();
+
+        let context = Box::into_raw(context);
+        unsafe { (*context).managed.manager_ctx = context as *mut c_void; }
+        context as *mut DLManagedTensor
+    }
+}
+
+/// A tensor received from a DLPack producer, which calls the producer's deleter when dropped.
+///
+/// Its data is viewed as an array with `NdArray::from_dlpack()` or `NdArray::from_dlpack_mut()`.
+#[derive(Debug)]
+pub struct DLPackTensor {
+    managed: NonNull<DLManagedTensor>,
+}
+
+impl DLPackTensor {
+    /// Takes ownership of a `DLManagedTensor` from its producer.
+    ///
+    /// # Safety
+    /// - `managed` must point to a valid `DLManagedTensor` which is not released elsewhere
+    /// - The data it describes must remain valid, and must not be written to by others,
+    ///   until its deleter is called
+    ///
+    /// # Panics
+    /// - If `managed` is null
+    pub unsafe fn from_raw(managed: *mut DLManagedTensor) -> Self {
+        Self { managed: NonNull::new(managed).expect("DLManagedTensor is null") }
+    }
+
+    /// Releases ownership of the `DLManagedTensor`, which the caller becomes responsible for deleting.
+    pub fn into_raw(self) -> *mut DLManagedTensor {
+        let managed = self.managed.as_ptr();
+        std::mem::forget(self);
+        managed
+    }
+
+    /// Returns the description of the tensor.
+    pub fn dl_tensor(&self) -> &DLTensor {
+        unsafe { &self.managed.as_ref().dl_tensor }
+    }
+}
+
+impl Drop for DLPackTensor {
+    fn drop(&mut self) {
+        let managed = self.managed.as_ptr();
+        if let Some(deleter) = unsafe { (*managed).deleter } {
+            unsafe { deleter(managed) };
+        }
+    }
+}
+
+/// Returns the address of the first element, shape and strides of a DLPack tensor of dtype `T`.
+///
+/// # Errors
+/// - If the data is not accessible from the CPU
+/// - If the tensor does not have dtype `T`
+/// - If the tensor has negative dimensions or strides, or its data is not aligned for `T`
+/// - If `T` is `bool` and the data contains bytes other than 0 and 1
+fn dl_tensor_layout<T: RawDataType>(tensor: &DLTensor) -> Result<(*mut T, Vec<usize>, Vec<usize>), ConversionError> {
+    if !matches!(tensor.device.device_type, DL_CPU | DL_CUDA_HOST | DL_ROCM_HOST | DL_CUDA_MANAGED) {
+        return Err(ConversionError::UnsupportedDevice(tensor.device.device_type));
+    }
+
+    if tensor.dtype != dl_data_type(T::DTYPE) {
+        return Err(ConversionError::DtypeMismatch { expected: T::DTYPE, found: dl_data_type_name(tensor.dtype) });
+    }
+
+    let ndims = usize::try_from(tensor.ndim).map_err(|_| ConversionError::InvalidData("negative ndim".into()))?;
+    let to_usize = |values: *const i64| -> Option<Vec<usize>> {
+        let values = unsafe { std::slice::from_raw_parts(values, ndims) };
+        values.iter().map(|&value| usize::try_from(value).ok()).collect()
+    };
+
+    let shape = match ndims {
+        0 => Vec::new(),
+        _ => to_usize(tensor.shape).ok_or_else(|| ConversionError::InvalidData("negative dimension".into()))?,
+    };
+
+    let stride = match tensor.strides.is_null() || ndims == 0 {
+        true => stride_from_shape(&shape),
+        false => to_usize(tensor.strides).ok_or(ConversionError::IncompatibleLayout)?,
+    };
+
+    let ptr = (tensor.data as *mut u8).wrapping_add(tensor.byte_offset as usize) as *mut T;
+    if shape.contains(&0) {
+        return Ok((std::ptr::null_mut(), shape, stride));
+    }
+
+    if !ptr.is_aligned() {
+        return Err(ConversionError::IncompatibleLayout);
+    }
+
+    if T::DTYPE == DType::Bool {
+        let bytes = unsafe { NdArray::<u8>::from_raw_view(ptr as *mut u8, shape.clone(), stride.clone(), false) };
+        if bytes.flatiter().any(|byte| byte > 1) {
+            return Err(ConversionError::InvalidData("invalid booleans".into()));
+        }
+    }
+
+    Ok((ptr, shape, stride))
+}
+
+impl<'a, T: RawDataType> NdArray<'a, T> {
+    /// Views the data of a tensor received from a DLPack producer as a read-only array, without copying.
+    ///
+    /// # Errors
+    /// - If the data is not accessible from the CPU
+    /// - If the tensor does not have dtype `T`
+    /// - If the tensor has negative strides or its data is not aligned for `T`
+    /// - If `T` is `bool` and the data contains bytes other than 0 and 1
+    pub fn from_dlpack(tensor: &'a DLPackTensor) -> Result<Self, ConversionError> {
+        let (ptr, shape, stride) = dl_tensor_layout::<T>(tensor.dl_tensor())?;
+        Ok(unsafe { NdArray::from_raw_view(ptr, shape, stride, false) })
+    }
+
+    /// Views the data of a tensor received from a DLPack producer as a writeable array, without copying.
+    ///
+    /// # Errors
+    /// - If the data is not accessible from the CPU
+    /// - If the tensor does not have dtype `T`
+    /// - If the tensor has negative strides or its data is not aligned for `T`
+    /// - If `T` is `bool` and the data contains bytes other than 0 and 1
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let mut tensor = unsafe { DLPackTensor::from_raw(NdArray::new([1, 2, 3]).to_dlpack()) };
+    ///
+    /// let mut array = NdArray::<i32>::from_dlpack_mut(&mut tensor).unwrap();
+    /// array *= 10;
+    /// assert_eq!(array, NdArray::new([10, 20, 30]));
+    /// ```
+    pub fn from_dlpack_mut(tensor: &'a mut DLPackTensor) -> Result<Self, ConversionError> {
+        let (ptr, shape, stride) = dl_tensor_layout::<T>(tensor.dl_tensor())?;
+        Ok(unsafe { NdArray::from_raw_view(ptr, shape, stride, true) })
+    }
+}
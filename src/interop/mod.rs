@@ -0,0 +1,70 @@
+//! Conversions between `NdArray` and the array types of other libraries,
+//! each enabled by the feature of the same name as the library,
+//! and the DLPack C ABI for exchanging arrays with any library in the same process.
+
+use crate::DType;
+use std::fmt::{Display, Formatter};
+
+mod dlpack;
+pub use dlpack::*;
+
+#[cfg(feature = "ndarray")]
+mod ndarray;
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+
+#[cfg(feature = "arrow")]
+mod arrow;
+
+#[cfg(feature = "arrow")]
+pub use arrow::ArrowDataType;
+
+
+/// An error converting between an `NdArray` and the array type of another library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The array does not have the number of dimensions required by the other type.
+    Dimensionality { expected: usize, found: usize },
+
+    /// The memory layout of the array cannot be shared without copying.
+    IncompatibleLayout,
+
+    /// A mutable view was requested of an array which is not writeable.
+    ReadOnly,
+
+    /// The foreign array does not contain elements of the requested dtype.
+    DtypeMismatch { expected: DType, found: String },
+
+    /// The foreign array contains null values, which an `NdArray` cannot represent.
+    NullValues,
+
+    /// The serialized array is malformed.
+    InvalidData(String),
+
+    /// The data of the foreign array resides on a device of the given DLPack device type,
+    /// which cannot be accessed from the CPU.
+    UnsupportedDevice(i32),
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionError::Dimensionality { expected, found } => {
+                write!(f, "expected an array with {expected} dimensions but found {found}")
+            }
+            ConversionError::IncompatibleLayout => write!(f, "the memory layout of the array cannot be shared"),
+            ConversionError::ReadOnly => write!(f, "the array is not writeable"),
+            ConversionError::DtypeMismatch { expected, found } => {
+                write!(f, "expected elements of dtype {} but found {found}", expected.name())
+            }
+            ConversionError::NullValues => write!(f, "the array contains null values"),
+            ConversionError::InvalidData(reason) => write!(f, "invalid array data: {reason}"),
+            ConversionError::UnsupportedDevice(device_type) => {
+                write!(f, "the array resides on device type {device_type}, which is not accessible from the CPU")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
@@ -0,0 +1,165 @@
+//! Conversions between matrices of the `nalgebra` crate and 2-D arrays,
+//! enabled by the `nalgebra` feature.
+//!
+//! `nalgebra` stores matrices in column-major order with arbitrary positive strides,
+//! so matrices are viewed as arrays (and vice versa) without copying.
+
+use crate::interop::ConversionError;
+use crate::ndarray::flags::NdArrayFlags;
+use crate::{Constructors, NdArray, RawDataType, Reshape, StridedMemory};
+use nalgebra::{DMatrix, DMatrixView, DMatrixViewMut, Dim, Dyn, MatrixView, MatrixViewMut, Scalar};
+
+
+/// Returns the shape and strides of a matrix.
+///
+/// # Errors
+/// - If `array` is not a matrix
+fn matrix_layout<T: RawDataType>(array: &NdArray<T>) -> Result<(usize, usize, usize, usize), ConversionError> {
+    if array.ndims() != 2 {
+        return Err(ConversionError::Dimensionality { expected: 2, found: array.ndims() });
+    }
+
+    let (shape, stride) = (array.shape(), array.stride());
+    Ok((shape[0], shape[1], stride[0], stride[1]))
+}
+
+/// Returns the number of elements spanned by a matrix with the given layout.
+fn strided_len(rows: usize, cols: usize, row_stride: usize, col_stride: usize) -> usize {
+    if rows == 0 || cols == 0 {
+        return 0;
+    }
+    (rows - 1) * row_stride + (cols - 1) * col_stride + 1
+}
+
+impl<T: RawDataType + Scalar> NdArray<'_, T> {
+    /// Returns a view of this matrix as a `nalgebra` matrix, sharing its memory.
+    ///
+    /// # Errors
+    /// - If the array is not a matrix
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([[1.0, 2.0], [3.0, 4.0]]);
+    ///
+    /// let matrix = array.as_matrix_view().unwrap();
+    /// assert_eq!(matrix.determinant(), -2.0);
+    /// ```
+    pub fn as_matrix_view(&self) -> Result<DMatrixView<'_, T, Dyn, Dyn>, ConversionError> {
+        let (rows, cols, row_stride, col_stride) = matrix_layout(self)?;
+
+        let len = strided_len(rows, cols, row_stride, col_stride);
+        let data = unsafe { std::slice::from_raw_parts(self.ptr(), len) };
+
+        Ok(DMatrixView::from_slice_with_strides_generic(data, Dyn(rows), Dyn(cols), Dyn(row_stride), Dyn(col_stride)))
+    }
+
+    /// Returns a mutable view of this matrix as a `nalgebra` matrix, sharing its memory.
+    ///
+    /// # Errors
+    /// - If the array is not a matrix
+    /// - If the array is not writeable, e.g. because it is broadcast
+    pub fn as_matrix_view_mut(&mut self) -> Result<DMatrixViewMut<'_, T, Dyn, Dyn>, ConversionError> {
+        let (rows, cols, row_stride, col_stride) = matrix_layout(self)?;
+
+        if !self.flags.contains(NdArrayFlags::Writeable) {
+            return Err(ConversionError::ReadOnly);
+        }
+
+        let len = strided_len(rows, cols, row_stride, col_stride);
+        let data = unsafe { std::slice::from_raw_parts_mut(self.mut_ptr(), len) };
+
+        Ok(DMatrixViewMut::from_slice_with_strides_generic(data, Dyn(rows), Dyn(cols), Dyn(row_stride), Dyn(col_stride)))
+    }
+}
+
+impl<'a, T: RawDataType + Scalar> TryFrom<&'a NdArray<'_, T>> for DMatrixView<'a, T, Dyn, Dyn> {
+    type Error = ConversionError;
+
+    fn try_from(array: &'a NdArray<'_, T>) -> Result<Self, Self::Error> {
+        array.as_matrix_view()
+    }
+}
+
+impl<'a, T: RawDataType + Scalar> TryFrom<&'a mut NdArray<'_, T>> for DMatrixViewMut<'a, T, Dyn, Dyn> {
+    type Error = ConversionError;
+
+    fn try_from(array: &'a mut NdArray<'_, T>) -> Result<Self, Self::Error> {
+        array.as_matrix_view_mut()
+    }
+}
+
+impl<T: RawDataType + Scalar> TryFrom<NdArray<'_, T>> for DMatrix<T> {
+    type Error = ConversionError;
+
+    /// Copies a matrix into a `nalgebra` matrix.
+    ///
+    /// # Errors
+    /// - If `array` is not a matrix
+    fn try_from(array: NdArray<'_, T>) -> Result<Self, Self::Error> {
+        let (rows, cols, _, _) = matrix_layout(&array)?;
+        Ok(DMatrix::from_row_iterator(rows, cols, array.flatiter()))
+    }
+}
+
+impl<T: RawDataType + Scalar> From<DMatrix<T>> for NdArray<'static, T> {
+    /// Converts a `nalgebra` matrix into a column-major matrix which owns its data without copying.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let matrix = nalgebra::dmatrix![1, 2, 3; 4, 5, 6];
+    ///
+    /// let array = NdArray::from(matrix);
+    /// assert_eq!(array, NdArray::new([[1, 2, 3], [4, 5, 6]]));
+    /// ```
+    fn from(matrix: DMatrix<T>) -> Self {
+        let (rows, cols) = matrix.shape();
+        let data: Vec<T> = matrix.data.into();
+
+        // the column-major buffer is the transpose of a row-major matrix
+        unsafe { NdArray::from_contiguous_owned_buffer(vec![cols, rows], data) }.T()
+    }
+}
+
+impl<'a, T: RawDataType + Scalar> From<&'a DMatrix<T>> for NdArray<'a, T> {
+    /// Views a `nalgebra` matrix as a read-only matrix without copying.
+    fn from(matrix: &'a DMatrix<T>) -> Self {
+        let (rows, cols) = matrix.shape();
+        unsafe { NdArray::from_raw_view(matrix.as_ptr() as *mut T, vec![rows, cols], vec![1, rows], false) }
+    }
+}
+
+impl<'a, T, R, C, RStride, CStride> From<MatrixView<'a, T, R, C, RStride, CStride>> for NdArray<'a, T>
+where
+    T: RawDataType + Scalar,
+    R: Dim,
+    C: Dim,
+    RStride: Dim,
+    CStride: Dim,
+{
+    /// Views a `nalgebra` matrix view as a read-only matrix without copying.
+    fn from(matrix: MatrixView<'a, T, R, C, RStride, CStride>) -> Self {
+        let (rows, cols) = matrix.shape();
+        let (row_stride, col_stride) = matrix.strides();
+
+        unsafe { NdArray::from_raw_view(matrix.as_ptr() as *mut T, vec![rows, cols], vec![row_stride, col_stride], false) }
+    }
+}
+
+impl<'a, T, R, C, RStride, CStride> From<MatrixViewMut<'a, T, R, C, RStride, CStride>> for NdArray<'a, T>
+where
+    T: RawDataType + Scalar,
+    R: Dim,
+    C: Dim,
+    RStride: Dim,
+    CStride: Dim,
+{
+    /// Views a mutable `nalgebra` matrix view as a writeable matrix without copying.
+    fn from(mut matrix: MatrixViewMut<'a, T, R, C, RStride, CStride>) -> Self {
+        let (rows, cols) = matrix.shape();
+        let (row_stride, col_stride) = matrix.strides();
+
+        unsafe { NdArray::from_raw_view(matrix.as_mut_ptr(), vec![rows, cols], vec![row_stride, col_stride], true) }
+    }
+}
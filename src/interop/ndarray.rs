@@ -0,0 +1,152 @@
+//! Conversions to and from the arrays of the `ndarray` crate, enabled by the `ndarray` feature.
+//!
+//! Both libraries describe arrays by a pointer, a shape and strides in units of elements,
+//! so views are shared in both directions without copying. Arrays of the `ndarray` crate
+//! with negative strides cannot be viewed without copying, since `NdArray` strides are unsigned.
+
+use crate::interop::ConversionError;
+use crate::ndarray::flags::NdArrayFlags;
+use crate::{Constructors, NdArray, RawDataType, StridedMemory};
+use ndarray_rs::{ArrayBase, ArrayD, ArrayView, ArrayViewD, ArrayViewMut, ArrayViewMutD, Data, Dimension, IxDyn, OwnedRepr, ShapeBuilder};
+
+
+/// Returns the strides of an array of the `ndarray` crate if none are negative.
+fn unsigned_strides<S: Data, D: Dimension>(array: &ArrayBase<S, D>) -> Option<Vec<usize>> {
+    array.strides().iter().map(|&stride| usize::try_from(stride).ok()).collect()
+}
+
+impl<T: RawDataType> NdArray<'_, T> {
+    /// Returns a view of this array as an array of the `ndarray` crate, sharing its memory.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([[1, 2, 3], [4, 5, 6]]);
+    /// let column = array.slice_along(Axis(1), 1);
+    ///
+    /// let view = column.as_array_view();
+    /// assert_eq!(view.shape(), &[2]);
+    /// assert_eq!(view.sum(), 7);
+    /// ```
+    pub fn as_array_view(&self) -> ArrayViewD<'_, T> {
+        let shape = IxDyn(self.shape()).strides(IxDyn(self.stride()));
+        unsafe { ArrayViewD::from_shape_ptr(shape, self.ptr()) }
+    }
+
+    /// Returns a mutable view of this array as an array of the `ndarray` crate, sharing its memory.
+    ///
+    /// # Errors
+    /// - If the array is not writeable, e.g. because it is broadcast
+    pub fn as_array_view_mut(&mut self) -> Result<ArrayViewMutD<'_, T>, ConversionError> {
+        if !self.flags.contains(NdArrayFlags::Writeable) {
+            return Err(ConversionError::ReadOnly);
+        }
+
+        let shape = IxDyn(self.shape()).strides(IxDyn(self.stride()));
+        Ok(unsafe { ArrayViewMutD::from_shape_ptr(shape, self.mut_ptr()) })
+    }
+}
+
+impl<'a, T: RawDataType> From<&'a NdArray<'_, T>> for ArrayViewD<'a, T> {
+    fn from(array: &'a NdArray<'_, T>) -> Self {
+        array.as_array_view()
+    }
+}
+
+impl<'a, T: RawDataType> TryFrom<&'a mut NdArray<'_, T>> for ArrayViewMutD<'a, T> {
+    type Error = ConversionError;
+
+    fn try_from(array: &'a mut NdArray<'_, T>) -> Result<Self, Self::Error> {
+        array.as_array_view_mut()
+    }
+}
+
+impl<T: RawDataType> From<NdArray<'_, T>> for ArrayD<T> {
+    /// Converts an `NdArray` into an owned array of the `ndarray` crate.
+    ///
+    /// The data is only copied if `array` is a view or is not contiguous.
+    fn from(array: NdArray<'_, T>) -> Self {
+        let shape = IxDyn(array.shape());
+
+        let data = if array.is_view() || !array.is_contiguous() {
+            array.flatiter().collect()
+        } else {
+            array.into_data_vector()
+        };
+
+        ArrayD::from_shape_vec(shape, data).unwrap()
+    }
+}
+
+impl<T: RawDataType, D: Dimension> From<ArrayBase<OwnedRepr<T>, D>> for NdArray<'static, T> {
+    /// Converts an owned array of the `ndarray` crate into an `NdArray`.
+    ///
+    /// The data is only copied if `array` is not in row-major order or does not start its buffer.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// use ndarray_rs::array;
+    ///
+    /// let array = NdArray::from(array![[1.0, 2.0], [3.0, 4.0]]);
+    /// assert_eq!(array, NdArray::new([[1.0, 2.0], [3.0, 4.0]]));
+    /// ```
+    fn from(array: ArrayBase<OwnedRepr<T>, D>) -> Self {
+        let shape = array.shape().to_vec();
+
+        let data = if array.is_standard_layout() {
+            let size = array.len();
+            let (mut data, offset) = array.into_raw_vec_and_offset();
+
+            let offset = offset.unwrap_or(0);
+            if offset != 0 || data.len() != size {
+                data = data[offset..offset + size].to_vec();
+            }
+            data
+        } else {
+            array.iter().copied().collect()
+        };
+
+        unsafe { NdArray::from_contiguous_owned_buffer(shape, data) }
+    }
+}
+
+impl<'a, T: RawDataType, D: Dimension> From<ArrayView<'a, T, D>> for NdArray<'a, T> {
+    /// Views an array of the `ndarray` crate as a read-only `NdArray`.
+    ///
+    /// The data is copied if `array` has negative strides.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let source = ndarray_rs::Array::from_shape_vec((2, 3), vec![1, 2, 3, 4, 5, 6]).unwrap();
+    ///
+    /// let transposed = NdArray::from(source.t());
+    /// assert!(transposed.is_view());
+    /// assert_eq!(transposed, NdArray::new([[1, 4], [2, 5], [3, 6]]));
+    /// ```
+    fn from(array: ArrayView<'a, T, D>) -> Self {
+        match unsigned_strides(&array) {
+            Some(stride) => unsafe {
+                NdArray::from_raw_view(array.as_ptr() as *mut T, array.shape().to_vec(), stride, false)
+            },
+            None => {
+                let data = array.iter().copied().collect();
+                unsafe { NdArray::from_contiguous_owned_buffer(array.shape().to_vec(), data) }
+            }
+        }
+    }
+}
+
+impl<'a, T: RawDataType, D: Dimension> TryFrom<ArrayViewMut<'a, T, D>> for NdArray<'a, T> {
+    type Error = ConversionError;
+
+    /// Views a mutable array of the `ndarray` crate as a writeable `NdArray`.
+    ///
+    /// # Errors
+    /// - If `array` has negative strides
+    fn try_from(mut array: ArrayViewMut<'a, T, D>) -> Result<Self, Self::Error> {
+        let stride = unsigned_strides(&array).ok_or(ConversionError::IncompatibleLayout)?;
+        Ok(unsafe { NdArray::from_raw_view(array.as_mut_ptr(), array.shape().to_vec(), stride, true) })
+    }
+}
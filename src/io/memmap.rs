@@ -0,0 +1,184 @@
+//! Arrays backed by memory-mapped files, for datasets which are too large to fit in memory.
+//!
+//! A `MemmapFile` maps a file of raw, native-endian elements into memory, and `NdArray::memmap()`
+//! views part of it as an array. Only the pages which are accessed are read from disk, and the
+//! operating system can evict them again under memory pressure.
+//!
+//! Views borrow the `MemmapFile` they were created from, so the mapping outlives them.
+//!
+//! The mapping reflects the file on disk, so the file must not be modified or truncated,
+//! by this process or any other, while it is mapped. Otherwise the contents of the arrays
+//! viewing it may change underneath them, and accessing a truncated part of the file
+//! terminates the process.
+//!
+//! # Example
+//! ```
+//! # use redstone_ml::*;
+//! # let path = std::env::temp_dir().join("redstone_memmap_module_example.bin");
+//! let embeddings: Vec<f32> = (0..12).map(|x| x as f32).collect();
+//! std::fs::write(&path, embeddings.iter().flat_map(|x| x.to_ne_bytes()).collect::<Vec<_>>()).unwrap();
+//!
+//! let file = MemmapFile::open(&path, MmapMode::ReadOnly).unwrap();
+//! let embeddings = NdArray::<f32>::memmap(&file, 0, [4, 3]).unwrap();
+//!
+//! let row = embeddings.slice_along(Axis(0), 2);
+//! assert_eq!(row, NdArray::new([6.0, 7.0, 8.0]));
+//! assert_eq!(embeddings.sum(), NdArray::scalar(66.0));
+//! # drop(row);
+//! # drop(embeddings);
+//! # drop(file);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use crate::io::mmap::Mmap;
+use crate::util::to_vec::ToVec;
+use crate::{DType, NdArray, RawDataType};
+use std::fs::File;
+use std::io;
+use std::mem::size_of;
+use std::path::Path;
+
+
+/// How a file is mapped into memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MmapMode {
+    /// The mapping cannot be written to and shares the pages of the file,
+    /// so changes made to the file by other processes are visible through it.
+    ReadOnly,
+
+    /// The mapping can be written to, but the changes are private to it and never reach the file.
+    CopyOnWrite,
+}
+
+/// A file mapped into memory, whose contents can be viewed as arrays with `NdArray::memmap()`.
+pub struct MemmapFile {
+    mmap: Mmap,
+    mode: MmapMode,
+}
+
+impl MemmapFile {
+    /// Maps the file at `path` into memory with the given `mode`.
+    ///
+    /// The file must not be modified or truncated while it is mapped (see the module documentation).
+    ///
+    /// # Errors
+    /// - If the file cannot be opened or mapped
+    pub fn open(path: impl AsRef<Path>, mode: MmapMode) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        let mmap = match mode {
+            MmapMode::ReadOnly => Mmap::map_read_only(&file)?,
+            MmapMode::CopyOnWrite => Mmap::map_copy_on_write(&file)?,
+        };
+
+        Ok(Self { mmap, mode })
+    }
+
+    /// Returns the mode the file was mapped with.
+    pub fn mode(&self) -> MmapMode {
+        self.mode
+    }
+
+    /// Returns the size of the file in bytes.
+    pub fn len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    /// Returns whether the file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.mmap.len() == 0
+    }
+}
+
+/// Returns the byte range of an array of dtype `T` and the given shape at `offset` into `bytes`,
+/// validating that it lies within the file and is aligned.
+fn array_range<T: RawDataType>(bytes: &[u8], offset: usize, shape: &[usize]) -> io::Result<(usize, usize)> {
+    let invalid = |reason: String| io::Error::new(io::ErrorKind::InvalidInput, reason);
+
+    let end = shape.iter()
+        .try_fold(size_of::<T>(), |size, &dim| size.checked_mul(dim))
+        .and_then(|size| size.checked_add(offset))
+        .filter(|&end| end <= bytes.len())
+        .ok_or_else(|| invalid(format!("an array of shape {shape:?} at offset {offset} does not fit in the file")))?;
+
+    // empty arrays do not point into the file
+    if end != offset && !bytes[offset..].as_ptr().cast::<T>().is_aligned() {
+        return Err(invalid(format!("offset {offset} is not aligned for dtype {}", T::DTYPE.name())));
+    }
+
+    if T::DTYPE == DType::Bool && bytes[offset..end].iter().any(|&byte| byte > 1) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "the file contains invalid booleans"));
+    }
+
+    Ok((offset, end))
+}
+
+impl<'a, T: RawDataType> NdArray<'a, T> {
+    /// Views the elements of dtype `T` starting `offset` bytes into `file` as a read-only array
+    /// with the given `shape`, without reading them into memory.
+    ///
+    /// The elements must be stored contiguously in row-major order and in native byte order.
+    /// The array is not writeable, so writing to it panics.
+    ///
+    /// # Errors
+    /// - If the array extends past the end of the file
+    /// - If `offset` is not aligned for `T`
+    /// - If `T` is `bool` and the data contains bytes other than 0 and 1
+    pub fn memmap(file: &'a MemmapFile, offset: usize, shape: impl ToVec<usize>) -> io::Result<Self> {
+        let shape = shape.to_vec();
+        let bytes = file.mmap.as_slice();
+        let (start, end) = array_range::<T>(bytes, offset, &shape)?;
+
+        if start == end {
+            return Ok(NdArray::from_contiguous_borrowed_buffer(shape, &[]));
+        }
+
+        let data = unsafe { std::slice::from_raw_parts(bytes[start..end].as_ptr() as *const T, (end - start) / size_of::<T>()) };
+        Ok(NdArray::from_contiguous_borrowed_buffer(shape, data))
+    }
+
+    /// Views the elements of dtype `T` starting `offset` bytes into `file` as a writeable array
+    /// with the given `shape`, like `memmap()`.
+    ///
+    /// Writes to the array are private to the mapping and never reach the file.
+    ///
+    /// # Errors
+    /// - If `file` was not mapped with `MmapMode::CopyOnWrite`
+    /// - If the array extends past the end of the file
+    /// - If `offset` is not aligned for `T`
+    /// - If `T` is `bool` and the data contains bytes other than 0 and 1
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// # let path = std::env::temp_dir().join("redstone_memmap_mut_example.bin");
+    /// std::fs::write(&path, [1u8, 2, 3, 4]).unwrap();
+    ///
+    /// let mut file = MemmapFile::open(&path, MmapMode::CopyOnWrite).unwrap();
+    /// let mut array = NdArray::<u8>::memmap_mut(&mut file, 0, [2, 2]).unwrap();
+    /// array += 10;
+    /// assert_eq!(array, NdArray::new([[11, 12], [13, 14]]));
+    ///
+    /// // the file is unchanged
+    /// assert_eq!(std::fs::read(&path).unwrap(), [1, 2, 3, 4]);
+    /// # drop(array);
+    /// # drop(file);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn memmap_mut(file: &'a mut MemmapFile, offset: usize, shape: impl ToVec<usize>) -> io::Result<Self> {
+        if file.mode != MmapMode::CopyOnWrite {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, "the file was not mapped as copy-on-write"));
+        }
+
+        let shape = shape.to_vec();
+        let (start, end) = array_range::<T>(file.mmap.as_slice(), offset, &shape)?;
+
+        if start == end {
+            return Ok(NdArray::from_contiguous_borrowed_buffer_mut(shape, &mut []));
+        }
+
+        let bytes = unsafe { &mut file.mmap.as_mut_slice()[start..end] };
+        let data = unsafe { std::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, bytes.len() / size_of::<T>()) };
+        Ok(NdArray::from_contiguous_borrowed_buffer_mut(shape, data))
+    }
+}
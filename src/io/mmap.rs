@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::io;
+
+/// A mapping of an entire file into memory.
+///
+/// Pages are loaded lazily by the operating system, so mapping a large file is cheap
+/// and only the parts that are read occupy memory.
+/// On platforms without `mmap`, the file is read into memory instead.
+pub(crate) struct Mmap {
+    #[cfg(unix)]
+    ptr: *mut libc::c_void,
+
+    #[cfg(not(unix))]
+    data: Vec<u128>,
+
+    len: usize,
+}
+
+// the mapping is private to this process and never written through a shared reference
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+impl Mmap {
+    /// Maps the contents of `file` into memory as read-only.
+    pub(crate) fn map_read_only(file: &File) -> io::Result<Self> {
+        Self::map(file, false)
+    }
+
+    /// Maps the contents of `file` into memory as copy-on-write,
+    /// so that writes to the mapping are private to it and never reach the file.
+    pub(crate) fn map_copy_on_write(file: &File) -> io::Result<Self> {
+        Self::map(file, true)
+    }
+
+    #[cfg(unix)]
+    fn map(file: &File, copy_on_write: bool) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "file is too large to map"))?;
+
+        // mmap rejects empty mappings
+        if len == 0 {
+            return Ok(Self { ptr: std::ptr::null_mut(), len });
+        }
+
+        let (protection, flags) = if copy_on_write {
+            (libc::PROT_READ | libc::PROT_WRITE, libc::MAP_PRIVATE)
+        } else {
+            (libc::PROT_READ, libc::MAP_SHARED)
+        };
+
+        let ptr = unsafe {
+            libc::mmap(std::ptr::null_mut(), len, protection, flags, file.as_raw_fd(), 0)
+        };
+
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { ptr, len })
+    }
+
+    #[cfg(not(unix))]
+    fn map(file: &File, _copy_on_write: bool) -> io::Result<Self> {
+        use std::io::Read;
+
+        let mut bytes = Vec::new();
+        (&*file).read_to_end(&mut bytes)?;
+
+        // the buffer is over-aligned so that it can hold elements of any dtype like a mapping
+        let len = bytes.len();
+        let mut data = vec![0u128; len.div_ceil(size_of::<u128>())];
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), data.as_mut_ptr() as *mut u8, len); }
+
+        Ok(Self { data, len })
+    }
+
+    /// Returns the length of the mapping in bytes.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a pointer to the start of the mapping, which is aligned to a page.
+    fn as_ptr(&self) -> *mut u8 {
+        #[cfg(unix)]
+        let ptr = self.ptr as *mut u8;
+
+        #[cfg(not(unix))]
+        let ptr = self.data.as_ptr() as *mut u8;
+
+        ptr
+    }
+
+    /// Returns the mapped bytes.
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+
+        unsafe { std::slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    /// Returns the mapped bytes mutably.
+    ///
+    /// # Safety
+    /// - The file must have been mapped as copy-on-write
+    pub(crate) unsafe fn as_mut_slice(&mut self) -> &mut [u8] {
+        if self.len == 0 {
+            return &mut [];
+        }
+
+        std::slice::from_raw_parts_mut(self.as_ptr(), self.len)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe { libc::munmap(self.ptr, self.len); }
+        }
+    }
+}
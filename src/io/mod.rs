@@ -0,0 +1,11 @@
+pub(crate) mod json;
+pub(crate) mod mmap;
+
+pub mod safetensors;
+pub use safetensors::*;
+
+pub mod text;
+pub use text::*;
+
+pub mod memmap;
+pub use memmap::*;
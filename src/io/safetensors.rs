@@ -0,0 +1,464 @@
+//! Saving and loading named tensors in the [safetensors](https://github.com/huggingface/safetensors)
+//! format.
+//!
+//! A safetensors file starts with the length of its header as a little-endian `u64`,
+//! followed by a JSON header describing the dtype, shape and byte range of each tensor,
+//! followed by the raw little-endian data of every tensor.
+//!
+//! Files are memory-mapped when loaded, so arrays are read lazily and views of the file's data
+//! are returned without copying whenever its layout allows it.
+//!
+//! # Example
+//! ```
+//! # use redstone_ml::*;
+//! # let path = std::env::temp_dir().join("redstone_safetensors_module_example.safetensors");
+//! let weight = Tensor::<f32>::randn([4, 3]);
+//! let bias = Tensor::<f32>::zeros([4]);
+//!
+//! save_state_dict(&path, &[("weight", &weight), ("bias", &bias)]).unwrap();
+//!
+//! let mut new_weight = Tensor::<f32>::zeros([4, 3]);
+//! let mut new_bias = Tensor::<f32>::ones([4]);
+//!
+//! let state_dict = load_state_dict(&path).unwrap();
+//! state_dict.load_into(&mut [("weight", &mut new_weight), ("bias", &mut new_bias)], true).unwrap();
+//!
+//! assert_eq!(new_weight, weight);
+//! assert_eq!(new_bias, bias);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use crate::io::json::{write_json_string, JsonValue};
+use crate::io::mmap::Mmap;
+use crate::{Constructors, DType, NdArray, RawDataType, StridedMemory, Tensor, TensorDataType};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Write as _};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+use std::path::Path;
+
+
+/// An error encountered while saving or loading a state dict.
+#[derive(Debug)]
+pub enum StateDictError {
+    /// The file could not be read or written.
+    Io(std::io::Error),
+
+    /// The file is not a valid safetensors file.
+    InvalidFile(String),
+
+    /// The dtype cannot be stored in a safetensors file.
+    UnsupportedDtype(DType),
+
+    /// Several tensors were given the same name.
+    DuplicateKey(String),
+
+    /// A tensor has a different dtype than the one it is loaded as.
+    DtypeMismatch { name: String, expected: DType, found: DType },
+
+    /// A tensor has a different shape than the one it is loaded into.
+    ShapeMismatch { name: String, expected: Vec<usize>, found: Vec<usize> },
+
+    /// There is no tensor with the given name.
+    MissingKey(String),
+
+    /// The names of the tensors in the file and those being loaded do not match.
+    IncompatibleKeys(IncompatibleKeys),
+}
+
+/// The names which differ between a state dict and the tensors it is loaded into.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IncompatibleKeys {
+    /// Names of tensors which are being loaded but are not in the state dict.
+    pub missing_keys: Vec<String>,
+
+    /// Names of tensors in the state dict which are not being loaded.
+    pub unexpected_keys: Vec<String>,
+}
+
+impl IncompatibleKeys {
+    /// Returns whether every tensor was loaded and every tensor in the state dict was used.
+    pub fn is_empty(&self) -> bool {
+        self.missing_keys.is_empty() && self.unexpected_keys.is_empty()
+    }
+}
+
+impl Display for StateDictError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateDictError::Io(err) => write!(f, "{err}"),
+            StateDictError::InvalidFile(reason) => write!(f, "invalid safetensors file: {reason}"),
+            StateDictError::UnsupportedDtype(dtype) => write!(f, "dtype {dtype:?} is not supported by safetensors"),
+            StateDictError::DuplicateKey(name) => write!(f, "duplicate tensor name '{name}'"),
+            StateDictError::DtypeMismatch { name, expected, found } => {
+                write!(f, "tensor '{name}' has dtype {found:?} but {expected:?} was expected")
+            }
+            StateDictError::ShapeMismatch { name, expected, found } => {
+                write!(f, "tensor '{name}' has shape {found:?} but {expected:?} was expected")
+            }
+            StateDictError::MissingKey(name) => write!(f, "no tensor named '{name}'"),
+            StateDictError::IncompatibleKeys(keys) => {
+                write!(f, "missing keys {:?}, unexpected keys {:?}", keys.missing_keys, keys.unexpected_keys)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StateDictError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StateDictError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for StateDictError {
+    fn from(err: std::io::Error) -> Self {
+        StateDictError::Io(err)
+    }
+}
+
+/// The name of a dtype in a safetensors header.
+fn dtype_name(dtype: DType) -> Option<&'static str> {
+    Some(match dtype {
+        DType::Bool => "BOOL",
+        DType::U8 => "U8",
+        DType::I8 => "I8",
+        DType::U16 => "U16",
+        DType::I16 => "I16",
+        DType::F16 => "F16",
+        DType::BF16 => "BF16",
+        DType::U32 => "U32",
+        DType::I32 => "I32",
+        DType::F32 => "F32",
+        DType::U64 => "U64",
+        DType::I64 => "I64",
+        DType::F64 => "F64",
+        _ => return None,
+    })
+}
+
+fn dtype_from_name(name: &str) -> Option<DType> {
+    Some(match name {
+        "BOOL" => DType::Bool,
+        "U8" => DType::U8,
+        "I8" => DType::I8,
+        "U16" => DType::U16,
+        "I16" => DType::I16,
+        "F16" => DType::F16,
+        "BF16" => DType::BF16,
+        "U32" => DType::U32,
+        "I32" => DType::I32,
+        "F32" => DType::F32,
+        "U64" => DType::U64,
+        "I64" => DType::I64,
+        "F64" => DType::F64,
+        _ => return None,
+    })
+}
+
+/// Writes `tensors` to a safetensors file at `path` under the given names.
+///
+/// Non-contiguous tensors are written in their logical order,
+/// and whether they require gradients is not saved.
+///
+/// # Errors
+/// - If two tensors have the same name
+/// - If the file cannot be written
+pub fn save_state_dict<T: TensorDataType>(path: impl AsRef<Path>, tensors: &[(&str, &Tensor<T>)]) -> Result<(), StateDictError> {
+    let arrays: Vec<_> = tensors.iter().map(|(name, tensor)| (*name, tensor.ndarray())).collect();
+    save_ndarrays(path, &arrays)
+}
+
+/// Writes `arrays` to a safetensors file at `path` under the given names.
+///
+/// # Errors
+/// - If two arrays have the same name
+/// - If `T` cannot be stored in a safetensors file
+/// - If the file cannot be written
+pub fn save_ndarrays<T: RawDataType>(path: impl AsRef<Path>, arrays: &[(&str, &NdArray<T>)]) -> Result<(), StateDictError> {
+    let dtype = dtype_name(T::DTYPE).ok_or(StateDictError::UnsupportedDtype(T::DTYPE))?;
+
+    let mut names = HashSet::new();
+    let mut header = String::from("{");
+    let mut offset = 0;
+
+    for (i, (name, array)) in arrays.iter().enumerate() {
+        if !names.insert(*name) {
+            return Err(StateDictError::DuplicateKey(name.to_string()));
+        }
+
+        if i != 0 {
+            header.push(',');
+        }
+
+        let size = array.size() * size_of::<T>();
+        let shape = array.shape().iter().map(usize::to_string).collect::<Vec<_>>().join(",");
+
+        write_json_string(&mut header, name);
+        write!(header, r#":{{"dtype":"{dtype}","shape":[{shape}],"data_offsets":[{offset},{}]}}"#, offset + size).unwrap();
+        offset += size;
+    }
+    header.push('}');
+
+    // the header is padded with spaces so that the data which follows is aligned
+    let mut header = header.into_bytes();
+    header.resize(header.len().next_multiple_of(8), b' ');
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&(header.len() as u64).to_le_bytes())?;
+    writer.write_all(&header)?;
+
+    for (_, array) in arrays {
+        let data: Vec<T> = array.flatiter().collect();
+        writer.write_all(&to_le_bytes(&data))?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Opens the safetensors file at `path`, whose tensors can then be read with `StateDict::get()`
+/// or copied into existing tensors with `StateDict::load_into()`.
+///
+/// The file is memory-mapped, so only the header is read until a tensor is accessed.
+///
+/// # Errors
+/// - If the file cannot be read
+/// - If the file is not a valid safetensors file
+pub fn load_state_dict(path: impl AsRef<Path>) -> Result<StateDict, StateDictError> {
+    let mmap = Mmap::map_copy_on_write(&File::open(path)?)?;
+    let entries = parse_header(mmap.as_slice())?;
+
+    Ok(StateDict { mmap, entries })
+}
+
+/// The dtype, shape and location in the file of a tensor in a state dict.
+struct Entry {
+    dtype: DType,
+    shape: Vec<usize>,
+    start: usize,
+    end: usize,
+}
+
+/// A memory-mapped safetensors file returned by `load_state_dict()`.
+pub struct StateDict {
+    mmap: Mmap,
+    entries: HashMap<String, Entry>,
+}
+
+impl StateDict {
+    /// Returns the names of the tensors in the state dict in the order they are stored.
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<_> = self.entries.iter().collect();
+        keys.sort_by_key(|(_, entry)| entry.start);
+        keys.into_iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Returns the number of tensors in the state dict.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the state dict contains no tensors.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns whether the state dict contains a tensor named `name`.
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Returns the dtype of the tensor named `name`.
+    pub fn dtype(&self, name: &str) -> Option<DType> {
+        self.entries.get(name).map(|entry| entry.dtype)
+    }
+
+    /// Returns the shape of the tensor named `name`.
+    pub fn shape(&self, name: &str) -> Option<&[usize]> {
+        self.entries.get(name).map(|entry| entry.shape.as_slice())
+    }
+
+    /// Returns the tensor named `name` as an array.
+    ///
+    /// The array is a read-only view into the memory-mapped file if its data is suitably aligned,
+    /// and an owned copy otherwise.
+    ///
+    /// # Errors
+    /// - If there is no tensor named `name`
+    /// - If the tensor does not have dtype `T`
+    /// - If a boolean tensor contains bytes other than 0 and 1
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// # let path = std::env::temp_dir().join("redstone_safetensors_get_example.safetensors");
+    /// save_ndarrays(&path, &[("x", &NdArray::new([[1, 2], [3, 4]]))]).unwrap();
+    ///
+    /// let state_dict = load_state_dict(&path).unwrap();
+    /// let x = state_dict.get::<i32>("x").unwrap();
+    /// assert_eq!(x, NdArray::new([[1, 2], [3, 4]]));
+    ///
+    /// assert!(state_dict.get::<f32>("x").is_err());
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn get<T: RawDataType>(&self, name: &str) -> Result<NdArray<'_, T>, StateDictError> {
+        let entry = self.entries.get(name).ok_or_else(|| StateDictError::MissingKey(name.to_string()))?;
+
+        if entry.dtype != T::DTYPE {
+            return Err(StateDictError::DtypeMismatch { name: name.to_string(), expected: T::DTYPE, found: entry.dtype });
+        }
+
+        let bytes = &self.mmap.as_slice()[entry.start..entry.end];
+        if T::DTYPE == DType::Bool && bytes.iter().any(|&byte| byte > 1) {
+            return Err(StateDictError::InvalidFile(format!("tensor '{name}' contains invalid booleans")));
+        }
+
+        let len = bytes.len() / size_of::<T>();
+        let shape = entry.shape.clone();
+
+        if cfg!(target_endian = "little") && bytes.as_ptr().cast::<T>().is_aligned() {
+            let data = unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, len) };
+            return Ok(NdArray::from_contiguous_borrowed_buffer(shape, data));
+        }
+
+        let data = from_le_bytes(bytes, len);
+        Ok(unsafe { NdArray::from_contiguous_owned_buffer(shape, data) })
+    }
+
+    /// Copies the tensors of the state dict into `tensors` by name.
+    ///
+    /// The dtype and shape of each tensor are validated before any tensor is modified.
+    /// Tensors keep their gradient requirements and the copies are not recorded in the graph.
+    ///
+    /// Returns the names of tensors which were not found in the state dict,
+    /// and of tensors in the state dict which were not loaded.
+    ///
+    /// # Errors
+    /// - If a tensor has a different dtype or shape than the one in the state dict
+    /// - If `strict` and any names do not match
+    pub fn load_into<T: TensorDataType>(&self, tensors: &mut [(&str, &mut Tensor<T>)], strict: bool)
+                                        -> Result<IncompatibleKeys, StateDictError> {
+        let names: HashSet<&str> = tensors.iter().map(|(name, _)| *name).collect();
+
+        let mut keys = IncompatibleKeys::default();
+        keys.unexpected_keys = self.keys().into_iter()
+            .filter(|name| !names.contains(name))
+            .map(str::to_string)
+            .collect();
+
+        let mut arrays = Vec::with_capacity(tensors.len());
+        for (name, tensor) in tensors.iter() {
+            if !self.contains_key(name) {
+                keys.missing_keys.push(name.to_string());
+                arrays.push(None);
+                continue;
+            }
+
+            let array = self.get::<T>(name)?;
+            if array.shape() != tensor.shape() {
+                return Err(StateDictError::ShapeMismatch {
+                    name: name.to_string(),
+                    expected: tensor.shape().to_vec(),
+                    found: array.shape().to_vec(),
+                });
+            }
+            arrays.push(Some(array));
+        }
+
+        if strict && !keys.is_empty() {
+            return Err(StateDictError::IncompatibleKeys(keys));
+        }
+
+        for ((_, tensor), array) in tensors.iter_mut().zip(arrays) {
+            if let Some(array) = array {
+                *tensor.ndarray_mut() = array.clone();
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+/// Parses and validates the header of the safetensors file `file`.
+fn parse_header(file: &[u8]) -> Result<HashMap<String, Entry>, StateDictError> {
+    let invalid = |reason: &str| StateDictError::InvalidFile(reason.to_string());
+
+    let header_len = file.get(..8).ok_or_else(|| invalid("the file is too short"))?;
+    let header_len = u64::from_le_bytes(header_len.try_into().unwrap());
+
+    let data_start = usize::try_from(header_len).ok()
+        .and_then(|len| len.checked_add(8))
+        .filter(|&start| start <= file.len())
+        .ok_or_else(|| invalid("the header is longer than the file"))?;
+
+    let header = JsonValue::parse(&file[8..data_start]).map_err(StateDictError::InvalidFile)?;
+    let JsonValue::Object(header) = header else {
+        return Err(invalid("the header is not an object"));
+    };
+
+    let data_len = file.len() - data_start;
+    let mut entries = HashMap::with_capacity(header.len());
+
+    for (name, info) in header {
+        if name == "__metadata__" {
+            continue;
+        }
+
+        let invalid_entry = |reason: &str| StateDictError::InvalidFile(format!("tensor '{name}' {reason}"));
+
+        let dtype = info.get("dtype").and_then(JsonValue::as_str)
+            .ok_or_else(|| invalid_entry("has no dtype"))?;
+        let dtype = dtype_from_name(dtype)
+            .ok_or_else(|| invalid_entry(&format!("has the unsupported dtype {dtype}")))?;
+
+        let shape = info.get("shape").and_then(JsonValue::as_array)
+            .and_then(|shape| shape.iter().map(|dim| dim.as_u64().map(|dim| dim as usize)).collect::<Option<Vec<_>>>())
+            .ok_or_else(|| invalid_entry("has an invalid shape"))?;
+
+        let offsets = info.get("data_offsets").and_then(JsonValue::as_array)
+            .filter(|offsets| offsets.len() == 2)
+            .and_then(|offsets| Some((offsets[0].as_u64()? as usize, offsets[1].as_u64()? as usize)))
+            .ok_or_else(|| invalid_entry("has invalid data offsets"))?;
+
+        let (start, end) = offsets;
+        if start > end || end > data_len {
+            return Err(invalid_entry("has data outside of the file"));
+        }
+
+        let size = shape.iter().try_fold(dtype.size(), |size, &dim| size.checked_mul(dim));
+        if size != Some(end - start) {
+            return Err(invalid_entry("has a shape which does not match its data"));
+        }
+
+        entries.insert(name, Entry { dtype, shape, start: data_start + start, end: data_start + end });
+    }
+
+    Ok(entries)
+}
+
+/// Returns the little-endian bytes of `data`.
+pub(crate) fn to_le_bytes<T: RawDataType>(data: &[T]) -> Vec<u8> {
+    let bytes = unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, size_of_val(data)) };
+
+    let mut bytes = bytes.to_vec();
+    if cfg!(target_endian = "big") {
+        bytes.chunks_exact_mut(size_of::<T>()).for_each(<[u8]>::reverse);
+    }
+    bytes
+}
+
+/// Reads `len` elements from the little-endian `bytes`, which need not be aligned.
+pub(crate) fn from_le_bytes<T: RawDataType>(bytes: &[u8], len: usize) -> Vec<T> {
+    let mut data = vec![T::default(); len];
+    let dst = unsafe { std::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, size_of_val(data.as_slice())) };
+
+    dst.copy_from_slice(bytes);
+    if cfg!(target_endian = "big") {
+        dst.chunks_exact_mut(size_of::<T>()).for_each(<[u8]>::reverse);
+    }
+    data
+}
@@ -0,0 +1,255 @@
+//! Reading and writing matrices as delimited text, such as CSV files.
+//!
+//! Each line of a file is a row of the matrix and each row is split into fields by a delimiter,
+//! or by runs of whitespace if there is no delimiter. Blank lines and lines starting with `#`
+//! are skipped.
+//!
+//! # Example
+//! ```
+//! # use redstone_ml::*;
+//! # let path = std::env::temp_dir().join("redstone_text_module_example.csv");
+//! let array = NdArray::new([[1.5, 2.0], [3.0, -4.25]]);
+//! array.savetxt(&path, ",", None, |x| format!("{x:.2}")).unwrap();
+//!
+//! assert_eq!(std::fs::read_to_string(&path).unwrap(), "1.50,2.00\n3.00,-4.25\n");
+//!
+//! let loaded = NdArray::<f64>::from_csv(&path, ',', 0).unwrap();
+//! assert_eq!(loaded, array);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use crate::{Constructors, NdArray, RawDataType, StridedMemory};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+
+/// An error encountered while reading or writing a text file.
+#[derive(Debug)]
+pub enum TextError {
+    /// The file could not be read or written.
+    Io(std::io::Error),
+
+    /// A field could not be parsed as the dtype of the array.
+    Parse { line: usize, column: usize, value: String },
+
+    /// A row has a different number of fields than the first row.
+    RaggedRow { line: usize, expected: usize, found: usize },
+}
+
+impl Display for TextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextError::Io(err) => write!(f, "{err}"),
+            TextError::Parse { line, column, value } => {
+                write!(f, "line {line}, column {column}: could not parse '{value}'")
+            }
+            TextError::RaggedRow { line, expected, found } => {
+                write!(f, "line {line}: expected {expected} fields but found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TextError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TextError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for TextError {
+    fn from(err: std::io::Error) -> Self {
+        TextError::Io(err)
+    }
+}
+
+impl<T: RawDataType + FromStr> NdArray<'_, T> {
+    /// Reads a matrix from the CSV file at `path` whose fields are separated by `delimiter`,
+    /// after skipping its first `skip_header` lines.
+    ///
+    /// This is `loadtxt()` with a delimiter.
+    ///
+    /// # Errors
+    /// - If the file cannot be read
+    /// - If a field is missing or cannot be parsed as `T`
+    /// - If the rows have different numbers of fields
+    pub fn from_csv(path: impl AsRef<Path>, delimiter: char, skip_header: usize) -> Result<NdArray<'static, T>, TextError> {
+        NdArray::loadtxt(path, Some(delimiter), skip_header)
+    }
+
+    /// Reads a matrix from the text file at `path`, after skipping its first `skip_header` lines.
+    ///
+    /// Fields are separated by `delimiter` or by whitespace if it is `None`,
+    /// and surrounding whitespace is ignored. The result always has 2 dimensions,
+    /// with a row for each non-blank line which is not a comment.
+    ///
+    /// # Errors
+    /// - If the file cannot be read
+    /// - If a field is missing or cannot be parsed as `T`
+    /// - If the rows have different numbers of fields
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// # let path = std::env::temp_dir().join("redstone_loadtxt_example.txt");
+    /// std::fs::write(&path, "x y\n# a comment\n1 2\n3   4\n").unwrap();
+    ///
+    /// let array = NdArray::<i32>::loadtxt(&path, None, 1).unwrap();
+    /// assert_eq!(array, NdArray::new([[1, 2], [3, 4]]));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn loadtxt(path: impl AsRef<Path>, delimiter: Option<char>, skip_header: usize) -> Result<NdArray<'static, T>, TextError> {
+        let reader = BufReader::new(File::open(path)?);
+
+        let (shape, data) = parse_rows(reader, delimiter, skip_header, |line, column, field| {
+            field.parse().map_err(|_| TextError::Parse { line, column, value: field.to_string() })
+        })?;
+
+        Ok(unsafe { NdArray::from_contiguous_owned_buffer(shape, data) })
+    }
+
+    /// Reads a matrix from the text file at `path` like `loadtxt()`,
+    /// but replaces missing values with `filling_value`.
+    ///
+    /// A value is missing if its field is empty or equal to one of `missing_values`.
+    /// Returns the matrix and a mask of the same shape which is `true` where values were missing.
+    ///
+    /// # Errors
+    /// - If the file cannot be read
+    /// - If a field which is not missing cannot be parsed as `T`
+    /// - If the rows have different numbers of fields
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// # let path = std::env::temp_dir().join("redstone_genfromtxt_example.csv");
+    /// std::fs::write(&path, "a,b,c\n1.0,,3.0\nNA,5.0,6.0\n").unwrap();
+    ///
+    /// let (array, missing) = NdArray::<f32>::genfromtxt(&path, Some(','), 1, &["NA"], f32::NAN).unwrap();
+    ///
+    /// assert!(array[[0, 1]].is_nan() && array[[1, 0]].is_nan());
+    /// assert_eq!(array[[1, 2]], 6.0);
+    /// assert_eq!(missing, NdArray::new([[false, true, false], [true, false, false]]));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn genfromtxt(path: impl AsRef<Path>,
+                      delimiter: Option<char>,
+                      skip_header: usize,
+                      missing_values: &[&str],
+                      filling_value: T) -> Result<(NdArray<'static, T>, NdArray<'static, bool>), TextError> {
+        let reader = BufReader::new(File::open(path)?);
+
+        let (shape, data) = parse_rows(reader, delimiter, skip_header, |line, column, field| {
+            if field.is_empty() || missing_values.contains(&field) {
+                return Ok((filling_value, true));
+            }
+
+            match field.parse() {
+                Ok(value) => Ok((value, false)),
+                Err(_) => Err(TextError::Parse { line, column, value: field.to_string() }),
+            }
+        })?;
+
+        let (data, mask) = data.into_iter().unzip();
+
+        unsafe {
+            Ok((NdArray::from_contiguous_owned_buffer(shape.clone(), data),
+                NdArray::from_contiguous_owned_buffer(shape, mask)))
+        }
+    }
+}
+
+impl<T: RawDataType> NdArray<'_, T> {
+    /// Writes this array to the text file at `path` with a line for each row,
+    /// whose elements are formatted by `format` and separated by `delimiter`.
+    ///
+    /// If given, the lines of `header` are written first, each prefixed by `# `.
+    /// Vectors and scalars are written with one element per line.
+    ///
+    /// # Panics
+    /// - If the array has more than 2 dimensions
+    ///
+    /// # Errors
+    /// - If the file cannot be written
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// # let path = std::env::temp_dir().join("redstone_savetxt_example.txt");
+    /// let array = NdArray::new([[1.0, 0.5], [0.25, 2.0]]);
+    /// array.savetxt(&path, " ", Some("x y"), |x| format!("{x:e}")).unwrap();
+    ///
+    /// assert_eq!(std::fs::read_to_string(&path).unwrap(), "# x y\n1e0 5e-1\n2.5e-1 2e0\n");
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn savetxt(&self,
+                   path: impl AsRef<Path>,
+                   delimiter: &str,
+                   header: Option<&str>,
+                   format: impl Fn(T) -> String) -> Result<(), TextError> {
+        assert!(self.ndims() <= 2, "savetxt: the array must have at most 2 dimensions");
+
+        let columns = if self.ndims() == 2 { self.shape()[1] } else { 1 };
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        if let Some(header) = header {
+            for line in header.lines() {
+                writeln!(writer, "# {line}")?;
+            }
+        }
+
+        for (i, value) in self.flatiter().enumerate() {
+            let separator = if (i + 1) % columns == 0 { "\n" } else { delimiter };
+            write!(writer, "{}{separator}", format(value))?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Splits the rows of `reader` into fields and parses each with `parse(line, column, field)`,
+/// returning the shape of the matrix and its elements in row-major order.
+fn parse_rows<R, F>(reader: impl BufRead,
+                    delimiter: Option<char>,
+                    skip_header: usize,
+                    mut parse: F) -> Result<(Vec<usize>, Vec<R>), TextError>
+where
+    F: FnMut(usize, usize, &str) -> Result<R, TextError>,
+{
+    let mut data = Vec::new();
+    let mut rows = 0;
+    let mut columns = None;
+
+    for (i, line) in reader.lines().enumerate().skip(skip_header) {
+        let line = line?;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // the delimiter may itself be whitespace, so only the line ending is stripped before splitting
+        let fields: Vec<&str> = match delimiter {
+            Some(delimiter) => line.trim_end_matches(['\r', '\n']).split(delimiter).map(str::trim).collect(),
+            None => trimmed.split_whitespace().collect(),
+        };
+
+        let expected = *columns.get_or_insert(fields.len());
+        if fields.len() != expected {
+            return Err(TextError::RaggedRow { line: i + 1, expected, found: fields.len() });
+        }
+
+        for (j, field) in fields.into_iter().enumerate() {
+            data.push(parse(i + 1, j + 1, field)?);
+        }
+        rows += 1;
+    }
+
+    Ok((vec![rows, columns.unwrap_or(0)], data))
+}
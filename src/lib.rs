@@ -17,6 +17,9 @@ pub use ndarray::*;
 pub mod tensor;
 pub use tensor::*;
 
+pub mod dual_tensor;
+pub use dual_tensor::*;
+
 pub mod common;
 pub use common::*;
 
@@ -29,5 +32,14 @@ pub use util::*;
 pub mod autograd;
 pub use autograd::*;
 
+pub mod io;
+pub use io::*;
+
+pub mod interop;
+pub use interop::*;
+
 pub mod ops;
 pub mod profiler;
+
+pub use half::{bf16, f16};
+pub use num::complex::{Complex, Complex32, Complex64};
@@ -524,3 +524,39 @@ impl MatrixOps for f64 {
         }
     }
 }
+
+/// Half-precision matrix products are accumulated in `f32` and rounded once at the end.
+macro_rules! impl_matrix_ops_via_f32 {
+    ($($dtype:ty),*) => {
+        $(
+            impl MatrixOps for $dtype {
+                unsafe fn matrix_matrix_product<'a>(lhs: &NdArray<'a, Self>,
+                                                    rhs: &NdArray<'a, Self>,
+                                                    result_stride: &[usize],
+                                                    result: *mut Self) {
+                    let lhs = lhs.map(|x| x.to_f32());
+                    let rhs = rhs.map(|x| x.to_f32());
+                    let product = lhs.matmul(&rhs);
+
+                    let cols = product.shape()[1];
+                    for (i, value) in product.flatiter().enumerate() {
+                        let offset = (i / cols) * result_stride[0] + (i % cols) * result_stride[1];
+                        *result.add(offset) = Self::from_f32(value);
+                    }
+                }
+
+                unsafe fn matrix_vector_product<'a, 'b, 'r>(matrix: &NdArray<'a, Self>,
+                                                            vector: &NdArray<'b, Self>) -> NdArray<'r, Self> {
+                    let matrix = matrix.map(|x| x.to_f32());
+                    let vector = vector.map(|x| x.to_f32());
+                    matrix.matmul(&vector).map(Self::from_f32)
+                }
+            }
+        )*
+    };
+}
+
+impl_matrix_ops_via_f32!(half::f16, half::bf16);
+
+impl MatrixOps for num::complex::Complex32 {}
+impl MatrixOps for num::complex::Complex64 {}
@@ -1,7 +1,10 @@
 #![allow(unused_mut)]
 #![allow(unused_variables)]
 
-use crate::dtype::{IntegerDataType, NumericDataType};
+use crate::dtype::{FromBool, IntegerDataType, RawDataType};
+use crate::ops::dot_product::DotProduct;
+use crate::ops::mul_add::MulAdd;
+use std::iter::Product;
 use std::hint::assert_unchecked;
 
 
@@ -52,7 +55,7 @@ pub(super) fn get_sum_of_products_function_generic_nops<T: SumOfProductsType>(st
 }
 
 
-pub(crate) trait SumOfProductsType: NumericDataType {
+pub(crate) trait SumOfProductsType: RawDataType + FromBool + Product + MulAdd + DotProduct {
     #[inline(always)]
     unsafe fn sum_of_products_generic_(ptrs: &[*mut Self], strides: &[usize], count: usize) {
         let nops = ptrs.len();
@@ -265,3 +268,8 @@ accelerated_sum_of_products!(ptrs, strides, count, dst,
 
     sum_of_scaled_array, { Self::simd_sum_of_scaled_array(*ptrs[0], ptrs[1], ptrs[2], count); },
 );
+
+impl SumOfProductsType for half::f16 {}
+impl SumOfProductsType for half::bf16 {}
+impl SumOfProductsType for num::complex::Complex32 {}
+impl SumOfProductsType for num::complex::Complex64 {}
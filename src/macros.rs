@@ -19,6 +19,10 @@ macro_rules! recursive_trait_base_cases {
 
         $macro_name!(f32);
         $macro_name!(f64);
+        $macro_name!(half::f16);
+        $macro_name!(half::bf16);
+        $macro_name!(num::complex::Complex32);
+        $macro_name!(num::complex::Complex64);
 
         $macro_name!(bool);
     };
@@ -48,6 +52,24 @@ macro_rules! test_for_float_dtypes {
     };
 }
 
+#[macro_export]
+macro_rules! test_for_half_dtypes {
+    ($name:ident, $body:tt) => {
+        implement_test_for_dtypes!($name, $body,
+            f16, bf16
+        );
+    };
+}
+
+#[macro_export]
+macro_rules! test_for_complex_dtypes {
+    ($name:ident, $body:tt) => {
+        implement_test_for_dtypes!($name, $body,
+            Complex32, Complex64
+        );
+    };
+}
+
 #[macro_export]
 macro_rules! test_for_common_signed_int_dtypes {
     ($name:ident, $body:tt) => {
@@ -172,6 +172,9 @@ pub(crate) mod flags;
 use flags::NdArrayFlags;
 
 pub mod reduce;
+pub mod sort;
+pub mod statistics;
+pub mod random;
 
 pub mod constructors;
 pub mod index_impl;
@@ -182,6 +185,7 @@ pub mod equals;
 pub mod broadcast;
 pub mod binary_ops;
 pub mod astype;
+pub mod complex;
 
 mod print;
 mod unary_ops;
@@ -203,3 +207,8 @@ pub struct NdArray<'a, T: RawDataType> {
 
     _marker: PhantomData<&'a T>,
 }
+
+// an array owns its buffer or borrows it for `'a` just like `Vec<T>` or `&'a [T]`,
+// so it may be sent and shared between threads whenever `T` can
+unsafe impl<T: RawDataType> Send for NdArray<'_, T> {}
+unsafe impl<T: RawDataType> Sync for NdArray<'_, T> {}
@@ -4,12 +4,33 @@ use crate::ops::binary_op_div::BinaryOpDiv;
 use crate::ops::binary_op_mul::BinaryOpMul;
 use crate::ops::binary_op_sub::BinaryOpSub;
 use crate::ops::binary_ops::{BinaryOpBitAnd, BinaryOpBitOr, BinaryOpRem, BinaryOpShl, BinaryOpShr};
-use crate::RawDataType;
+use crate::{RawDataType, Reshape};
 use crate::{NdArray, StridedMemory};
 use paste::paste;
 use std::ops::{AddAssign, BitAndAssign, BitOrAssign, DivAssign, MulAssign, RemAssign, ShlAssign, ShrAssign, SubAssign};
 
 
+impl<T: RawDataType> NdArray<'_, T> {
+    /// Runs `kernel` with a pointer to the elements of this array and a pointer to which
+    /// the results are written contiguously, then stores the results in this array.
+    ///
+    /// The kernels write their output contiguously, so if this array is not contiguous,
+    /// the results are written to a temporary buffer and then copied into place.
+    fn assign_with(&mut self, kernel: impl FnOnce(*const T, *mut T)) {
+        if self.is_contiguous() {
+            unsafe { kernel(self.ptr(), self.mut_ptr()); }
+            return;
+        }
+
+        let mut result = vec![T::default(); self.size()];
+        unsafe { kernel(self.ptr(), result.as_mut_ptr()); }
+
+        for (dst, value) in self.flatiter_ptr().zip(result) {
+            unsafe { *dst = value; }
+        }
+    }
+}
+
 macro_rules! define_binary_iop {
     ( $binary_op_trait:ident, $iop_trait:ident, $operator:tt, $method:ident ) => {
         paste! {
@@ -25,24 +46,19 @@ macro_rules! define_binary_iop {
                         panic!("tensor is readonly.");
                     }
                     
-                    if rhs.shape() == self.shape() {
-                        unsafe {
-                            <T as $binary_op_trait>::$method(self.ptr(), &self.stride(),
-                                                             rhs.ptr(), &rhs.stride(),
-                                                             self.mut_ptr(), self.shape());
-                        }
-                    }
-                    
-                    // right-hand term needs broadcasting
-                    else {
-                        let rhs = rhs.broadcast_to(&self.shape);
-                        
-                        unsafe {
-                        <T as $binary_op_trait>::$method(self.ptr(), &self.stride(),
+                    let rhs = if rhs.shape() == self.shape() {
+                        rhs.view()
+                    } else {
+                        // right-hand term needs broadcasting
+                        rhs.broadcast_to(&self.shape)
+                    };
+
+                    let lhs_stride = self.stride().to_vec();
+                    self.assign_with(|lhs, dst| unsafe {
+                        <T as $binary_op_trait>::$method(lhs, &lhs_stride,
                                                          rhs.ptr(), &rhs.stride(),
-                                                         self.mut_ptr(), self.shape());
-                        }
-                    }
+                                                         dst, &rhs.shape);
+                    });
                 }
             }
 
@@ -52,10 +68,11 @@ macro_rules! define_binary_iop {
                         panic!("tensor is readonly.");
                     }
 
-                    unsafe {
-                        <T as $binary_op_trait>::[<$method _scalar>](self.ptr(), self.shape(), self.stride(),
-                                                                     rhs, self.mut_ptr());
-                    }
+                    let shape = self.shape().to_vec();
+                    let stride = self.stride().to_vec();
+                    self.assign_with(|lhs, dst| unsafe {
+                        <T as $binary_op_trait>::[<$method _scalar>](lhs, &shape, &stride, rhs, dst);
+                    });
                 }
             }
         }
@@ -1,10 +1,10 @@
-use crate::dtype::NumericDataType;
+use crate::dtype::{NumericDataType, RawDataType};
 use crate::{Constructors, NdArray, StridedMemory};
 use num::NumCast;
 use crate::ndarray::flags::NdArrayFlags;
 
 impl<T: NumericDataType> NdArray<'_, T> {
-    pub fn astype<'b, F: NumericDataType>(&self) -> NdArray<'b, F>
+    pub fn astype<'b, F: RawDataType + NumCast>(&self) -> NdArray<'b, F>
     {
         let mut data = vec![F::default(); self.size()];
 
@@ -1,4 +1,5 @@
 use crate::dtype::RawDataType;
+use crate::ndarray::flags::NdArrayFlags;
 use crate::iterator::collapse_contiguous::collapse_to_uniform_stride;
 use crate::iterator::flat_index_generator::FlatIndexGenerator;
 use crate::{Constructors, NdArray, StridedMemory};
@@ -10,6 +11,30 @@ impl<'a, T: RawDataType> NdArray<'a, T> {
         unsafe { NdArray::from_contiguous_owned_buffer(self.shape.clone(), self.clone_data()) }
     }
 
+    /// Converts the ndarray into one with a `'static` lifetime.
+    ///
+    /// The data is only copied if the ndarray is a view, since owned data is not borrowed.
+    pub(crate) fn into_owned(mut self) -> NdArray<'static, T> {
+        if !self.flags.contains(NdArrayFlags::Owned) {
+            return self.clone();
+        }
+
+        // ensure the data is not dropped when self goes out of scope and is destroyed
+        self.flags -= NdArrayFlags::Owned;
+
+        NdArray {
+            ptr: self.ptr,
+            len: self.len,
+            capacity: self.capacity,
+
+            shape: std::mem::take(&mut self.shape),
+            stride: std::mem::take(&mut self.stride),
+            flags: self.flags | NdArrayFlags::Owned,
+
+            _marker: Default::default(),
+        }
+    }
+
     pub(super) fn clone_data(&self) -> Vec<T> {
         if self.is_contiguous() {
             return unsafe { self.clone_data_contiguous() };
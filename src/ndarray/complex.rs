@@ -0,0 +1,118 @@
+use crate::dtype::ComplexDataType;
+use crate::ndarray::flags::NdArrayFlags;
+use crate::slice::{calculate_strided_buffer_length, update_flags_with_contiguity};
+use crate::{NdArray, StridedMemory};
+use std::ptr::NonNull;
+
+impl<'a, T: ComplexDataType> NdArray<'a, T> {
+    /// Returns the elementwise complex conjugate of the ndarray.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([Complex64::new(1.0, 2.0), Complex64::new(-3.0, -4.0)]);
+    /// assert_eq!(array.conj(), NdArray::new([Complex64::new(1.0, -2.0), Complex64::new(-3.0, 4.0)]));
+    /// ```
+    pub fn conj(&self) -> NdArray<'static, T> {
+        self.map(|value| value.conj())
+    }
+
+    /// Returns a view of the real parts of the ndarray.
+    ///
+    /// The view shares memory with the original ndarray.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([Complex32::new(1.0, 2.0), Complex32::new(3.0, 4.0)]);
+    /// assert_eq!(array.real(), NdArray::new([1.0, 3.0]));
+    /// ```
+    pub fn real<'r>(&'r self) -> NdArray<'r, T::RealType> {
+        unsafe { self.component_view(0) }
+    }
+
+    /// Returns a view of the imaginary parts of the ndarray.
+    ///
+    /// The view shares memory with the original ndarray.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([Complex32::new(1.0, 2.0), Complex32::new(3.0, 4.0)]);
+    /// assert_eq!(array.imag(), NdArray::new([2.0, 4.0]));
+    /// ```
+    pub fn imag<'r>(&'r self) -> NdArray<'r, T::RealType> {
+        unsafe { self.component_view(1) }
+    }
+
+    /// Returns the elementwise magnitude of the ndarray.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([Complex64::new(3.0, 4.0), Complex64::new(0.0, -2.0)]);
+    /// assert_eq!(array.abs(), NdArray::new([5.0, 2.0]));
+    /// ```
+    pub fn abs(&self) -> NdArray<'static, T::RealType> {
+        self.map(|value| value.abs())
+    }
+
+    /// Returns the elementwise argument (phase angle) of the ndarray in radians, in `(-π, π]`.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([Complex64::new(1.0, 0.0), Complex64::new(0.0, 1.0)]);
+    /// assert_eq!(array.angle(), NdArray::new([0.0, std::f64::consts::FRAC_PI_2]));
+    /// ```
+    pub fn angle(&self) -> NdArray<'static, T::RealType> {
+        self.map(|value| value.angle())
+    }
+
+    /// Calculates the dot product of two 1D arrays, conjugating the first array.
+    ///
+    /// # Panics
+    /// - Panics if either array is not 1D
+    /// - Panics if the lengths of the two arrays are not equal
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let a = NdArray::new([Complex64::new(1.0, 1.0), Complex64::new(0.0, 2.0)]);
+    /// let b = NdArray::new([Complex64::new(2.0, 0.0), Complex64::new(1.0, 1.0)]);
+    ///
+    /// // (1 - i)(2) + (-2i)(1 + i) = 4 - 4i
+    /// assert_eq!(a.vdot(&b).value(), Complex64::new(4.0, -4.0));
+    /// ```
+    pub fn vdot<'b, 'r>(&self, other: impl AsRef<NdArray<'b, T>>) -> NdArray<'r, T> {
+        self.conj().dot(other)
+    }
+
+    /// Returns a view of either the real (`component = 0`) or imaginary (`component = 1`) parts.
+    ///
+    /// # Safety
+    /// - `T` must be laid out in memory as a `[T::RealType; 2]` pair of real and imaginary parts.
+    unsafe fn component_view<'r>(&'r self, component: usize) -> NdArray<'r, T::RealType> {
+        let shape = self.shape().to_vec();
+        let stride: Vec<usize> = self.stride().iter().map(|stride| 2 * stride).collect();
+
+        let mut flags = update_flags_with_contiguity(self.flags, &shape, &stride);
+        flags -= NdArrayFlags::UserCreated;
+        flags -= NdArrayFlags::Owned;
+
+        let ptr = self.ptr.as_ptr() as *mut T::RealType;
+        let len = calculate_strided_buffer_length(&shape, &stride);
+
+        NdArray {
+            ptr: NonNull::new_unchecked(ptr.add(component)),
+            len,
+            capacity: 0,
+
+            shape,
+            stride,
+            flags,
+
+            _marker: Default::default(),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::ndarray::flags::NdArrayFlags;
+use crate::ndarray::slice::{calculate_strided_buffer_length, update_flags_with_contiguity};
 use crate::ndarray::NdArray;
 use crate::common::constructors::Constructors;
 use crate::RawDataType;
@@ -84,3 +85,80 @@ impl<T: RawDataType> Drop for NdArray<'_, T> {
         self.capacity = 0;
     }
 }
+
+impl<'a, T: RawDataType> NdArray<'a, T> {
+    /// Creates a read-only view of `data`, which is laid out contiguously with the given `shape`.
+    ///
+    /// # Panics
+    /// - If the number of elements of `data` and `shape` differ
+    pub(crate) fn from_contiguous_borrowed_buffer(shape: Vec<usize>, data: &'a [T]) -> Self {
+        assert_eq!(data.len(), shape.iter().product::<usize>(), "data does not match the shape");
+
+        let flags = NdArrayFlags::Contiguous | NdArrayFlags::UniformStride;
+        let stride = stride_from_shape(&shape);
+
+        Self {
+            ptr: NonNull::from(data).cast(),
+            len: data.len(),
+            capacity: 0,
+
+            shape,
+            stride,
+            flags,
+
+            _marker: Default::default(),
+        }
+    }
+
+    /// Creates a writeable view of `data`, which is laid out contiguously with the given `shape`.
+    ///
+    /// # Panics
+    /// - If the number of elements of `data` and `shape` differ
+    pub(crate) fn from_contiguous_borrowed_buffer_mut(shape: Vec<usize>, data: &'a mut [T]) -> Self {
+        assert_eq!(data.len(), shape.iter().product::<usize>(), "data does not match the shape");
+
+        let flags = NdArrayFlags::Contiguous | NdArrayFlags::UniformStride | NdArrayFlags::Writeable;
+        let stride = stride_from_shape(&shape);
+
+        Self {
+            ptr: NonNull::from(&mut *data).cast(),
+            len: data.len(),
+            capacity: 0,
+
+            shape,
+            stride,
+            flags,
+
+            _marker: Default::default(),
+        }
+    }
+
+    /// Creates a view of the elements at `ptr` with the given `shape` and `stride`,
+    /// which are in units of elements.
+    ///
+    /// # Safety
+    /// - Every element addressed by `ptr`, `shape` and `stride` must be valid for reads for `'a`,
+    ///   and also for writes if the view is `writeable`
+    /// - `ptr` must be aligned, and may only be null if the view has no elements
+    pub(crate) unsafe fn from_raw_view(ptr: *mut T, shape: Vec<usize>, stride: Vec<usize>, writeable: bool) -> Self {
+        let mut flags = update_flags_with_contiguity(NdArrayFlags::empty(), &shape, &stride);
+        if writeable {
+            flags |= NdArrayFlags::Writeable;
+        }
+
+        // views with gaps between their elements span more memory than they have elements
+        let len = if shape.contains(&0) { 0 } else { calculate_strided_buffer_length(&shape, &stride) };
+
+        Self {
+            ptr: NonNull::new(ptr).unwrap_or(NonNull::dangling()),
+            len,
+            capacity: 0,
+
+            shape,
+            stride,
+            flags,
+
+            _marker: Default::default(),
+        }
+    }
+}
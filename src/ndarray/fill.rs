@@ -1,4 +1,5 @@
-use crate::dtype::RawDataType;
+use crate::dtype::{FromBool, RawDataType};
+use crate::ndarray::NdArrayFlags;
 use crate::{NdArray, StridedMemory};
 use crate::ops::fill::Fill;
 
@@ -16,11 +17,15 @@ impl<T: RawDataType> NdArray<'_, T> {
     /// assert_eq!(arr, NdArray::new([10, 10, 10]));
     /// ```
     pub fn fill(&mut self, value: T) {
+        if !self.flags.contains(NdArrayFlags::Writeable) {
+            panic!("tensor is readonly.");
+        }
+
         unsafe { <T as Fill>::fill(self.mut_ptr(), self.shape(), self.stride(), self.len, value) }
     }
 }
 
-impl<T: RawDataType + From<bool>> NdArray<'_, T> {
+impl<T: RawDataType + FromBool> NdArray<'_, T> {
     /// Fills the entire array with a zero (or `false` if dtype is boolean).
     ///
     /// # Example
@@ -33,6 +38,6 @@ impl<T: RawDataType + From<bool>> NdArray<'_, T> {
     /// assert_eq!(arr, NdArray::new([0, 0, 0]));
     /// ```
     pub fn zero(&mut self) {
-        self.fill(false.into());
+        self.fill(T::from_bool(false));
     }
 }
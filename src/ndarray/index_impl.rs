@@ -1,4 +1,5 @@
 use crate::dtype::RawDataType;
+use crate::ndarray::NdArrayFlags;
 use crate::{NdArray, StridedMemory};
 use std::ops::{Index, IndexMut};
 
@@ -21,6 +22,10 @@ impl<T: RawDataType, const D: usize> IndexMut<[usize; D]> for NdArray<'_, T> {
     fn index_mut(&mut self, index: [usize; D]) -> &mut Self::Output {
         assert!(D <= self.ndims(), "[] index must be equal number of array dimensions!");
 
+        if !self.flags.contains(NdArrayFlags::Writeable) {
+            panic!("tensor is readonly.");
+        }
+
         let i: usize = index.iter().zip(self.stride.iter())
                             .map(|(idx, stride)| idx * stride)
                             .sum();
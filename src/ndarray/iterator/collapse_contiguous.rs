@@ -81,8 +81,9 @@ pub(crate) fn has_uniform_stride(shape: &[usize], stride: &[usize]) -> Option<us
         return Some(0);
     }
 
+    // a stride of 0 can only be skipped if the axis has length 1, otherwise the axis is broadcast
     for i in 1..ndims {
-        if stride[i - 1] != 0 && stride[i - 1] != shape[i] * stride[i] {
+        if (stride[i - 1] != 0 || shape[i - 1] != 1) && stride[i - 1] != shape[i] * stride[i] {
             return None;
         }
     }
@@ -0,0 +1,212 @@
+use crate::dtype::{FloatDataType, RawDataType};
+use crate::generator::with_default_generator;
+use crate::ndarray::NdArrayFlags;
+use crate::{AxisType, Constructors, Generator, NdArray, StridedMemory};
+use rand::distributions::{Distribution, Uniform, WeightedIndex};
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+impl<T: FloatDataType> NdArray<'_, T> {
+    /// Draws `num_samples` indices from the categorical distribution whose (unnormalized)
+    /// probabilities are the elements of this array.
+    ///
+    /// If the array is a matrix, each row is a separate distribution and the samples
+    /// of each row are returned in the corresponding row of the result.
+    /// Without `replacement`, the indices of each row are distinct and returned in the order drawn.
+    ///
+    /// # Panics
+    /// - If the array is not a vector or matrix
+    /// - If any weight is negative or not finite, or all weights of a distribution are 0
+    /// - If sampling without replacement and `num_samples` exceeds the number of nonzero weights
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let probabilities = NdArray::new([0.0, 0.5, 0.5]);
+    ///
+    /// let samples = probabilities.multinomial(4, true);
+    /// assert!(samples.flatiter().all(|index| index == 1 || index == 2));
+    ///
+    /// // a single draw from each row is a categorical sample
+    /// let probabilities = NdArray::new([[1.0, 0.0], [0.0, 1.0]]);
+    /// assert_eq!(probabilities.multinomial(1, false), NdArray::new([[0], [1]]));
+    /// ```
+    pub fn multinomial(&self, num_samples: usize, replacement: bool) -> NdArray<'static, usize> {
+        with_default_generator(|generator| self.multinomial_with(generator, num_samples, replacement))
+    }
+
+    /// Draws `num_samples` indices from the categorical distribution whose (unnormalized)
+    /// probabilities are the elements of this array using the random numbers of `generator`.
+    ///
+    /// See `multinomial()`.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let mut generator = Generator::new(0);
+    /// let probabilities = NdArray::new([0.1, 0.2, 0.7]);
+    ///
+    /// let samples = probabilities.multinomial_with(&mut generator, 2, false);
+    /// assert_ne!(samples[0], samples[1]);
+    /// ```
+    pub fn multinomial_with(&self, generator: &mut Generator, num_samples: usize, replacement: bool) -> NdArray<'static, usize> {
+        assert!(self.ndims() == 1 || self.ndims() == 2, "multinomial: the probabilities must be a vector or matrix");
+
+        let num_categories = *self.shape().last().unwrap();
+        let weights: Vec<f64> = self.flatiter().map(|weight| weight.to_f64().unwrap()).collect();
+
+        let mut samples = Vec::with_capacity(self.size() / num_categories * num_samples);
+        for weights in weights.chunks(num_categories) {
+            if replacement {
+                let distribution = WeightedIndex::new(weights)
+                    .expect("multinomial: weights must be non-negative and finite with a positive sum");
+                samples.extend(distribution.sample_iter(&mut *generator).take(num_samples));
+            } else {
+                samples.extend(sample_without_replacement(generator, weights, num_samples));
+            }
+        }
+
+        let mut shape = self.shape().to_vec();
+        *shape.last_mut().unwrap() = num_samples;
+
+        unsafe { NdArray::from_contiguous_owned_buffer(shape, samples) }
+    }
+}
+
+impl<T: RawDataType> NdArray<'_, T> {
+    /// Draws `num_samples` elements uniformly at random from this vector.
+    ///
+    /// Without `replacement`, each element is drawn at most once.
+    ///
+    /// # Panics
+    /// - If the array is not a vector
+    /// - If sampling without replacement and `num_samples` exceeds the length of the vector
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let array = NdArray::new([10, 20, 30, 40]);
+    ///
+    /// let mut samples = array.choice(4, false).flatiter().collect::<Vec<_>>();
+    /// samples.sort();
+    /// assert_eq!(samples, vec![10, 20, 30, 40]);
+    /// ```
+    pub fn choice(&self, num_samples: usize, replacement: bool) -> NdArray<'static, T> {
+        with_default_generator(|generator| self.choice_with(generator, num_samples, replacement))
+    }
+
+    /// Draws `num_samples` elements uniformly at random from this vector
+    /// using the random numbers of `generator`.
+    ///
+    /// See `choice()`.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let mut generator = Generator::new(0);
+    ///
+    /// let samples = NdArray::new([1.0, 2.0]).choice_with(&mut generator, 5, true);
+    /// assert_eq!(samples.shape(), &[5]);
+    /// ```
+    pub fn choice_with(&self, generator: &mut Generator, num_samples: usize, replacement: bool) -> NdArray<'static, T> {
+        assert_eq!(self.ndims(), 1, "choice: the array must be a vector");
+
+        let elements: Vec<T> = self.flatiter().collect();
+
+        let samples = if replacement {
+            assert!(!elements.is_empty() || num_samples == 0, "choice: cannot sample from an empty array");
+            // indices are drawn as u64 so that the samples do not depend on the width of usize
+            Uniform::new(0, elements.len().max(1) as u64).sample_iter(generator)
+                .take(num_samples)
+                .map(|i| elements[i as usize])
+                .collect()
+        } else {
+            assert!(num_samples <= elements.len(), "choice: cannot draw more samples than elements without replacement");
+            index::sample(generator, elements.len(), num_samples).into_iter()
+                .map(|i| elements[i])
+                .collect()
+        };
+
+        unsafe { NdArray::from_contiguous_owned_buffer(vec![num_samples], samples) }
+    }
+
+    /// Shuffles the subarrays of this array along `axis` in place.
+    ///
+    /// Every lane along `axis` is permuted the same way, so e.g. shuffling along axis 0
+    /// shuffles the rows of a matrix while keeping each row intact.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let mut array = NdArray::new([[0, 0], [1, 1], [2, 2]]);
+    /// array.shuffle_along(0);
+    ///
+    /// // the rows are reordered but kept intact
+    /// assert_eq!(array.sort(0), NdArray::new([[0, 0], [1, 1], [2, 2]]));
+    /// assert_eq!(array.slice_along(Axis(1), 0), array.slice_along(Axis(1), 1));
+    /// ```
+    pub fn shuffle_along(&mut self, axis: impl AxisType) {
+        with_default_generator(|generator| self.shuffle_along_with(generator, axis))
+    }
+
+    /// Shuffles the subarrays of this array along `axis` in place
+    /// using the random numbers of `generator`.
+    ///
+    /// See `shuffle_along()`.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let mut generator = Generator::new(0);
+    ///
+    /// let mut array = NdArray::new([1, 2, 3, 4]);
+    /// array.shuffle_along_with(&mut generator, -1);
+    /// assert_eq!(array.sort(0), NdArray::new([1, 2, 3, 4]));
+    /// ```
+    pub fn shuffle_along_with(&mut self, generator: &mut Generator, axis: impl AxisType) {
+        if !self.flags.contains(NdArrayFlags::Writeable) {
+            panic!("tensor is readonly.");
+        }
+
+        let axis = axis.as_absolute(self.ndims());
+
+        let length = self.shape()[axis];
+        let inner: usize = self.shape()[axis + 1..].iter().product();
+
+        let mut permutation: Vec<usize> = (0..length).collect();
+        permutation.shuffle(generator);
+
+        let elements: Vec<T> = self.flatiter().collect();
+
+        // the element at (outer, i, inner) in logical order is taken from (outer, permutation[i], inner)
+        let shuffled = (0..elements.len()).map(|index| {
+            let (outer, rest) = (index / (length * inner), index % (length * inner));
+            let (i, j) = (rest / inner, rest % inner);
+            elements[(outer * length + permutation[i]) * inner + j]
+        });
+
+        for (dst, value) in self.flatiter_ptr().zip(shuffled) {
+            unsafe { *dst = value; }
+        }
+    }
+}
+
+/// Draws `num_samples` distinct indices with probabilities proportional to `weights`,
+/// in the order they would be drawn one at a time.
+///
+/// Each index is given the key `ln(u) / weight` for `u ~ U(0, 1)`, and the indices with
+/// the largest keys are distributed like successive draws without replacement (Efraimidis-Spirakis).
+fn sample_without_replacement(generator: &mut Generator, weights: &[f64], num_samples: usize) -> Vec<usize> {
+    assert!(weights.iter().all(|weight| weight.is_finite() && *weight >= 0.0),
+            "multinomial: weights must be non-negative and finite");
+    assert!(num_samples <= weights.iter().filter(|weight| **weight > 0.0).count(),
+            "multinomial: cannot draw more samples than nonzero weights without replacement");
+
+    let mut keys: Vec<(f64, usize)> = weights.iter().enumerate()
+        .filter(|(_, weight)| **weight > 0.0)
+        .map(|(i, weight)| (generator.gen::<f64>().ln() / weight, i))
+        .collect();
+
+    keys.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+    keys.into_iter().take(num_samples).map(|(_, i)| i).collect()
+}
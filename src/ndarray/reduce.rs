@@ -1,6 +1,7 @@
 use crate::dtype::{NumericDataType, RawDataType};
 use crate::flat_index_generator::FlatIndexGenerator;
 use crate::iterator::collapse_contiguous::collapse_to_uniform_stride;
+use crate::ndarray::constructors::stride_from_shape;
 use crate::ops::reduce_max::ReduceMax;
 use crate::ops::reduce_min::ReduceMin;
 use crate::ops::reduce_min_magnitude::ReduceMinMagnitude;
@@ -8,10 +9,12 @@ use crate::ops::reduce_product::ReduceProduct;
 use crate::ops::reduce_sum::ReduceSum;
 use crate::partial_ord::*;
 use crate::util::to_vec::ToVec;
-use crate::{AxisType, Constructors, FloatDataType, NdArray, StridedMemory};
+use crate::{AxisType, Constructors, DType, FloatDataType, NdArray, Reshape, StridedMemory};
 use num::NumCast;
 use std::collections::VecDeque;
 use crate::ops::reduce_max_magnitude::ReduceMaxMagnitude;
+use crate::ops::reduce_argmax::ReduceArgMax;
+use crate::ops::reduce_argmin::ReduceArgMin;
 
 /// Returns a tuple `(output_shape, map_stride)`
 ///
@@ -51,6 +54,47 @@ fn reduced_shape_and_stride(axes: &[isize], shape: &[usize]) -> (Vec<usize>, Vec
     (Vec::from(new_shape), Vec::from(new_stride))
 }
 
+/// Whether reductions over `dtype` are accumulated in `f32`,
+/// since half-precision accumulators lose precision after a few thousand elements.
+pub(crate) fn accumulates_in_f32(dtype: DType) -> bool {
+    matches!(dtype, DType::F16 | DType::BF16)
+}
+
+/// Returns the shape of the output of a reduction along `axes` when the reduced axes are retained
+/// with length 1 instead of being removed.
+pub(crate) fn keepdims_shape(axes: &[isize], shape: &[usize]) -> Vec<usize> {
+    let mut keepdims_shape = shape.to_vec();
+    for axis in axes.iter() {
+        keepdims_shape[axis.as_absolute(shape.len())] = 1;
+    }
+    keepdims_shape
+}
+
+/// Returns a tuple `(axis_length, axis_stride, outer_shape, outer_stride)`
+///
+/// - `axis_length` and `axis_stride` describe iteration along `axis`
+/// - `outer_shape` and `outer_stride` describe the remaining axes, i.e. the starting points of
+///   every 1D lane along `axis`
+pub(super) fn split_axis(axis: usize, shape: &[usize], stride: &[usize]) -> (usize, usize, Vec<usize>, Vec<usize>) {
+    let mut outer_shape = shape.to_vec();
+    let mut outer_stride = stride.to_vec();
+
+    let axis_length = outer_shape.remove(axis);
+    let axis_stride = outer_stride.remove(axis);
+
+    (axis_length, axis_stride, outer_shape, outer_stride)
+}
+
+/// Returns a tuple `(axis_stride, outer_stride)` for a contiguous ndarray with the given `shape`
+///
+/// - `axis_stride` is the stride along `axis`
+/// - `outer_stride` contains the strides of the remaining axes
+pub(super) fn split_contiguous_axis(axis: usize, shape: &[usize]) -> (usize, Vec<usize>) {
+    let mut stride = stride_from_shape(shape);
+    let axis_stride = stride.remove(axis);
+    (axis_stride, stride)
+}
+
 impl<T: RawDataType> NdArray<'_, T> {
     /// Reduces the elements of a contiguous ndarray into a scalar using the specified function.
     ///
@@ -75,14 +119,22 @@ impl<T: RawDataType> NdArray<'_, T> {
         NdArray::scalar(output)
     }
 
-    fn reduce_along(&self, func: impl Fn(T, T) -> T, axes: impl ToVec<isize>, default: T) -> NdArray<'static, T> {
+    pub(super) fn reduce_along(&self, func: impl Fn(T, T) -> T, axes: impl ToVec<isize>, default: T) -> NdArray<'static, T> {
+        let (out_shape, output) = self.reduce_along_into(func, axes, default);
+        unsafe { NdArray::from_contiguous_owned_buffer(out_shape, output) }
+    }
+
+    /// Reduces the elements along `axes` into accumulators of type `A` using the specified function.
+    ///
+    /// Returns the shape of the reduction's output along with the (contiguous) accumulators.
+    fn reduce_along_into<A: Copy>(&self, func: impl Fn(T, A) -> A, axes: impl ToVec<isize>, default: A) -> (Vec<usize>, Vec<A>) {
         let (out_shape, map_stride) = reduced_shape_and_stride(&axes.to_vec(), &self.shape);
         let (map_shape, map_stride) = collapse_to_uniform_stride(&self.shape, &map_stride);
 
         let mut output = vec![default; out_shape.iter().product()];
 
         let mut dst_indices = FlatIndexGenerator::from(&map_shape, &map_stride);
-        let dst: *mut T = output.as_mut_ptr();
+        let dst: *mut A = output.as_mut_ptr();
 
         for el in self.flatiter() {
             unsafe {
@@ -92,7 +144,24 @@ impl<T: RawDataType> NdArray<'_, T> {
             }
         }
 
-        unsafe { NdArray::from_contiguous_owned_buffer(out_shape, output) }
+        (out_shape, output)
+    }
+
+    /// Calls `func(index, value)` for every element of the ndarray where `index` is the flat index
+    /// of the element's destination in the (contiguous) output of a reduction along `axes`.
+    ///
+    /// Returns the shape of the reduction's output.
+    pub(super) fn for_each_reduced(&self, axes: &[isize], mut func: impl FnMut(usize, T)) -> Vec<usize> {
+        let (out_shape, map_stride) = reduced_shape_and_stride(axes, &self.shape);
+        let (map_shape, map_stride) = collapse_to_uniform_stride(&self.shape, &map_stride);
+
+        let dst_indices = FlatIndexGenerator::from(&map_shape, &map_stride);
+
+        for (el, dst_i) in self.flatiter().zip(dst_indices) {
+            func(dst_i, el);
+        }
+
+        out_shape
     }
 
     fn reduce(&self, func: impl Fn(T, T) -> T, default: T) -> NdArray<'static, T> {
@@ -111,6 +180,15 @@ impl<T: RawDataType> NdArray<'_, T> {
 }
 
 impl<T: NumericDataType> NdArray<'_, T> {
+    /// Reduces the elements along `axes` like `reduce_along()` but accumulates them in `f32`,
+    /// then converts each accumulator to `T` after applying `finish` to it.
+    pub(crate) fn reduce_along_in_f32(&self, func: impl Fn(f32, f32) -> f32, axes: impl ToVec<isize>,
+                                      default: f32, finish: impl Fn(f32) -> f32) -> NdArray<'static, T> {
+        let (out_shape, output) = self.reduce_along_into(|val, acc| func(val.to_f32().unwrap(), acc), axes, default);
+        let output = output.into_iter().map(|acc| NumCast::from(finish(acc)).unwrap()).collect();
+        unsafe { NdArray::from_contiguous_owned_buffer(out_shape, output) }
+    }
+
     /// Computes the sum of all elements in the array.
     ///
     /// # Example
@@ -127,9 +205,29 @@ impl<T: NumericDataType> NdArray<'_, T> {
     }
 
     pub fn sum_along(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        if accumulates_in_f32(T::DTYPE) {
+            return self.reduce_along_in_f32(|val, acc| acc + val, axes, 0.0, |sum| sum);
+        }
         self.reduce_along(|val, acc| acc + val, axes, T::zero())
     }
 
+    /// Computes the sum along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// This is useful when the output is broadcast against the original array.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1, 2, 3], [4, 5, 6]]);
+    /// let sum = array.sum_keepdims(1);
+    /// assert_eq!(sum, NdArray::new([[6], [15]]));
+    /// ```
+    pub fn sum_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.sum_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
     /// Computes the product of all elements in the array.
     ///
     /// # Example
@@ -146,9 +244,18 @@ impl<T: NumericDataType> NdArray<'_, T> {
     }
 
     pub fn product_along(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        if accumulates_in_f32(T::DTYPE) {
+            return self.reduce_along_in_f32(|val, acc| acc * val, axes, 1.0, |product| product);
+        }
         self.reduce_along(|val, acc| acc * val, axes, T::one())
     }
 
+    /// Computes the product along the specified axes, retaining the reduced axes with length 1.
+    pub fn product_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.product_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
     /// Computes the minimum of all elements in the array.
     ///
     /// # Example
@@ -168,6 +275,12 @@ impl<T: NumericDataType> NdArray<'_, T> {
         self.reduce_along(partial_min, axes, T::max_value())
     }
 
+    /// Computes the minimum along the specified axes, retaining the reduced axes with length 1.
+    pub fn min_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.min_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
     /// Computes the maximum of all elements in the array.
     ///
     /// # Example
@@ -187,6 +300,140 @@ impl<T: NumericDataType> NdArray<'_, T> {
         self.reduce_along(partial_max, axes, T::min_value())
     }
 
+    /// Computes the maximum along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1, 7, 3], [4, 5, 6]]);
+    /// let max = array.max_keepdims(-1);
+    /// assert_eq!(&array - max, NdArray::new([[-6, 0, -4], [-2, -1, 0]]));
+    /// ```
+    pub fn max_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.max_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Returns the flat index of the minimum of all elements in the array.
+    ///
+    /// If the minimum occurs more than once, the index of the first occurrence is returned.
+    /// NaNs are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[-1, 3], [-7, 8]]);
+    /// let argmin = array.argmin();
+    /// assert_eq!(argmin.value(), 2);
+    /// ```
+    pub fn argmin(&self) -> NdArray<'static, usize> {
+        let output = unsafe { <T as ReduceArgMin>::argmin(self.ptr(), self.shape(), self.stride()) };
+        NdArray::scalar(output)
+    }
+
+    /// Returns the indices of the minimum values along the specified axis.
+    ///
+    /// The output has the shape of the array with `axis` removed.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[-1, 3, 2], [-7, 8, 9]]);
+    /// assert_eq!(array.argmin_along(0), NdArray::new([1, 0, 0]));
+    /// assert_eq!(array.argmin_along(-1), NdArray::new([0, 0]));
+    /// ```
+    pub fn argmin_along(&self, axis: impl AxisType) -> NdArray<'static, usize> {
+        self.arg_reduce_along(axis, |ptr, count, stride| unsafe {
+            if stride == 1 {
+                <T as ReduceArgMin>::argmin_contiguous(ptr, count)
+            } else {
+                <T as ReduceArgMin>::argmin_uniform_stride(ptr, count, stride)
+            }
+        })
+    }
+
+    /// Returns the indices of the minimum values along the specified axis,
+    /// retaining the reduced axis with length 1.
+    pub fn argmin_keepdims(&self, axis: impl AxisType) -> NdArray<'static, usize> {
+        let axis = axis.isize();
+        self.argmin_along(axis).reshape(keepdims_shape(&[axis], self.shape()))
+    }
+
+    /// Returns the flat index of the maximum of all elements in the array.
+    ///
+    /// If the maximum occurs more than once, the index of the first occurrence is returned.
+    /// NaNs are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[-1, 3], [-7, 8]]);
+    /// let argmax = array.argmax();
+    /// assert_eq!(argmax.value(), 3);
+    /// ```
+    pub fn argmax(&self) -> NdArray<'static, usize> {
+        let output = unsafe { <T as ReduceArgMax>::argmax(self.ptr(), self.shape(), self.stride()) };
+        NdArray::scalar(output)
+    }
+
+    /// Returns the indices of the maximum values along the specified axis.
+    ///
+    /// The output has the shape of the array with `axis` removed.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[-1, 3, 2], [-7, 8, 9]]);
+    /// assert_eq!(array.argmax_along(0), NdArray::new([0, 1, 1]));
+    /// assert_eq!(array.argmax_along(-1), NdArray::new([1, 2]));
+    /// ```
+    pub fn argmax_along(&self, axis: impl AxisType) -> NdArray<'static, usize> {
+        self.arg_reduce_along(axis, |ptr, count, stride| unsafe {
+            if stride == 1 {
+                <T as ReduceArgMax>::argmax_contiguous(ptr, count)
+            } else {
+                <T as ReduceArgMax>::argmax_uniform_stride(ptr, count, stride)
+            }
+        })
+    }
+
+    /// Returns the indices of the maximum values along the specified axis,
+    /// retaining the reduced axis with length 1.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[-1, 3, 2], [-7, 8, 9]]);
+    /// assert_eq!(array.argmax_keepdims(1), NdArray::new([[1], [2]]));
+    /// ```
+    pub fn argmax_keepdims(&self, axis: impl AxisType) -> NdArray<'static, usize> {
+        let axis = axis.isize();
+        self.argmax_along(axis).reshape(keepdims_shape(&[axis], self.shape()))
+    }
+
+    /// Applies `func` to every 1D lane along `axis` and collects the resulting indices.
+    ///
+    /// `func` takes a pointer to the start of the lane, the number of elements in the lane,
+    /// and the stride between consecutive elements.
+    fn arg_reduce_along(&self, axis: impl AxisType,
+                        func: impl Fn(*const T, usize, usize) -> usize) -> NdArray<'static, usize> {
+        let axis = axis.as_absolute(self.ndims());
+        let (axis_length, axis_stride, outer_shape, outer_stride) = split_axis(axis, self.shape(), self.stride());
+
+        let ptr = unsafe { self.ptr() };
+        let output = FlatIndexGenerator::from(&outer_shape, &outer_stride)
+            .map(|offset| func(unsafe { ptr.add(offset) }, axis_length, axis_stride))
+            .collect();
+
+        unsafe { NdArray::from_contiguous_owned_buffer(outer_shape, output) }
+    }
+
     /// Computes the minimum absolute value of all elements in the array.
     ///
     /// # Example
@@ -206,6 +453,13 @@ impl<T: NumericDataType> NdArray<'_, T> {
         self.reduce_along(partial_min_magnitude, axes, T::max_value())
     }
 
+    /// Computes the minimum absolute value along the specified axes,
+    /// retaining the reduced axes with length 1.
+    pub fn min_magnitude_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.min_magnitude_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
     /// Computes the maximum absolute value of all elements in the array.
     ///
     /// # Example
@@ -225,6 +479,13 @@ impl<T: NumericDataType> NdArray<'_, T> {
         self.reduce_along(partial_max_magnitude, axes, T::zero())
     }
 
+    /// Computes the maximum absolute value along the specified axes,
+    /// retaining the reduced axes with length 1.
+    pub fn max_magnitude_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.max_magnitude_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
     /// Computes the mean of all elements in the array.
     ///
     /// # Example
@@ -239,6 +500,10 @@ impl<T: NumericDataType> NdArray<'_, T> {
     where
         T: FloatDataType
     {
+        if accumulates_in_f32(T::DTYPE) {
+            return self.mean_along((0..self.ndims() as isize).collect::<Vec<_>>());
+        }
+
         let n: T = NumCast::from(self.size()).unwrap();
         self.sum() / n
     }
@@ -255,15 +520,106 @@ impl<T: NumericDataType> NdArray<'_, T> {
             n *= self.shape()[axis as usize];
         }
 
+        if accumulates_in_f32(T::DTYPE) {
+            let n = n as f32;
+            return self.reduce_along_in_f32(|val, acc| acc + val, axes, 0.0, |sum| sum / n);
+        }
+
         let n: T = NumCast::from(n).unwrap();
         self.sum_along(axes) / n
     }
+
+    /// Computes the mean along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1.0, 3.0], [2.0, 6.0]]);
+    /// let centered = &array - array.mean_keepdims(0);
+    /// assert_eq!(centered, NdArray::new([[-0.5, -1.5], [0.5, 1.5]]));
+    /// ```
+    pub fn mean_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, T>
+    where
+        T: FloatDataType
+    {
+        let axes = axes.to_vec();
+        self.mean_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
 }
 
 
+impl NdArray<'_, bool> {
+    /// Returns whether all elements of the array are `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[true, false], [true, true]]);
+    /// assert!(!array.all().value());
+    /// ```
+    pub fn all(&self) -> NdArray<'static, bool> {
+        self.reduce(|val, acc| val && acc, true)
+    }
+
+    /// Returns whether all elements along the specified axes are `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[true, false], [true, true]]);
+    /// assert_eq!(array.all_along(0), NdArray::new([true, false]));
+    /// ```
+    pub fn all_along(&self, axes: impl ToVec<isize>) -> NdArray<'static, bool> {
+        self.reduce_along(|val, acc| val && acc, axes, true)
+    }
+
+    /// Returns whether all elements along the specified axes are `true`,
+    /// retaining the reduced axes with length 1.
+    pub fn all_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, bool> {
+        let axes = axes.to_vec();
+        self.all_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Returns whether any element of the array is `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[true, false], [false, false]]);
+    /// assert!(array.any().value());
+    /// ```
+    pub fn any(&self) -> NdArray<'static, bool> {
+        self.reduce(|val, acc| val || acc, false)
+    }
+
+    /// Returns whether any element along the specified axes is `true`.
+    ///
+    /// # Example
+    /// ```
+    /// use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[true, false], [false, false]]);
+    /// assert_eq!(array.any_along(1), NdArray::new([true, false]));
+    /// ```
+    pub fn any_along(&self, axes: impl ToVec<isize>) -> NdArray<'static, bool> {
+        self.reduce_along(|val, acc| val || acc, axes, false)
+    }
+
+    /// Returns whether any element along the specified axes is `true`,
+    /// retaining the reduced axes with length 1.
+    pub fn any_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, bool> {
+        let axes = axes.to_vec();
+        self.any_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::reduced_shape_and_stride;
+    use super::{keepdims_shape, reduced_shape_and_stride};
 
     #[test]
     fn test_reduce_shape_and_stride() {
@@ -313,4 +669,14 @@ mod tests {
         assert_eq!(new_shape, correct_shape);
         assert_eq!(new_stride, correct_stride);
     }
+
+    #[test]
+    fn test_keepdims_shape() {
+        let shape = vec![4, 2, 3];
+
+        assert_eq!(keepdims_shape(&[0], &shape), vec![1, 2, 3]);
+        assert_eq!(keepdims_shape(&[-1], &shape), vec![4, 2, 1]);
+        assert_eq!(keepdims_shape(&[0, 2], &shape), vec![1, 2, 1]);
+        assert_eq!(keepdims_shape(&[], &shape), shape);
+    }
 }
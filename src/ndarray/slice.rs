@@ -14,7 +14,8 @@ pub(super) fn update_flags_with_contiguity(mut flags: NdArrayFlags, shape: &[usi
         Some(stride) => {
             flags |= NdArrayFlags::UniformStride;
 
-            if stride <= 1 {
+            // a stride of 0 is only contiguous if it's never stepped along (i.e. there is 1 element)
+            if stride == 1 || (stride == 0 && shape.iter().product::<usize>() <= 1) {
                 flags |= NdArrayFlags::Contiguous;
             } else {
                 flags -= NdArrayFlags::Contiguous;
@@ -25,7 +26,7 @@ pub(super) fn update_flags_with_contiguity(mut flags: NdArrayFlags, shape: &[usi
     flags
 }
 
-fn calculate_strided_buffer_length(shape: &[usize], stride: &[usize]) -> usize {
+pub(crate) fn calculate_strided_buffer_length(shape: &[usize], stride: &[usize]) -> usize {
     // let mut len = 1;
     // for i in 0..ndims {
     //     len += stride[i] * (shape[i] - 1);
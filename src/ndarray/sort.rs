@@ -0,0 +1,246 @@
+use crate::dtype::NumericDataType;
+use crate::flat_index_generator::FlatIndexGenerator;
+use crate::ndarray::reduce::{split_axis, split_contiguous_axis};
+use crate::ops::sort::SortOps;
+use crate::{AxisType, Constructors, NdArray, StridedMemory};
+use std::cmp::Ordering;
+
+impl<T: NumericDataType> NdArray<'_, T> {
+    /// Returns a copy of the array sorted in ascending order along the specified axis.
+    ///
+    /// The sort is stable: equal elements retain their relative order.
+    /// NaNs are sorted to the end.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[3, 1, 2], [0, 5, -4]]);
+    /// assert_eq!(array.sort(-1), NdArray::new([[1, 2, 3], [-4, 0, 5]]));
+    /// assert_eq!(array.sort(0), NdArray::new([[0, 1, -4], [3, 5, 2]]));
+    /// ```
+    pub fn sort(&self, axis: impl AxisType) -> NdArray<'static, T> {
+        self.sort_along(axis, true)
+    }
+
+    /// Returns a copy of the array sorted in ascending order along the specified axis.
+    ///
+    /// The sort is unstable: equal elements may be reordered. This is typically faster than `sort()`.
+    /// NaNs are sorted to the end.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[3.0, 1.0, 2.0], [0.0, 5.0, -4.0]]);
+    /// assert_eq!(array.sort_unstable(1), NdArray::new([[1.0, 2.0, 3.0], [-4.0, 0.0, 5.0]]));
+    /// ```
+    pub fn sort_unstable(&self, axis: impl AxisType) -> NdArray<'static, T> {
+        self.sort_along(axis, false)
+    }
+
+    /// Returns the indices that would sort the array in ascending order along the specified axis.
+    ///
+    /// The sort is stable: the indices of equal elements retain their relative order.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[3, 1, 2], [0, 5, 0]]);
+    /// assert_eq!(array.argsort(-1), NdArray::new([[1, 2, 0], [0, 2, 1]]));
+    /// ```
+    pub fn argsort(&self, axis: impl AxisType) -> NdArray<'static, usize> {
+        self.argsort_along(axis, true)
+    }
+
+    /// Returns the indices that would sort the array in ascending order along the specified axis.
+    ///
+    /// The sort is unstable: the indices of equal elements may be in any order.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([3, 1, 2]);
+    /// assert_eq!(array.argsort_unstable(0), NdArray::new([1, 2, 0]));
+    /// ```
+    pub fn argsort_unstable(&self, axis: impl AxisType) -> NdArray<'static, usize> {
+        self.argsort_along(axis, false)
+    }
+
+    /// Returns the `k` largest (or smallest) elements along the specified axis
+    /// along with their indices.
+    ///
+    /// The elements are returned in sorted order (descending if `largest`, ascending otherwise).
+    /// Ties are broken in favour of the element with the lower index.
+    ///
+    /// # Panics
+    /// - If `k` is larger than the length of `axis`
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1, 9, 3, 7], [8, 2, 6, 4]]);
+    ///
+    /// let (values, indices) = array.topk(2, -1, true);
+    /// assert_eq!(values, NdArray::new([[9, 7], [8, 6]]));
+    /// assert_eq!(indices, NdArray::new([[1, 3], [0, 2]]));
+    ///
+    /// let (values, indices) = array.topk(1, 0, false);
+    /// assert_eq!(values, NdArray::new([[1, 2, 3, 4]]));
+    /// assert_eq!(indices, NdArray::new([[0, 1, 0, 1]]));
+    /// ```
+    pub fn topk(&self, k: usize, axis: impl AxisType, largest: bool) -> (NdArray<'static, T>, NdArray<'static, usize>) {
+        let axis = axis.as_absolute(self.ndims());
+        let (axis_length, axis_stride, outer_shape, outer_stride) = split_axis(axis, self.shape(), self.stride());
+        assert!(k <= axis_length, "topk: k ({k}) is larger than the length of axis {axis} ({axis_length})");
+
+        let mut shape = self.shape().to_vec();
+        shape[axis] = k;
+
+        let (dst_length_stride, dst_outer_stride) = split_contiguous_axis(axis, &shape);
+
+        let size = shape.iter().product();
+        let mut values = vec![T::default(); size];
+        let mut indices = vec![0; size];
+
+        let src = unsafe { self.ptr() };
+        let dst_offsets = FlatIndexGenerator::from(&outer_shape, &dst_outer_stride);
+
+        for (src_offset, dst_offset) in FlatIndexGenerator::from(&outer_shape, &outer_stride).zip(dst_offsets) {
+            unsafe {
+                let lane = src.add(src_offset);
+                let sorted = <T as SortOps>::argsort_uniform_stride(lane, axis_length, axis_stride, true, largest);
+
+                for (i, &index) in sorted.iter().take(k).enumerate() {
+                    values[dst_offset + i * dst_length_stride] = *lane.add(index * axis_stride);
+                    indices[dst_offset + i * dst_length_stride] = index;
+                }
+            }
+        }
+
+        unsafe {
+            (NdArray::from_contiguous_owned_buffer(shape.clone(), values),
+             NdArray::from_contiguous_owned_buffer(shape, indices))
+        }
+    }
+
+    /// Returns the sorted unique elements of the array as a 1D array.
+    ///
+    /// All NaNs are considered equal to each other.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[3, 1, 3], [2, 1, 5]]);
+    /// assert_eq!(array.unique(), NdArray::new([1, 2, 3, 5]));
+    /// ```
+    pub fn unique(&self) -> NdArray<'static, T> {
+        let mut data = self.clone_data();
+
+        data.sort_unstable_by(<T as SortOps>::sort_cmp);
+        data.dedup_by(|a, b| <T as SortOps>::sort_cmp(a, b) == Ordering::Equal);
+
+        unsafe { NdArray::from_contiguous_owned_buffer(vec![data.len()], data) }
+    }
+
+    /// Finds the indices into this sorted 1D array such that, if the corresponding elements of
+    /// `values` were inserted before the indices, the order would be preserved.
+    ///
+    /// Each returned index `i` is the leftmost valid insertion point, i.e. `self[i - 1] < value <= self[i]`.
+    /// See `searchsorted_right()` for the rightmost insertion point.
+    ///
+    /// # Panics
+    /// - If this array is not 1-dimensional
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([1, 2, 3, 3, 5]);
+    /// let indices = array.searchsorted(&NdArray::new([3, 0, 6]));
+    /// assert_eq!(indices, NdArray::new([2, 0, 5]));
+    /// ```
+    pub fn searchsorted(&self, values: &NdArray<T>) -> NdArray<'static, usize> {
+        self.searchsorted_by(values, |ordering| ordering == Ordering::Less)
+    }
+
+    /// Finds the indices into this sorted 1D array such that, if the corresponding elements of
+    /// `values` were inserted before the indices, the order would be preserved.
+    ///
+    /// Each returned index `i` is the rightmost valid insertion point, i.e. `self[i - 1] <= value < self[i]`.
+    /// See `searchsorted()` for the leftmost insertion point.
+    ///
+    /// # Panics
+    /// - If this array is not 1-dimensional
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([1, 2, 3, 3, 5]);
+    /// let indices = array.searchsorted_right(&NdArray::new([3, 0, 6]));
+    /// assert_eq!(indices, NdArray::new([4, 0, 5]));
+    /// ```
+    pub fn searchsorted_right(&self, values: &NdArray<T>) -> NdArray<'static, usize> {
+        self.searchsorted_by(values, |ordering| ordering != Ordering::Greater)
+    }
+
+    fn searchsorted_by(&self, values: &NdArray<T>, is_before: impl Fn(Ordering) -> bool) -> NdArray<'static, usize> {
+        assert_eq!(self.ndims(), 1, "searchsorted requires a 1-dimensional sorted array");
+
+        let sorted = self.clone_data();
+        let output = values.flatiter()
+                           .map(|value| sorted.partition_point(|x| is_before(<T as SortOps>::sort_cmp(x, &value))))
+                           .collect();
+
+        unsafe { NdArray::from_contiguous_owned_buffer(values.shape().to_vec(), output) }
+    }
+
+    fn sort_along(&self, axis: impl AxisType, stable: bool) -> NdArray<'static, T> {
+        let axis = axis.as_absolute(self.ndims());
+        let (axis_length, axis_stride, outer_shape, outer_stride) = split_axis(axis, self.shape(), self.stride());
+        let (dst_axis_stride, dst_outer_stride) = split_contiguous_axis(axis, self.shape());
+
+        let mut output = vec![T::default(); self.size()];
+
+        let src = unsafe { self.ptr() };
+        let dst = output.as_mut_ptr();
+        let dst_offsets = FlatIndexGenerator::from(&outer_shape, &dst_outer_stride);
+
+        for (src_offset, dst_offset) in FlatIndexGenerator::from(&outer_shape, &outer_stride).zip(dst_offsets) {
+            unsafe {
+                <T as SortOps>::sort_uniform_stride(src.add(src_offset), axis_length, axis_stride,
+                                                    dst.add(dst_offset), dst_axis_stride, stable);
+            }
+        }
+
+        unsafe { NdArray::from_contiguous_owned_buffer(self.shape().to_vec(), output) }
+    }
+
+    fn argsort_along(&self, axis: impl AxisType, stable: bool) -> NdArray<'static, usize> {
+        let axis = axis.as_absolute(self.ndims());
+        let (axis_length, axis_stride, outer_shape, outer_stride) = split_axis(axis, self.shape(), self.stride());
+        let (dst_axis_stride, dst_outer_stride) = split_contiguous_axis(axis, self.shape());
+
+        let mut output = vec![0; self.size()];
+
+        let src = unsafe { self.ptr() };
+        let dst_offsets = FlatIndexGenerator::from(&outer_shape, &dst_outer_stride);
+
+        for (src_offset, dst_offset) in FlatIndexGenerator::from(&outer_shape, &outer_stride).zip(dst_offsets) {
+            let sorted = unsafe {
+                <T as SortOps>::argsort_uniform_stride(src.add(src_offset), axis_length, axis_stride, stable, false)
+            };
+
+            for (i, index) in sorted.into_iter().enumerate() {
+                output[dst_offset + i * dst_axis_stride] = index;
+            }
+        }
+
+        unsafe { NdArray::from_contiguous_owned_buffer(self.shape().to_vec(), output) }
+    }
+}
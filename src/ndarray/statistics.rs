@@ -0,0 +1,544 @@
+use crate::dtype::{NumericDataType, RawDataType};
+use crate::flat_index_generator::FlatIndexGenerator;
+use crate::ndarray::reduce::{accumulates_in_f32, keepdims_shape, split_axis, split_contiguous_axis};
+use crate::ops::sort::SortOps;
+use crate::util::to_vec::ToVec;
+use crate::{AxisType, Constructors, FloatDataType, NdArray, Norm, Reshape, StridedMemory};
+use num::NumCast;
+
+impl<T: FloatDataType> NdArray<'_, T> {
+    /// Computes the variance of all elements in the array.
+    ///
+    /// The divisor used is `N - ddof` where `N` is the number of elements.
+    /// The result is NaN if `N <= ddof`.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(array.var(0).value(), 1.25);
+    /// assert_eq!(array.var(1).value(), 5.0 / 3.0);
+    /// ```
+    pub fn var(&self, ddof: usize) -> NdArray<'static, T> {
+        self.var_along(self.all_axes(), ddof)
+    }
+
+    /// Computes the variance along the specified axes.
+    ///
+    /// The divisor used is `N - ddof` where `N` is the number of elements reduced into each output.
+    /// The variance is computed in a single pass using Welford's algorithm.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1.0, 3.0], [2.0, 6.0]]);
+    /// assert_eq!(array.var_along(0, 0), NdArray::new([0.25, 2.25]));
+    /// assert_eq!(array.var_along(-1, 1), NdArray::new([2.0, 8.0]));
+    /// ```
+    pub fn var_along(&self, axes: impl ToVec<isize>, ddof: usize) -> NdArray<'static, T> {
+        if accumulates_in_f32(T::DTYPE) {
+            return self.var_along_in::<f32>(&axes.to_vec(), ddof);
+        }
+        self.var_along_in::<T>(&axes.to_vec(), ddof)
+    }
+
+    /// Computes the variance along `axes` with accumulators of type `A`.
+    fn var_along_in<A: FloatDataType>(&self, axes: &[isize], ddof: usize) -> NdArray<'static, T> {
+        let size = self.reduced_size(axes);
+
+        let mut count = vec![A::zero(); size];
+        let mut mean = vec![A::zero(); size];
+        let mut m2 = vec![A::zero(); size];
+
+        let out_shape = self.for_each_reduced(axes, |i, value| {
+            let value: A = NumCast::from(value).unwrap();
+            count[i] += A::one();
+
+            let delta = value - mean[i];
+            mean[i] += delta / count[i];
+            m2[i] += delta * (value - mean[i]);
+        });
+
+        let ddof: A = NumCast::from(ddof).unwrap();
+        let output = count.into_iter().zip(m2)
+                          .map(|(n, m2)| if n > ddof { m2 / (n - ddof) } else { A::nan() })
+                          .map(|var| NumCast::from(var).unwrap())
+                          .collect();
+
+        unsafe { NdArray::from_contiguous_owned_buffer(out_shape, output) }
+    }
+
+    /// Computes the variance along the specified axes, retaining the reduced axes with length 1.
+    pub fn var_keepdims(&self, axes: impl ToVec<isize>, ddof: usize) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.var_along(axes.clone(), ddof).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the standard deviation of all elements in the array.
+    ///
+    /// The divisor used is `N - ddof` where `N` is the number of elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    /// assert_eq!(array.std(0).value(), 2.0);
+    /// ```
+    pub fn std(&self, ddof: usize) -> NdArray<'static, T> {
+        self.std_along(self.all_axes(), ddof)
+    }
+
+    /// Computes the standard deviation along the specified axes.
+    ///
+    /// The divisor used is `N - ddof` where `N` is the number of elements reduced into each output.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1.0, 3.0], [3.0, 7.0]]);
+    /// assert_eq!(array.std_along(0, 0), NdArray::new([1.0, 2.0]));
+    /// ```
+    pub fn std_along(&self, axes: impl ToVec<isize>, ddof: usize) -> NdArray<'static, T> {
+        self.var_along(axes, ddof).map(|x| x.sqrt())
+    }
+
+    /// Computes the standard deviation along the specified axes,
+    /// retaining the reduced axes with length 1.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1.0, 3.0], [3.0, 7.0]]);
+    /// let standardized = (&array - array.mean_keepdims(0)) / array.std_keepdims(0, 0);
+    /// assert_eq!(standardized, NdArray::new([[-1.0, -1.0], [1.0, 1.0]]));
+    /// ```
+    pub fn std_keepdims(&self, axes: impl ToVec<isize>, ddof: usize) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.std_along(axes.clone(), ddof).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes `log(sum(exp(x)))` over all elements in a numerically stable way.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([1000.0, 1000.0]);
+    /// assert_eq!(array.logsumexp().value(), 1000.0 + 2.0f64.ln());
+    /// ```
+    pub fn logsumexp(&self) -> NdArray<'static, T> {
+        self.logsumexp_along(self.all_axes())
+    }
+
+    /// Computes `log(sum(exp(x)))` along the specified axes in a numerically stable way.
+    ///
+    /// The maximum along the axes is subtracted before exponentiating to avoid overflow.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[0.0, 0.0], [1.0, f64::NEG_INFINITY]]);
+    /// assert_eq!(array.logsumexp_along(1), NdArray::new([2.0f64.ln(), 1.0]));
+    /// ```
+    pub fn logsumexp_along(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+
+        let shift: Vec<T> = self.max_along(axes.clone())
+                                .flatiter()
+                                .map(|max| if max.is_finite() { max } else { T::zero() })
+                                .collect();
+
+        let mut sum = vec![T::zero(); shift.len()];
+        let out_shape = self.for_each_reduced(&axes, |i, value| {
+            sum[i] += (value - shift[i]).exp();
+        });
+
+        let output = sum.into_iter().zip(shift)
+                        .map(|(sum, shift)| shift + sum.ln())
+                        .collect();
+
+        unsafe { NdArray::from_contiguous_owned_buffer(out_shape, output) }
+    }
+
+    /// Computes `log(sum(exp(x)))` along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let logits = NdArray::new([[0.0, 0.0], [0.0, 0.0]]);
+    /// let log_softmax = &logits - logits.logsumexp_keepdims(-1);
+    /// assert_eq!(log_softmax, NdArray::new([[-(2.0f64.ln()), -(2.0f64.ln())],
+    ///                                       [-(2.0f64.ln()), -(2.0f64.ln())]]));
+    /// ```
+    pub fn logsumexp_keepdims(&self, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.logsumexp_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the vector norm of all elements in the array.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[3.0, -4.0], [0.0, 0.0]]);
+    /// assert_eq!(array.norm(Norm::L1).value(), 7.0);
+    /// assert_eq!(array.norm(Norm::L2).value(), 5.0);
+    /// assert_eq!(array.norm(Norm::Inf).value(), 4.0);
+    /// ```
+    pub fn norm(&self, ord: Norm<T>) -> NdArray<'static, T> {
+        self.norm_along(ord, self.all_axes())
+    }
+
+    /// Computes the vector norm along the specified axes.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[3.0, -4.0], [1.0, 1.0]]);
+    /// assert_eq!(array.norm_along(Norm::L2, 1), NdArray::new([5.0, 2.0f64.sqrt()]));
+    /// assert_eq!(array.norm_along(Norm::Lp(1.0), 0), NdArray::new([4.0, 5.0]));
+    /// ```
+    pub fn norm_along(&self, ord: Norm<T>, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        match ord {
+            Norm::L1 => self.reduce_along(|value, acc| acc + value.abs(), axes, T::zero()),
+            Norm::L2 => self.reduce_along(|value, acc| acc + value * value, axes, T::zero())
+                            .map(|x| x.sqrt()),
+            Norm::Lp(p) => self.reduce_along(|value, acc| acc + value.abs().powf(p), axes, T::zero())
+                               .map(|x| x.powf(p.recip())),
+            Norm::Inf => self.reduce_along(|value, acc| acc.max(value.abs()), axes, T::zero()),
+        }
+    }
+
+    /// Computes the vector norm along the specified axes, retaining the reduced axes with length 1.
+    pub fn norm_keepdims(&self, ord: Norm<T>, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.norm_along(ord, axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the median of all elements in the array.
+    ///
+    /// The result is NaN if the array contains a NaN.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[3.0, 1.0], [4.0, 2.0]]);
+    /// assert_eq!(array.median().value(), 2.5);
+    /// ```
+    pub fn median(&self) -> NdArray<'static, T> {
+        self.quantile(<T as NumCast>::from(0.5).unwrap())
+    }
+
+    /// Computes the median along the specified axis.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[3.0, 1.0, 2.0], [4.0, 6.0, 5.0]]);
+    /// assert_eq!(array.median_along(-1), NdArray::new([2.0, 5.0]));
+    /// ```
+    pub fn median_along(&self, axis: impl AxisType) -> NdArray<'static, T> {
+        self.quantile_along(<T as NumCast>::from(0.5).unwrap(), axis)
+    }
+
+    /// Computes the median along the specified axis, retaining the reduced axis with length 1.
+    pub fn median_keepdims(&self, axis: impl AxisType) -> NdArray<'static, T> {
+        let axis = axis.isize();
+        self.median_along(axis).reshape(keepdims_shape(&[axis], self.shape()))
+    }
+
+    /// Computes the `q`-th quantile of all elements in the array.
+    ///
+    /// Quantiles that fall between two elements are linearly interpolated.
+    /// The result is NaN if the array contains a NaN.
+    ///
+    /// # Panics
+    /// - If `q` is not in the range `[0, 1]`
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// assert_eq!(array.quantile(0.25).value(), 2.0);
+    /// assert_eq!(array.quantile(0.1).value(), 1.4);
+    /// ```
+    pub fn quantile(&self, q: T) -> NdArray<'static, T> {
+        let output = quantile_of_lane(self.clone_data(), q);
+        NdArray::scalar(output)
+    }
+
+    /// Computes the `q`-th quantile along the specified axis.
+    ///
+    /// Quantiles that fall between two elements are linearly interpolated.
+    ///
+    /// # Panics
+    /// - If `q` is not in the range `[0, 1]`
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1.0, 10.0], [2.0, 20.0], [4.0, 40.0]]);
+    /// assert_eq!(array.quantile_along(0.75, 0), NdArray::new([3.0, 30.0]));
+    /// ```
+    pub fn quantile_along(&self, q: T, axis: impl AxisType) -> NdArray<'static, T> {
+        let axis = axis.as_absolute(self.ndims());
+        let (axis_length, axis_stride, outer_shape, outer_stride) = split_axis(axis, self.shape(), self.stride());
+
+        let src = unsafe { self.ptr() };
+        let output = FlatIndexGenerator::from(&outer_shape, &outer_stride).map(|offset| {
+            let lane = (0..axis_length).map(|i| unsafe { *src.add(offset + i * axis_stride) }).collect();
+            quantile_of_lane(lane, q)
+        }).collect();
+
+        unsafe { NdArray::from_contiguous_owned_buffer(outer_shape, output) }
+    }
+
+    /// Computes the `q`-th quantile along the specified axis, retaining the reduced axis with length 1.
+    pub fn quantile_keepdims(&self, q: T, axis: impl AxisType) -> NdArray<'static, T> {
+        let axis = axis.isize();
+        self.quantile_along(q, axis).reshape(keepdims_shape(&[axis], self.shape()))
+    }
+
+    /// Computes the mean of the elements of the array where `mask` is `true`.
+    ///
+    /// `mask` is broadcast to the shape of the array.
+    /// The result is NaN if no element is selected.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([1.0, 2.0, 3.0, 4.0]);
+    /// let mask = NdArray::new([true, false, true, false]);
+    /// assert_eq!(array.mean_where(&mask).value(), 2.0);
+    /// ```
+    pub fn mean_where(&self, mask: &NdArray<bool>) -> NdArray<'static, T> {
+        self.mean_where_along(mask, self.all_axes())
+    }
+
+    /// Computes the mean along the specified axes of the elements where `mask` is `true`.
+    ///
+    /// `mask` is broadcast to the shape of the array.
+    /// Outputs for which no element is selected are NaN.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// // a padded batch of 2 sequences with lengths 3 and 1
+    /// let batch = NdArray::new([[1.0, 2.0, 3.0], [4.0, 0.0, 0.0]]);
+    /// let mask = NdArray::new([[true, true, true], [true, false, false]]);
+    /// assert_eq!(batch.mean_where_along(&mask, 1), NdArray::new([2.0, 4.0]));
+    /// ```
+    pub fn mean_where_along(&self, mask: &NdArray<bool>, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+
+        let count = self.zip_with(mask, |_, selected| if selected { T::one() } else { T::zero() });
+        self.sum_where_along(mask, axes.clone()) / count.sum_along(axes)
+    }
+
+    /// Computes the mean along the specified axes of the elements where `mask` is `true`,
+    /// retaining the reduced axes with length 1.
+    pub fn mean_where_keepdims(&self, mask: &NdArray<bool>, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.mean_where_along(mask, axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the weighted average of all elements in the array.
+    ///
+    /// `weights` is broadcast to the shape of the array.
+    /// The result is NaN if the weights sum to zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([1.0, 2.0, 3.0, 4.0]);
+    /// let weights = NdArray::new([4.0, 3.0, 2.0, 1.0]);
+    /// assert_eq!(array.average(&weights).value(), 2.0);
+    /// ```
+    pub fn average(&self, weights: &NdArray<T>) -> NdArray<'static, T> {
+        self.average_along(weights, self.all_axes())
+    }
+
+    /// Computes the weighted average along the specified axes.
+    ///
+    /// `weights` is broadcast to the shape of the array.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1.0, 2.0], [3.0, 4.0]]);
+    /// let weights = NdArray::new([3.0, 1.0]);
+    /// assert_eq!(array.average_along(&weights, 1), NdArray::new([1.25, 3.25]));
+    /// ```
+    pub fn average_along(&self, weights: &NdArray<T>, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+
+        let weighted = self.zip_with(weights, |value, weight| value * weight);
+        let weights = self.zip_with(weights, |_, weight| weight);
+
+        weighted.sum_along(axes.clone()) / weights.sum_along(axes)
+    }
+
+    /// Computes the weighted average along the specified axes,
+    /// retaining the reduced axes with length 1.
+    pub fn average_keepdims(&self, weights: &NdArray<T>, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.average_along(weights, axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Returns the total number of elements in the output of a reduction along `axes`.
+    fn reduced_size(&self, axes: &[isize]) -> usize {
+        let mut size = self.size();
+        for &axis in axes.iter() {
+            size /= self.shape()[axis.as_absolute(self.ndims())];
+        }
+        size
+    }
+
+    fn all_axes(&self) -> Vec<isize> {
+        (0..self.ndims() as isize).collect()
+    }
+}
+
+impl<T: NumericDataType> NdArray<'_, T> {
+    /// Computes the cumulative sum of elements along the specified axis.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1, 2, 3], [4, 5, 6]]);
+    /// assert_eq!(array.cumsum(-1), NdArray::new([[1, 3, 6], [4, 9, 15]]));
+    /// assert_eq!(array.cumsum(0), NdArray::new([[1, 2, 3], [5, 7, 9]]));
+    /// ```
+    pub fn cumsum(&self, axis: impl AxisType) -> NdArray<'static, T> {
+        self.scan_along(axis, T::zero(), |acc, value| *acc += value)
+    }
+
+    /// Computes the cumulative product of elements along the specified axis.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1, 2, 3], [4, 5, 6]]);
+    /// assert_eq!(array.cumprod(1), NdArray::new([[1, 2, 6], [4, 20, 120]]));
+    /// ```
+    pub fn cumprod(&self, axis: impl AxisType) -> NdArray<'static, T> {
+        self.scan_along(axis, T::one(), |acc, value| *acc *= value)
+    }
+
+    /// Computes the sum of the elements of the array where `mask` is `true`.
+    ///
+    /// `mask` is broadcast to the shape of the array.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1, 2], [3, 4]]);
+    /// let mask = NdArray::new([[true, false], [true, true]]);
+    /// assert_eq!(array.sum_where(&mask).value(), 8);
+    /// ```
+    pub fn sum_where(&self, mask: &NdArray<bool>) -> NdArray<'static, T> {
+        self.zip_with(mask, |value, selected| if selected { value } else { T::zero() }).sum()
+    }
+
+    /// Computes the sum along the specified axes of the elements where `mask` is `true`.
+    ///
+    /// `mask` is broadcast to the shape of the array.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let array = NdArray::new([[1, 2], [3, 4]]);
+    /// let mask = NdArray::new([true, false]);
+    /// assert_eq!(array.sum_where_along(&mask, 1), NdArray::new([1, 3]));
+    /// ```
+    pub fn sum_where_along(&self, mask: &NdArray<bool>, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        self.zip_with(mask, |value, selected| if selected { value } else { T::zero() }).sum_along(axes)
+    }
+
+    /// Computes the sum along the specified axes of the elements where `mask` is `true`,
+    /// retaining the reduced axes with length 1.
+    pub fn sum_where_keepdims(&self, mask: &NdArray<bool>, axes: impl ToVec<isize>) -> NdArray<'static, T> {
+        let axes = axes.to_vec();
+        self.sum_where_along(mask, axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Returns a new array with `func(value, other)` applied to every element of `self`
+    /// and the corresponding element of `other` broadcast to the shape of `self`.
+    ///
+    /// # Panics
+    /// - If `other` cannot be broadcast to the shape of `self`
+    fn zip_with<U: RawDataType>(&self, other: &NdArray<U>, func: impl Fn(T, U) -> T) -> NdArray<'static, T> {
+        let other = other.broadcast_to(self.shape());
+
+        let data = self.flatiter().zip(other.flatiter())
+                       .map(|(value, other)| func(value, other))
+                       .collect();
+
+        unsafe { NdArray::from_contiguous_owned_buffer(self.shape().to_vec(), data) }
+    }
+
+    fn scan_along(&self, axis: impl AxisType, init: T, func: impl Fn(&mut T, T)) -> NdArray<'static, T> {
+        let axis = axis.as_absolute(self.ndims());
+        let (axis_length, axis_stride, outer_shape, outer_stride) = split_axis(axis, self.shape(), self.stride());
+        let (dst_axis_stride, dst_outer_stride) = split_contiguous_axis(axis, self.shape());
+
+        let mut output = vec![T::default(); self.size()];
+
+        let src = unsafe { self.ptr() };
+        let dst_offsets = FlatIndexGenerator::from(&outer_shape, &dst_outer_stride);
+
+        for (src_offset, dst_offset) in FlatIndexGenerator::from(&outer_shape, &outer_stride).zip(dst_offsets) {
+            let mut acc = init;
+
+            for i in 0..axis_length {
+                func(&mut acc, unsafe { *src.add(src_offset + i * axis_stride) });
+                output[dst_offset + i * dst_axis_stride] = acc;
+            }
+        }
+
+        unsafe { NdArray::from_contiguous_owned_buffer(self.shape().to_vec(), output) }
+    }
+}
+
+/// Computes the `q`-th quantile of `lane` using linear interpolation between the closest ranks.
+fn quantile_of_lane<T: FloatDataType>(mut lane: Vec<T>, q: T) -> T {
+    assert!(q >= T::zero() && q <= T::one(), "quantile must be in the range [0, 1], got {q}");
+
+    if lane.is_empty() || lane.iter().any(|x| x.is_nan()) {
+        return T::nan();
+    }
+
+    lane.sort_unstable_by(<T as SortOps>::sort_cmp);
+
+    let last: T = NumCast::from(lane.len() - 1).unwrap();
+    let position = q * last;
+
+    let lower = position.floor();
+    let weight = position - lower;
+
+    let lower = lower.to_usize().unwrap();
+    let upper = (lower + 1).min(lane.len() - 1);
+
+    lane[lower] + (lane[upper] - lane[lower]) * weight
+}
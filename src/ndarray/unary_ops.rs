@@ -20,3 +20,11 @@ impl<T: RawDataType + UnaryOps> Neg for &NdArray<'_, T> {
         }
     }
 }
+
+impl<T: RawDataType> NdArray<'_, T> {
+    /// Returns a new contiguous ndarray with `func` applied to every element.
+    pub(crate) fn map<F: RawDataType>(&self, func: impl Fn(T) -> F) -> NdArray<'static, F> {
+        let data = self.flatiter().map(func).collect();
+        unsafe { NdArray::from_contiguous_owned_buffer(self.shape().to_vec(), data) }
+    }
+}
@@ -119,3 +119,8 @@ impl BinaryOpAdd for u32 {
                    dst as *mut i32, 1, count);
     }
 }
+
+impl BinaryOpAdd for half::f16 {}
+impl BinaryOpAdd for half::bf16 {}
+impl BinaryOpAdd for num::complex::Complex32 {}
+impl BinaryOpAdd for num::complex::Complex64 {}
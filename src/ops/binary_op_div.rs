@@ -45,3 +45,8 @@ impl BinaryOpDiv for f64 {
         vDSP_vdivD(rhs, rhs_stride as isize, lhs, lhs_stride as isize, dst, 1, count);
     }
 }
+
+impl BinaryOpDiv for half::f16 {}
+impl BinaryOpDiv for half::bf16 {}
+impl BinaryOpDiv for num::complex::Complex32 {}
+impl BinaryOpDiv for num::complex::Complex64 {}
@@ -63,3 +63,8 @@ impl BinaryOpMul for f64 {
         vDSP_vmulD(lhs, lhs_stride as isize, rhs, rhs_stride as isize, dst, 1, count);
     }
 }
+
+impl BinaryOpMul for half::f16 {}
+impl BinaryOpMul for half::bf16 {}
+impl BinaryOpMul for num::complex::Complex32 {}
+impl BinaryOpMul for num::complex::Complex64 {}
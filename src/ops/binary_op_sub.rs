@@ -33,3 +33,8 @@ impl BinaryOpSub for f64 {
         vDSP_vsubD(rhs, rhs_stride as isize, lhs, lhs_stride as isize, dst, 1, count);
     }
 }
+
+impl BinaryOpSub for half::f16 {}
+impl BinaryOpSub for half::bf16 {}
+impl BinaryOpSub for num::complex::Complex32 {}
+impl BinaryOpSub for num::complex::Complex64 {}
@@ -134,3 +134,35 @@ impl DotProduct for f64 {
         *dst += cblas_ddot(count as i32, src0, stride0 as i32, src1, stride1 as i32);
     }
 }
+
+/// Half-precision dot products are accumulated in `f32` to avoid losing precision.
+macro_rules! impl_dot_product_via_f32 {
+    ($($dtype:ty),*) => {
+        $(
+            impl DotProduct for $dtype {
+                unsafe fn dot_product(src0: *const Self, src1: *const Self, dst: *mut Self, count: usize) {
+                    Self::strided_dot_product(src0, 1, src1, 1, dst, count);
+                }
+
+                unsafe fn strided_dot_product(mut src0: *const Self, stride0: usize,
+                                              mut src1: *const Self, stride1: usize,
+                                              dst: *mut Self, count: usize) {
+                    let mut sum = (*dst).to_f32();
+
+                    for _ in 0..count {
+                        sum += (*src0).to_f32() * (*src1).to_f32();
+                        src0 = src0.add(stride0);
+                        src1 = src1.add(stride1);
+                    }
+
+                    *dst = Self::from_f32(sum);
+                }
+            }
+        )*
+    };
+}
+
+impl_dot_product_via_f32!(half::f16, half::bf16);
+
+impl DotProduct for num::complex::Complex32 {}
+impl DotProduct for num::complex::Complex64 {}
@@ -117,3 +117,8 @@ impl Fill for f64 {
         vDSP_vfillD(addr_of!(value), ptr, stride as isize, count);
     }
 }
+
+impl Fill for half::f16 {}
+impl Fill for half::bf16 {}
+impl Fill for num::complex::Complex32 {}
+impl Fill for num::complex::Complex64 {}
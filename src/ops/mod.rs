@@ -9,6 +9,7 @@ pub mod simd_neg;
 pub mod fill;
 
 pub mod dot_product;
+pub mod mul_add;
 
 pub mod reduce_sum;
 pub mod reduce_product;
@@ -16,6 +17,10 @@ pub mod reduce_min;
 pub mod reduce_max;
 pub mod reduce_min_magnitude;
 pub mod reduce_max_magnitude;
+pub mod reduce_argmin;
+pub mod reduce_argmax;
+
+pub mod sort;
 
 pub mod binary_ops;
 pub mod binary_op_add;
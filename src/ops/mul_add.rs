@@ -0,0 +1,53 @@
+use half::{bf16, f16};
+
+
+pub(crate) trait MulAdd: Copy {
+    /// Computes `(self * a) + b`.
+    fn mul_add(self, a: Self, b: Self) -> Self;
+}
+
+macro_rules! impl_mul_add {
+    ($($dtype:ty),*) => {
+        $(
+            impl MulAdd for $dtype {
+                #[inline(always)]
+                fn mul_add(self, a: Self, b: Self) -> Self {
+                    num::traits::MulAdd::mul_add(self, a, b)
+                }
+            }
+        )*
+    };
+}
+
+impl_mul_add!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Half-precision values are fused in `f32` and rounded once.
+macro_rules! impl_mul_add_via_f32 {
+    ($($dtype:ty),*) => {
+        $(
+            impl MulAdd for $dtype {
+                #[inline(always)]
+                fn mul_add(self, a: Self, b: Self) -> Self {
+                    Self::from_f32(self.to_f32().mul_add(a.to_f32(), b.to_f32()))
+                }
+            }
+        )*
+    };
+}
+
+impl_mul_add_via_f32!(f16, bf16);
+
+macro_rules! impl_mul_add_unfused {
+    ($($dtype:ty),*) => {
+        $(
+            impl MulAdd for $dtype {
+                #[inline(always)]
+                fn mul_add(self, a: Self, b: Self) -> Self {
+                    self * a + b
+                }
+            }
+        )*
+    };
+}
+
+impl_mul_add_unfused!(num::complex::Complex32, num::complex::Complex64);
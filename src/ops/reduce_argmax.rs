@@ -0,0 +1,113 @@
+use crate::flat_index_generator::FlatIndexGenerator;
+use crate::ndarray::collapse_contiguous::has_uniform_stride;
+use crate::IntegerDataType;
+
+
+pub(crate) trait ReduceArgMax: Copy + PartialOrd {
+    /// Returns whether `value` should replace `current` as the running maximum.
+    ///
+    /// Ties are resolved in favour of `current` so that the first occurrence of the maximum
+    /// is returned.
+    #[inline(always)]
+    fn is_new_max(value: Self, current: Self) -> bool {
+        value > current
+    }
+
+    /// Computes the index of the max of `count` elements stored contiguously in memory
+    /// pointed to by `ptr`.
+    ///
+    /// # Safety
+    /// - `ptr` must point to a valid array of `count` elements.
+    /// - `count` must be non-zero.
+    unsafe fn argmax_contiguous(ptr: *const Self, count: usize) -> usize {
+        Self::argmax_uniform_stride(ptr, count, 1)
+    }
+
+    /// Computes the index of the max of `count` elements stored with a uniform stride in memory
+    /// pointed to by `ptr`. The returned index counts elements, not memory locations.
+    ///
+    /// # Safety
+    /// - `ptr` must point to a valid array of `count * stride` elements.
+    /// - `count` must be non-zero.
+    unsafe fn argmax_uniform_stride(ptr: *const Self, count: usize, stride: usize) -> usize {
+        let mut output = *ptr;
+        let mut index = 0;
+
+        for i in 1..count {
+            let value = *ptr.add(i * stride);
+            if Self::is_new_max(value, output) {
+                output = value;
+                index = i;
+            }
+        }
+
+        index
+    }
+
+    /// Computes the flat index of the max of elements stored in a strided memory layout
+    /// defined by `shape` and `stride` and pointed to by `ptr`.
+    ///
+    /// # Safety
+    /// - `ptr` must be a valid, non-null pointer to the memory region described by `shape` and `stride`.
+    ///
+    /// # Implementation
+    /// - If the memory layout is contiguous, delegates this to the `argmax_contiguous()` function
+    /// - If the memory layout has a uniform stride between elements, delegates to `argmax_uniform_stride()`
+    /// - Otherwise, uses an unspecialized loop
+    unsafe fn argmax(ptr: *const Self, shape: &[usize], stride: &[usize]) -> usize {
+        if let Some(stride) = has_uniform_stride(shape, stride) {
+            return if stride == 1 {
+                Self::argmax_contiguous(ptr, shape.iter().product())
+            } else {
+                Self::argmax_uniform_stride(ptr, shape.iter().product(), stride)
+            };
+        }
+
+        let mut indices = FlatIndexGenerator::from(shape, stride);
+        let mut output = *ptr.add(indices.next().unwrap());
+        let mut index = 0;
+
+        for (i, offset) in indices.enumerate() {
+            let value = *ptr.add(offset);
+            if Self::is_new_max(value, output) {
+                output = value;
+                index = i + 1;
+            }
+        }
+        index
+    }
+}
+
+impl<T: IntegerDataType> ReduceArgMax for T {}
+
+impl ReduceArgMax for f32 {
+    /// NaNs are skipped, consistent with `ReduceMax`.
+    #[inline(always)]
+    fn is_new_max(value: Self, current: Self) -> bool {
+        value > current || (current.is_nan() && !value.is_nan())
+    }
+}
+
+impl ReduceArgMax for f64 {
+    /// NaNs are skipped, consistent with `ReduceMax`.
+    #[inline(always)]
+    fn is_new_max(value: Self, current: Self) -> bool {
+        value > current || (current.is_nan() && !value.is_nan())
+    }
+}
+
+impl ReduceArgMax for half::f16 {
+    /// NaNs are skipped, consistent with `ReduceMax`.
+    #[inline(always)]
+    fn is_new_max(value: Self, current: Self) -> bool {
+        value > current || (current.is_nan() && !value.is_nan())
+    }
+}
+
+impl ReduceArgMax for half::bf16 {
+    /// NaNs are skipped, consistent with `ReduceMax`.
+    #[inline(always)]
+    fn is_new_max(value: Self, current: Self) -> bool {
+        value > current || (current.is_nan() && !value.is_nan())
+    }
+}
@@ -104,3 +104,6 @@ impl ReduceMax for f64 {
         Self::simd_max_uniform(ptr, count, stride)
     }
 }
+
+impl ReduceMax for half::f16 {}
+impl ReduceMax for half::bf16 {}
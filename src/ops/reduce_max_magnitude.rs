@@ -81,3 +81,6 @@ impl ReduceMaxMagnitude for f64 {
         output
     }
 }
+
+impl ReduceMaxMagnitude for half::f16 {}
+impl ReduceMaxMagnitude for half::bf16 {}
@@ -104,3 +104,6 @@ impl ReduceMin for f64 {
         Self::simd_min_uniform(ptr, count, stride)
     }
 }
+
+impl ReduceMin for half::f16 {}
+impl ReduceMin for half::bf16 {}
@@ -81,3 +81,6 @@ impl ReduceMinMagnitude for f64 {
         output
     }
 }
+
+impl ReduceMinMagnitude for half::f16 {}
+impl ReduceMinMagnitude for half::bf16 {}
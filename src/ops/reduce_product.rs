@@ -85,3 +85,6 @@ impl ReduceProduct for f64 {
         Self::simd_product_uniform(ptr, count, stride)
     }
 }
+
+impl ReduceProduct for half::f16 {}
+impl ReduceProduct for half::bf16 {}
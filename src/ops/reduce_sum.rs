@@ -105,3 +105,37 @@ impl ReduceSum for f64 {
         Self::simd_sum_uniform(ptr, count, stride)
     }
 }
+
+/// Half-precision sums are accumulated in `f32` to avoid losing precision.
+macro_rules! impl_reduce_sum_via_f32 {
+    ($($dtype:ty),*) => {
+        $(
+            impl ReduceSum for $dtype {
+                unsafe fn sum_uniform_stride(mut ptr: *const Self, count: usize, stride: usize) -> Self {
+                    let mut output = 0f32;
+
+                    for _ in 0..count {
+                        output += (*ptr).to_f32();
+                        ptr = ptr.add(stride);
+                    }
+
+                    Self::from_f32(output)
+                }
+
+                unsafe fn sum(ptr: *const Self, shape: &[usize], stride: &[usize]) -> Self {
+                    if let Some(stride) = has_uniform_stride(shape, stride) {
+                        return Self::sum_uniform_stride(ptr, shape.iter().product(), stride);
+                    }
+
+                    let mut output = 0f32;
+                    for index in FlatIndexGenerator::from(shape, stride) {
+                        output += (*ptr.add(index)).to_f32();
+                    }
+                    Self::from_f32(output)
+                }
+            }
+        )*
+    };
+}
+
+impl_reduce_sum_via_f32!(half::f16, half::bf16);
@@ -0,0 +1,97 @@
+use crate::IntegerDataType;
+use std::cmp::Ordering;
+
+
+pub(crate) trait SortOps: Copy + PartialOrd {
+    /// A total ordering over values of this type used by all sorting routines.
+    fn sort_cmp(a: &Self, b: &Self) -> Ordering;
+
+    /// Sorts `count` elements stored with a uniform stride in memory pointed to by `src`
+    /// and writes them in ascending order to `dst` with a stride of `dst_stride`.
+    ///
+    /// # Safety
+    /// - `src` must point to a valid array of `count * stride` elements.
+    /// - `dst` must point to a valid array of `count * dst_stride` elements.
+    unsafe fn sort_uniform_stride(src: *const Self, count: usize, stride: usize,
+                                  dst: *mut Self, dst_stride: usize, stable: bool) {
+        let mut lane: Vec<Self> = (0..count).map(|i| *src.add(i * stride)).collect();
+
+        if stable {
+            lane.sort_by(Self::sort_cmp);
+        } else {
+            lane.sort_unstable_by(Self::sort_cmp);
+        }
+
+        for (i, value) in lane.into_iter().enumerate() {
+            *dst.add(i * dst_stride) = value;
+        }
+    }
+
+    /// Computes the indices that would sort `count` elements stored with a uniform stride
+    /// in memory pointed to by `src`.
+    ///
+    /// # Safety
+    /// - `src` must point to a valid array of `count * stride` elements.
+    unsafe fn argsort_uniform_stride(src: *const Self, count: usize, stride: usize,
+                                     stable: bool, descending: bool) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..count).collect();
+
+        let compare = |&i: &usize, &j: &usize| {
+            let ordering = Self::sort_cmp(&*src.add(i * stride), &*src.add(j * stride));
+            if descending { ordering.reverse() } else { ordering }
+        };
+
+        if stable {
+            indices.sort_by(compare);
+        } else {
+            indices.sort_unstable_by(compare);
+        }
+
+        indices
+    }
+}
+
+impl<T: IntegerDataType> SortOps for T {
+    fn sort_cmp(a: &Self, b: &Self) -> Ordering {
+        a.cmp(b)
+    }
+
+    /// Equal integers are indistinguishable, so a stable sort is never required.
+    unsafe fn sort_uniform_stride(src: *const Self, count: usize, stride: usize,
+                                  dst: *mut Self, dst_stride: usize, _: bool) {
+        let mut lane: Vec<Self> = (0..count).map(|i| *src.add(i * stride)).collect();
+        lane.sort_unstable();
+
+        for (i, value) in lane.into_iter().enumerate() {
+            *dst.add(i * dst_stride) = value;
+        }
+    }
+}
+
+impl SortOps for f32 {
+    /// NaNs are ordered after all other values.
+    fn sort_cmp(a: &Self, b: &Self) -> Ordering {
+        a.partial_cmp(b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+    }
+}
+
+impl SortOps for f64 {
+    /// NaNs are ordered after all other values.
+    fn sort_cmp(a: &Self, b: &Self) -> Ordering {
+        a.partial_cmp(b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+    }
+}
+
+impl SortOps for half::f16 {
+    /// NaNs are ordered after all other values.
+    fn sort_cmp(a: &Self, b: &Self) -> Ordering {
+        a.partial_cmp(b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+    }
+}
+
+impl SortOps for half::bf16 {
+    /// NaNs are ordered after all other values.
+    fn sort_cmp(a: &Self, b: &Self) -> Ordering {
+        a.partial_cmp(b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+    }
+}
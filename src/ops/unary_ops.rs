@@ -75,3 +75,8 @@ impl<T: FloatDataType + SimdNeg> UnaryOps for T {
         Self::simd_neg_stride_n(operand, stride, dst, count);
     }
 }
+
+impl UnaryOps for half::f16 {}
+impl UnaryOps for half::bf16 {}
+impl UnaryOps for num::complex::Complex32 {}
+impl UnaryOps for num::complex::Complex64 {}
@@ -1,8 +1,10 @@
 use crate::accumulate_grad::AccumulateGrad;
-use crate::gradient_function::GradientFunction;
+use crate::gradient_function::{run_backward, GradientFunction};
 use crate::ndarray::flags::NdArrayFlags;
 use crate::{Constructors, NdArray, StridedMemory, Tensor, TensorDataType};
 use crate::none_backwards::NoneBackwards;
+use crate::autograd::util::constant;
+use crate::graph_dot::graph_dot;
 
 impl<'a, T: TensorDataType> Tensor<'a, T> {
     /// Checks if the tensor is a leaf.
@@ -83,7 +85,10 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
 
     /// Returns the gradient of the differentiated tensor with respect to `self`.
     ///
-    /// This method returns a view into the gradient.
+    /// Gradients are only accumulated into leaf tensors and tensors which retain their gradients
+    /// (see `retain_grad()`). For other tensors, this returns `None`.
+    ///
+    /// This method returns a copy of the gradient, which is unaffected by later backward passes.
     ///
     /// # Examples
     ///
@@ -101,8 +106,39 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
     /// // dc/da = b
     /// assert_eq!(a.gradient().unwrap(), b);
     /// ```
-    pub fn gradient(&'a self) -> Option<NdArray<'a, T>> {
-        unsafe { (*self.grad_fn.as_ptr()).gradient() }
+    pub fn gradient(&self) -> Option<NdArray<'static, T>> {
+        self.gradient_tensor().map(|gradient| gradient.ndarray().clone())
+    }
+
+    /// Returns the gradient of the differentiated tensor with respect to `self` as a tensor.
+    ///
+    /// If the gradient was computed with `backward_create_graph()`, it has its own graph
+    /// and can itself be differentiated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut a = Tensor::scalar(3.0f32);
+    /// a.set_requires_grad(true);
+    ///
+    /// let b = &a * &a * &a;
+    /// b.backward_create_graph();
+    ///
+    /// // db/da = 3a^2
+    /// let gradient = a.gradient_tensor().unwrap();
+    /// assert_eq!(gradient.value(), 27.0);
+    ///
+    /// a.zero_gradient();
+    /// gradient.backward();
+    ///
+    /// // d(3a^2)/da = 6a
+    /// assert_eq!(a.gradient().unwrap(), Tensor::scalar(18.0));
+    /// ```
+    pub fn gradient_tensor(&self) -> Option<Tensor<'static, T>> {
+        let gradient = self.grad_fn.borrow().gradient().map(Tensor::alias);
+        gradient.or_else(|| self.grad_fn.hooks().retained_grad().map(Tensor::alias))
     }
 
     /// Sets the gradient of this tensor to zero.
@@ -125,6 +161,112 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
     /// ```
     pub fn zero_gradient(&self) {
         self.grad_fn.borrow_mut().zero_gradient();
+        self.grad_fn.hooks().zero_retained_grad();
+    }
+
+    /// Enables the accumulation of gradients into this tensor on the backward pass
+    /// so that they can be retrieved with `gradient()` even if it is not a leaf.
+    ///
+    /// This has no effect on leaf tensors, which always accumulate their gradients.
+    ///
+    /// # Panics
+    /// - If the tensor does not require gradients
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut a = Tensor::new([1.0, 2.0]);
+    /// a.set_requires_grad(true);
+    ///
+    /// let b = &a * 3.0;
+    /// b.retain_grad();
+    ///
+    /// (&b * &b).sum().backward();
+    ///
+    /// // d(b^2)/db = 2b
+    /// assert_eq!(b.gradient().unwrap(), NdArray::new([6.0, 12.0]));
+    /// ```
+    pub fn retain_grad(&self) {
+        assert!(self.requires_grad(), "cannot retain the gradient of a tensor which does not require gradients");
+
+        if !self.is_leaf() {
+            self.grad_fn.hooks().retain_grad();
+        }
+    }
+
+    /// Returns whether this non-leaf tensor accumulates its gradients (see `retain_grad()`).
+    pub fn retains_grad(&self) -> bool {
+        self.grad_fn.hooks().retains_grad()
+    }
+
+    /// Registers a hook which is called with the gradient of this tensor on every backward pass.
+    ///
+    /// If the hook returns an array, it replaces the gradient which is propagated to the sources
+    /// of this tensor (and accumulated into it, if it's a leaf). Hooks run in the order they
+    /// were registered, each receiving the gradient returned by the previous hook.
+    ///
+    /// A replaced gradient is a constant, so it is not differentiable even within
+    /// `backward_create_graph()`.
+    ///
+    /// # Panics
+    /// - If the tensor does not require gradients
+    /// - On the backward pass, if the hook returns an array with a different shape than the gradient
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut a = Tensor::new([1.0, 2.0]);
+    /// a.set_requires_grad(true);
+    ///
+    /// let b = &a * 100.0;
+    ///
+    /// // rescale the gradient flowing through b
+    /// b.register_hook(|grad| Some(grad / 100.0));
+    ///
+    /// // d(b^2)/db = 2b = [200, 400]
+    /// (&b * &b).sum().backward();
+    /// assert_eq!(a.gradient().unwrap(), NdArray::new([200.0, 400.0]));
+    /// ```
+    pub fn register_hook(&self, hook: impl FnMut(&NdArray<T>) -> Option<NdArray<'static, T>> + Send + 'static) {
+        assert!(self.requires_grad(), "cannot register a hook on a tensor which does not require gradients");
+        self.grad_fn.hooks().register_hook(Box::new(hook));
+    }
+
+    /// Registers a hook on this leaf tensor which is called with its accumulated gradient
+    /// after each backward pass has accumulated into it.
+    ///
+    /// The hook may modify the accumulated gradient in place,
+    /// e.g. to average the gradients of replicas of a model.
+    ///
+    /// # Panics
+    /// - If the tensor is not a leaf which requires gradients
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut a = Tensor::new([1.0, 2.0]);
+    /// a.set_requires_grad(true);
+    ///
+    /// // average the gradient with a gradient from elsewhere
+    /// a.register_post_accumulate_grad_hook(|gradient| {
+    ///     *gradient += NdArray::new([3.0, 3.0]);
+    ///     *gradient /= 2.0;
+    /// });
+    ///
+    /// (&a * 5.0).sum().backward();
+    /// assert_eq!(a.gradient().unwrap(), NdArray::new([4.0, 4.0]));
+    /// ```
+    pub fn register_post_accumulate_grad_hook(&self, hook: impl FnMut(&mut NdArray<'static, T>) + Send + 'static) {
+        assert!(self.requires_grad() && self.is_leaf(),
+                "post-accumulate hooks can only be registered on leaf tensors which require gradients");
+
+        self.grad_fn.hooks().register_post_accumulate_grad_hook(Box::new(hook));
     }
 
     /// Computes the gradient of the `self` with respect to its leaf tensors.
@@ -153,7 +295,7 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
         let gradient = gradient.as_ref();
         assert_eq!(gradient.shape(), self.shape());
 
-        self.grad_fn.borrow_mut().backward(gradient);
+        run_backward(&self.grad_fn, constant(gradient.clone()), false);
     }
 
     /// Computes the gradient of the `self` with respect to its leaf tensors.
@@ -178,6 +320,36 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
         self.backward_with(NdArray::ones(self.shape()))
     }
 
+    /// Computes the gradient of the `self` with respect to its leaf tensors
+    /// and records the computation of these gradients in a graph.
+    ///
+    /// The accumulated gradients can be retrieved with `gradient_tensor()` and differentiated,
+    /// e.g. to compute second derivatives or gradient penalties.
+    ///
+    /// A leaf's gradient keeps the graph it was computed in alive (including the leaf itself)
+    /// until `zero_gradient()` is called on the leaf. Prefer `grad()` if the gradients
+    /// are not needed in the leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut a = Tensor::new([1.0, 2.0]);
+    /// a.set_requires_grad(true);
+    ///
+    /// let b = &a * &a;
+    /// b.backward_create_graph();
+    ///
+    /// // db/da = 2a
+    /// let gradient = a.gradient_tensor().unwrap();
+    /// assert_eq!(gradient, Tensor::new([2.0, 4.0]));
+    /// assert!(gradient.requires_grad());
+    /// ```
+    pub fn backward_create_graph(&self) {
+        run_backward(&self.grad_fn, constant(NdArray::ones(self.shape())), true);
+    }
+
     /// Detaches the tensor from the computation graph and returns an `NdArray`.
     ///
     /// # Examples
@@ -196,4 +368,30 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
     pub fn detach(&self) -> NdArray<'static, T> {
         self.array.as_ref().clone()
     }
+
+    /// Renders the graph which computes the gradients of this tensor's sources
+    /// in the Graphviz DOT language, e.g. for viewing with `dot -Tsvg`.
+    ///
+    /// Each node shows the gradient function's type (`MulBackwards`, `AccumulateGrad`, ...)
+    /// along with the shape and dtype of each tensor it keeps alive for the backward pass.
+    /// Sources which do not require gradients are drawn dashed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use redstone_ml::*;
+    ///
+    /// let mut a = Tensor::new([1.0f32, 2.0]);
+    /// a.set_requires_grad(true);
+    ///
+    /// let b = (&a * &a).sum();
+    /// let dot = b.graph_dot();
+    ///
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("MulBackwards\\nlhs: [2] f32\\nrhs: [2] f32"));
+    /// assert!(dot.contains("AccumulateGrad"));
+    /// ```
+    pub fn graph_dot(&self) -> String {
+        graph_dot(self)
+    }
 }
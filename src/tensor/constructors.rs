@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 use crate::gradient_function::GradientFunction;
 use crate::ndarray::flags::NdArrayFlags;
 use crate::ndarray::NdArray;
@@ -29,7 +29,7 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
         }
 
         Self {
-            array: Rc::new(array),
+            array: Arc::new(array),
             flags,
             grad_fn,
             
@@ -58,7 +58,7 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
         }
 
         Self {
-            array: Rc::new(array),
+            array: Arc::new(array),
             flags,
             grad_fn: NoneBackwards::new(),
 
@@ -66,3 +66,21 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
         }
     }
 }
+
+impl<T: TensorDataType> From<NdArray<'_, T>> for Tensor<'static, T> {
+    /// Creates a tensor which does not require gradients from `array`.
+    ///
+    /// The data is only copied if `array` is a view.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::from(NdArray::new([1.0, 2.0]));
+    ///
+    /// assert_eq!(tensor, Tensor::new([1.0, 2.0]));
+    /// assert!(!tensor.requires_grad());
+    /// ```
+    fn from(array: NdArray<'_, T>) -> Self {
+        unsafe { Tensor::from_array_and_flags(array.into_owned(), false, true) }
+    }
+}
@@ -0,0 +1,328 @@
+//! In-place initialization schemes for the parameters of a model.
+//!
+//! These follow the conventions of PyTorch's `torch.nn.init`. The fans of a tensor are computed
+//! from its shape: for a weight of shape `[out_features, in_features, *kernel]`, `fan_in` is
+//! `in_features * prod(kernel)` and `fan_out` is `out_features * prod(kernel)`.
+//!
+//! The tensor's data is overwritten without being recorded in the graph, so parameters which
+//! require gradients remain leaves. Random schemes draw from the default generator
+//! (see `manual_seed()`), and each has a `_with_` variant taking an explicit `Generator`.
+//!
+//! # Example
+//! ```
+//! # use redstone_ml::*;
+//! use redstone_ml::init;
+//!
+//! let mut weight = Tensor::<f32>::zeros([64, 32]);
+//! let mut bias = Tensor::<f32>::zeros([64]);
+//!
+//! init::kaiming_uniform_(&mut weight, init::FanMode::FanIn, init::Nonlinearity::Relu);
+//! init::constant_(&mut bias, 0.1);
+//!
+//! weight.set_requires_grad(true);
+//! ```
+
+use crate::generator::with_default_generator;
+use crate::{Constructors, Generator, NdArray, RandomConstructors, StridedMemory, Tensor, TensorDataType};
+use num::NumCast;
+
+
+/// Which fan to preserve the variance of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FanMode {
+    /// Preserves the magnitude of the activations in the forward pass.
+    FanIn,
+
+    /// Preserves the magnitude of the gradients in the backward pass.
+    FanOut,
+}
+
+/// The nonlinearity following a layer, which determines the recommended gain of its weights.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Nonlinearity<T> {
+    Linear,
+    Sigmoid,
+    Tanh,
+    Relu,
+
+    /// A leaky ReLU with the given negative slope.
+    LeakyRelu(T),
+    Selu,
+}
+
+/// Returns the recommended gain for the weights of a layer followed by `nonlinearity`.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// use redstone_ml::init::{calculate_gain, Nonlinearity};
+///
+/// assert_eq!(calculate_gain::<f64>(Nonlinearity::Relu), 2.0f64.sqrt());
+/// assert_eq!(calculate_gain::<f64>(Nonlinearity::Tanh), 5.0 / 3.0);
+/// ```
+pub fn calculate_gain<T: TensorDataType>(nonlinearity: Nonlinearity<T>) -> T {
+    let gain = match nonlinearity {
+        Nonlinearity::Linear | Nonlinearity::Sigmoid => 1.0,
+        Nonlinearity::Tanh => 5.0 / 3.0,
+        Nonlinearity::Relu => 2.0f64.sqrt(),
+        Nonlinearity::LeakyRelu(slope) => {
+            let slope = slope.to_f64().unwrap();
+            (2.0 / (1.0 + slope * slope)).sqrt()
+        }
+        Nonlinearity::Selu => 0.75,
+    };
+
+    cast(gain)
+}
+
+/// Returns the `(fan_in, fan_out)` of a weight of the given shape.
+///
+/// # Panics
+/// - If the shape has fewer than 2 dimensions
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// use redstone_ml::init::calculate_fans;
+///
+/// // a convolution with 16 output channels, 3 input channels and a 5x5 kernel
+/// assert_eq!(calculate_fans(&[16, 3, 5, 5]), (75, 400));
+/// ```
+pub fn calculate_fans(shape: &[usize]) -> (usize, usize) {
+    assert!(shape.len() >= 2, "fans cannot be computed for tensors with fewer than 2 dimensions");
+
+    let receptive_field_size: usize = shape[2..].iter().product();
+    (shape[1] * receptive_field_size, shape[0] * receptive_field_size)
+}
+
+/// Fills `tensor` with `value`.
+pub fn constant_<T: TensorDataType>(tensor: &mut Tensor<T>, value: T) {
+    tensor.ndarray_mut().fill(value);
+}
+
+/// Fills the matrix `tensor` with the identity matrix,
+/// or the leading diagonal of 1s if it is not square.
+///
+/// # Panics
+/// - If `tensor` is not a matrix
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut tensor = Tensor::<f64>::ones([2, 3]);
+/// init::eye_(&mut tensor);
+/// assert_eq!(tensor, Tensor::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]));
+/// ```
+pub fn eye_<T: TensorDataType>(tensor: &mut Tensor<T>) {
+    assert_eq!(tensor.ndims(), 2, "eye_ is only defined for matrices");
+
+    let (rows, cols) = (tensor.shape()[0], tensor.shape()[1]);
+    let data = (0..rows * cols)
+        .map(|i| if i / cols == i % cols { T::one() } else { T::zero() })
+        .collect();
+
+    overwrite(tensor, data);
+}
+
+/// Fills `tensor` with values drawn from a uniform distribution in [-a, a) where
+/// `a = gain * sqrt(6 / (fan_in + fan_out))`.
+///
+/// This is also known as Glorot initialization.
+pub fn xavier_uniform_<T: TensorDataType>(tensor: &mut Tensor<T>, gain: T) {
+    with_default_generator(|generator| xavier_uniform_with_(generator, tensor, gain))
+}
+
+/// Fills `tensor` using `xavier_uniform_()` with the random numbers of `generator`.
+pub fn xavier_uniform_with_<T: TensorDataType>(generator: &mut Generator, tensor: &mut Tensor<T>, gain: T) {
+    let (fan_in, fan_out) = calculate_fans(tensor.shape());
+    let bound = gain.to_f64().unwrap() * (6.0 / (fan_in + fan_out) as f64).sqrt();
+
+    uniform_with_(generator, tensor, bound);
+}
+
+/// Fills `tensor` with values drawn from a normal distribution with mean 0 and standard deviation
+/// `gain * sqrt(2 / (fan_in + fan_out))`.
+///
+/// This is also known as Glorot initialization.
+pub fn xavier_normal_<T: TensorDataType>(tensor: &mut Tensor<T>, gain: T) {
+    with_default_generator(|generator| xavier_normal_with_(generator, tensor, gain))
+}
+
+/// Fills `tensor` using `xavier_normal_()` with the random numbers of `generator`.
+pub fn xavier_normal_with_<T: TensorDataType>(generator: &mut Generator, tensor: &mut Tensor<T>, gain: T) {
+    let (fan_in, fan_out) = calculate_fans(tensor.shape());
+    let std = gain.to_f64().unwrap() * (2.0 / (fan_in + fan_out) as f64).sqrt();
+
+    normal_with_(generator, tensor, std);
+}
+
+/// Fills `tensor` with values drawn from a uniform distribution in [-a, a) where
+/// `a = gain * sqrt(3 / fan)`, the gain is that of `nonlinearity` and the fan is chosen by `mode`.
+///
+/// This is also known as He initialization.
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// use redstone_ml::init::{self, FanMode, Nonlinearity};
+///
+/// let mut weight = Tensor::<f64>::zeros([8, 6]);
+/// init::kaiming_uniform_(&mut weight, FanMode::FanIn, Nonlinearity::LeakyRelu(0.01));
+///
+/// let bound = init::calculate_gain(Nonlinearity::LeakyRelu(0.01)) * (3.0 / 6.0f64).sqrt();
+/// assert!(weight.ndarray().flatiter().all(|x| x.abs() <= bound));
+/// ```
+pub fn kaiming_uniform_<T: TensorDataType>(tensor: &mut Tensor<T>, mode: FanMode, nonlinearity: Nonlinearity<T>) {
+    with_default_generator(|generator| kaiming_uniform_with_(generator, tensor, mode, nonlinearity))
+}
+
+/// Fills `tensor` using `kaiming_uniform_()` with the random numbers of `generator`.
+pub fn kaiming_uniform_with_<T: TensorDataType>(generator: &mut Generator,
+                                                tensor: &mut Tensor<T>,
+                                                mode: FanMode,
+                                                nonlinearity: Nonlinearity<T>) {
+    let std = kaiming_std(tensor.shape(), mode, nonlinearity);
+    uniform_with_(generator, tensor, 3.0f64.sqrt() * std);
+}
+
+/// Fills `tensor` with values drawn from a normal distribution with mean 0 and standard deviation
+/// `gain / sqrt(fan)`, where the gain is that of `nonlinearity` and the fan is chosen by `mode`.
+///
+/// This is also known as He initialization.
+pub fn kaiming_normal_<T: TensorDataType>(tensor: &mut Tensor<T>, mode: FanMode, nonlinearity: Nonlinearity<T>) {
+    with_default_generator(|generator| kaiming_normal_with_(generator, tensor, mode, nonlinearity))
+}
+
+/// Fills `tensor` using `kaiming_normal_()` with the random numbers of `generator`.
+pub fn kaiming_normal_with_<T: TensorDataType>(generator: &mut Generator,
+                                               tensor: &mut Tensor<T>,
+                                               mode: FanMode,
+                                               nonlinearity: Nonlinearity<T>) {
+    let std = kaiming_std(tensor.shape(), mode, nonlinearity);
+    normal_with_(generator, tensor, std);
+}
+
+/// Fills `tensor` with values drawn from a normal distribution with the given `mean` and `std`,
+/// truncated to [`low`, `high`].
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut tensor = Tensor::<f32>::zeros([100]);
+/// init::trunc_normal_(&mut tensor, 0.0, 1.0, -2.0, 2.0);
+/// assert!(tensor.ndarray().flatiter().all(|x| x.abs() <= 2.0));
+/// ```
+pub fn trunc_normal_<T: TensorDataType>(tensor: &mut Tensor<T>, mean: T, std: T, low: T, high: T) {
+    with_default_generator(|generator| trunc_normal_with_(generator, tensor, mean, std, low, high))
+}
+
+/// Fills `tensor` using `trunc_normal_()` with the random numbers of `generator`.
+pub fn trunc_normal_with_<T: TensorDataType>(generator: &mut Generator,
+                                             tensor: &mut Tensor<T>,
+                                             mean: T, std: T, low: T, high: T) {
+    let values = NdArray::truncated_normal_with(generator, tensor.shape(), mean, std, low, high);
+    *tensor.ndarray_mut() = values;
+}
+
+/// Fills `tensor` with a (semi-)orthogonal matrix scaled by `gain`.
+///
+/// The tensor is treated as a matrix with `shape[0]` rows, with the remaining dimensions flattened.
+/// Its rows are orthonormal if there are fewer rows than columns, and its columns are orthonormal
+/// otherwise. The matrix is drawn uniformly from the orthogonal matrices of its shape.
+///
+/// # Panics
+/// - If `tensor` has fewer than 2 dimensions
+///
+/// # Example
+/// ```
+/// # use redstone_ml::*;
+/// let mut weight = Tensor::<f64>::zeros([3, 3]);
+/// init::orthogonal_(&mut weight, 1.0);
+///
+/// // W^T W = I
+/// let product = (&weight).T().matmul(&weight);
+/// for (i, x) in product.ndarray().flatiter().enumerate() {
+///     let expected = if i % 4 == 0 { 1.0 } else { 0.0 };
+///     assert!((x - expected).abs() < 1e-10);
+/// }
+/// ```
+pub fn orthogonal_<T: TensorDataType>(tensor: &mut Tensor<T>, gain: T) {
+    with_default_generator(|generator| orthogonal_with_(generator, tensor, gain))
+}
+
+/// Fills `tensor` using `orthogonal_()` with the random numbers of `generator`.
+pub fn orthogonal_with_<T: TensorDataType>(generator: &mut Generator, tensor: &mut Tensor<T>, gain: T) {
+    assert!(tensor.ndims() >= 2, "orthogonal_ is only defined for tensors with at least 2 dimensions");
+
+    let rows = tensor.shape()[0];
+    let cols = tensor.size() / rows;
+
+    // orthonormalize the columns of a tall random matrix, transposing wide matrices
+    let (m, n) = (rows.max(cols), rows.min(cols));
+    let mut q = NdArray::<f64>::randn_with(generator, [m, n]).into_data_vector();
+    orthonormalize_columns(&mut q, m, n);
+
+    let gain = gain.to_f64().unwrap();
+    let data = (0..rows * cols)
+        .map(|i| {
+            let (row, col) = (i / cols, i % cols);
+            let value = if rows >= cols { q[row * n + col] } else { q[col * n + row] };
+            cast(gain * value)
+        })
+        .collect();
+
+    overwrite(tensor, data);
+}
+
+/// Converts an `f64` to the dtype of a tensor.
+fn cast<T: TensorDataType>(value: f64) -> T {
+    <T as NumCast>::from(value).unwrap()
+}
+
+/// Replaces the data of `tensor` with the contiguous `data` of the same shape.
+fn overwrite<T: TensorDataType>(tensor: &mut Tensor<T>, data: Vec<T>) {
+    let shape = tensor.shape().to_vec();
+    *tensor.ndarray_mut() = unsafe { NdArray::from_contiguous_owned_buffer(shape, data) };
+}
+
+fn uniform_with_<T: TensorDataType>(generator: &mut Generator, tensor: &mut Tensor<T>, bound: f64) {
+    let values = NdArray::uniform_with(generator, tensor.shape(), cast(-bound), cast(bound));
+    *tensor.ndarray_mut() = values;
+}
+
+fn normal_with_<T: TensorDataType>(generator: &mut Generator, tensor: &mut Tensor<T>, std: f64) {
+    let values = NdArray::normal_with(generator, tensor.shape(), T::zero(), cast(std));
+    *tensor.ndarray_mut() = values;
+}
+
+fn kaiming_std<T: TensorDataType>(shape: &[usize], mode: FanMode, nonlinearity: Nonlinearity<T>) -> f64 {
+    let (fan_in, fan_out) = calculate_fans(shape);
+    let fan = match mode {
+        FanMode::FanIn => fan_in,
+        FanMode::FanOut => fan_out,
+    };
+
+    calculate_gain(nonlinearity).to_f64().unwrap() / (fan as f64).sqrt()
+}
+
+/// Orthonormalizes the columns of the row-major `m x n` matrix `a` in place (`m >= n`).
+///
+/// This uses modified Gram-Schmidt with a second pass for numerical stability,
+/// which gives the Q of a QR decomposition whose R has a positive diagonal.
+fn orthonormalize_columns(a: &mut [f64], m: usize, n: usize) {
+    for j in 0..n {
+        for _ in 0..2 {
+            for k in 0..j {
+                let projection: f64 = (0..m).map(|i| a[i * n + k] * a[i * n + j]).sum();
+                for i in 0..m {
+                    a[i * n + j] -= projection * a[i * n + k];
+                }
+            }
+        }
+
+        let norm = (0..m).map(|i| a[i * n + j] * a[i * n + j]).sum::<f64>().sqrt();
+        for i in 0..m {
+            a[i * n + j] /= norm;
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::grad_mode::is_grad_enabled;
 use crate::bmm_backwards::BMMBackwards;
 use crate::dot_backwards::DotBackwards;
 use crate::matrix_product_backwards::MatrixProductBackwards;
@@ -23,7 +24,7 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
     pub fn dot<'b, 'r>(&self, other: impl AsRef<Tensor<'b, T>>) -> Tensor<'r, T> {
         let other = other.as_ref();
 
-        let requires_grad = self.requires_grad() || other.requires_grad();
+        let requires_grad = is_grad_enabled() && (self.requires_grad() || other.requires_grad());
         let grad_fn = if requires_grad { DotBackwards::new(self, other) } else { NoneBackwards::new() };
 
         unsafe { Tensor::from_raw_parts(self.array.dot(&other.array), requires_grad, grad_fn) }
@@ -59,14 +60,14 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
     ///     [139.0, 154.0],
     /// ]));
     /// ```
-    pub fn matmul<'r>(&self, other: impl AsRef<Tensor<'a, T>>) -> Tensor<'r, T> {
+    pub fn matmul<'b, 'r>(&self, other: impl AsRef<Tensor<'b, T>>) -> Tensor<'r, T> {
         let other = other.as_ref();
 
         if self.ndims() == 1 && other.ndims() == 1 {
             return self.dot(other);
         }
 
-        let requires_grad = self.requires_grad() || other.requires_grad();
+        let requires_grad = is_grad_enabled() && (self.requires_grad() || other.requires_grad());
         let result = self.array.matmul(&other.array);
 
         let grad_fn = if requires_grad {
@@ -100,10 +101,10 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
     /// let result = arr1.bmm(&arr2);
     /// assert_eq!(result.shape(), [3, 2, 5]); // result is 3 batches of 2x5 matrices
     /// ```
-    pub fn bmm<'r>(&self, other: impl AsRef<Tensor<'a, T>>) -> Tensor<'r, T> {
+    pub fn bmm<'b, 'r>(&self, other: impl AsRef<Tensor<'b, T>>) -> Tensor<'r, T> {
         let other = other.as_ref();
         
-        let requires_grad = self.requires_grad() || other.requires_grad();
+        let requires_grad = is_grad_enabled() && (self.requires_grad() || other.requires_grad());
         let grad_fn = if requires_grad { BMMBackwards::new(self, other) } else { NoneBackwards::new() };
 
         unsafe { Tensor::from_raw_parts(self.array.bmm(&other.array), requires_grad, grad_fn) }
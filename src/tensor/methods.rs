@@ -1,5 +1,6 @@
-use std::rc::Rc;
+use std::sync::Arc;
 use crate::ndarray::flags::NdArrayFlags;
+use crate::none_backwards::NoneBackwards;
 use crate::common::methods::StridedMemory;
 use crate::{NdArray, Tensor, TensorDataType};
 
@@ -33,17 +34,53 @@ impl<'a, T: TensorDataType> Tensor<'a, T> {
     }
 
     /// Returns a reference-counted pointer to the underlying `NdArray` of the tensor
-    pub fn get_ndarray(&self) -> Rc<NdArray<'static, T>> {
+    pub fn get_ndarray(&self) -> Arc<NdArray<'static, T>> {
         self.array.clone()
     }
 
     /// Converts the tensor to an `NdArray`
     pub fn into_ndarray(self) -> NdArray<'static, T> {
-        match Rc::try_unwrap(self.array) {
+        match Arc::try_unwrap(self.array) {
             Ok(result) => { result }
-            Err(rc) => { (*rc).clone() }
+            Err(array) => { (*array).clone() }
         }
     }
+
+    /// Returns a tensor which shares both the data and the gradient function of `self`.
+    pub(crate) fn alias(&self) -> Tensor<'static, T> {
+        Tensor {
+            array: self.array.clone(),
+            flags: self.flags,
+            grad_fn: self.grad_fn.clone(),
+
+            _marker: Default::default(),
+        }
+    }
+
+    /// Returns a leaf tensor which shares the data of `self` but not its graph.
+    pub(crate) fn detached_alias(&self) -> Tensor<'static, T> {
+        Tensor {
+            array: self.array.clone(),
+            flags: (self.flags - NdArrayFlags::RequiresGrad) | NdArrayFlags::UserCreated,
+            grad_fn: NoneBackwards::new(),
+
+            _marker: Default::default(),
+        }
+    }
+
+    /// Returns a mutable reference to the underlying `NdArray` of the tensor.
+    ///
+    /// The data is copied first if it is shared with another tensor or not owned by this tensor.
+    pub(crate) fn ndarray_mut(&mut self) -> &mut NdArray<'static, T> {
+        let unique = Arc::get_mut(&mut self.array)
+            .is_some_and(|array| array.flags().contains(NdArrayFlags::Owned));
+
+        if !unique {
+            self.array = Arc::new(self.array.as_ref().clone());
+        }
+
+        Arc::get_mut(&mut self.array).unwrap()
+    }
 }
 
 #[allow(clippy::len_without_is_empty)]
@@ -1,7 +1,7 @@
 //! # Multidimensional Tensors with Dynamic Automatic Differentiation
 //!
 //! The `Tensor` API is nearly identical to `NdArray` with the following differences:
-//! 1. Only floating point (`f32`, `f64`) types are supported
+//! 1. Only floating point (`f32`, `f64`, `f16`, `bf16`) types are supported
 //! 2. Operations without autograd implemented are omitted
 //!
 //! `Tensors` allow us to perform dynamic automatic differentiation which is independent of
@@ -26,6 +26,27 @@
 //!
 //! You can also use `backwards_with(grad: NdArray<T>)` to find the gradient with a custom input.
 //!
+//! # Thread Safety
+//!
+//! Tensors and their computation graphs are `Send + Sync`, so models can be moved to worker threads
+//! and parameters can be shared between them. Gradients accumulated from several threads are summed.
+//! Whether gradients are tracked (see `NoGradGuard`) is a property of each thread.
+//!
+//! ```rust
+//! # use redstone_ml::*;
+//! let mut w = Tensor::new([1.0, 2.0]);
+//! w.set_requires_grad(true);
+//!
+//! std::thread::scope(|s| {
+//!     for _ in 0..4 {
+//!         s.spawn(|| (&w * &w).sum().backward());
+//!     }
+//! });
+//!
+//! // each thread contributes 2w
+//! assert_eq!(w.gradient().unwrap(), NdArray::new([8.0, 16.0]));
+//! ```
+//!
 
 pub mod methods;
 pub mod ops;
@@ -35,15 +56,19 @@ pub mod autograd;
 pub mod print;
 pub mod matrix_ops;
 pub mod reshape;
+pub mod statistics;
+pub mod reduce;
+pub mod unary_ops;
+pub mod init;
 
 use std::marker::PhantomData;
-use std::rc::Rc;
+use std::sync::Arc;
 use crate::gradient_function::GradientFunction;
 use crate::ndarray::flags::NdArrayFlags;
 use crate::{NdArray, TensorDataType};
 
 pub struct Tensor<'a, T: TensorDataType> {
-    array: Rc<NdArray<'static, T>>,
+    array: Arc<NdArray<'static, T>>,
 
     pub(super) flags: NdArrayFlags,
     pub(super) grad_fn: GradientFunction<T>,
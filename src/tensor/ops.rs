@@ -1,3 +1,4 @@
+use crate::grad_mode::is_grad_enabled;
 use crate::{Tensor, TensorDataType};
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
@@ -19,7 +20,7 @@ impl<T: TensorDataType> Neg for &Tensor<'_, T> {
     type Output = Tensor<'static, T>;
 
     fn neg(self) -> Self::Output {
-        let requires_grad = self.requires_grad();
+        let requires_grad = is_grad_enabled() && self.requires_grad();
         let grad_fn = if requires_grad { NegBackwards::new(self) } else { NoneBackwards::new() };
 
         unsafe { Tensor::from_raw_parts(-self.array.as_ref(), requires_grad, grad_fn) }
@@ -50,7 +51,7 @@ macro_rules! implement_binary_ops {
             type Output = Tensor<'static, T>;
 
             fn $method(self, rhs: &Tensor<T>) -> Self::Output {
-                let requires_grad = self.requires_grad() || rhs.requires_grad();
+                let requires_grad = is_grad_enabled() && (self.requires_grad() || rhs.requires_grad());
                 let grad_fn = if requires_grad { $backwards::new(self, rhs) } else { NoneBackwards::new() };
 
                 unsafe { Tensor::from_raw_parts(self.array.as_ref() $operator rhs.array.as_ref(), requires_grad, grad_fn) }
@@ -67,7 +68,7 @@ macro_rules! implement_binary_ops {
             type Output = Tensor<'static, T>;
 
             fn $method(self, rhs: T) -> Self::Output {
-                let requires_grad = self.requires_grad();
+                let requires_grad = is_grad_enabled() && self.requires_grad();
                 let grad_fn = if requires_grad { $backwards_scalar::new(self, rhs) } else { NoneBackwards::new() };
 
                 unsafe { Tensor::from_raw_parts(self.array.as_ref() $operator rhs, requires_grad, grad_fn) }
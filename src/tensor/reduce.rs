@@ -0,0 +1,55 @@
+use crate::grad_mode::is_grad_enabled;
+use crate::ndarray::reduce::keepdims_shape;
+use crate::none_backwards::NoneBackwards;
+use crate::sum_backwards::SumBackwards;
+use crate::util::to_vec::ToVec;
+use crate::{Reshape, StridedMemory, Tensor, TensorDataType};
+
+impl<T: TensorDataType> Tensor<'_, T> {
+    /// Computes the sum of all elements in the tensor.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[1.0, 2.0], [3.0, 4.0]]);
+    /// assert_eq!(tensor.sum().value(), 10.0);
+    /// ```
+    pub fn sum(&self) -> Tensor<'static, T> {
+        self.sum_along(self.all_axes())
+    }
+
+    /// Computes the sum along the specified axes.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[1.0, 2.0], [3.0, 4.0]]);
+    /// assert_eq!(tensor.sum_along(0), Tensor::new([4.0, 6.0]));
+    /// ```
+    pub fn sum_along(&self, axes: impl ToVec<isize>) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        let array = self.ndarray().sum_along(axes.clone());
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad {
+            SumBackwards::new(self, self.absolute_axes(&axes))
+        } else {
+            NoneBackwards::new()
+        };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+
+    /// Computes the sum along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[1.0, 2.0], [3.0, 4.0]]);
+    /// assert_eq!(tensor.sum_keepdims(1), Tensor::new([[3.0], [7.0]]));
+    /// ```
+    pub fn sum_keepdims(&self, axes: impl ToVec<isize>) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        self.sum_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+}
@@ -1,8 +1,11 @@
+use crate::grad_mode::is_grad_enabled;
+use std::sync::Arc;
 use crate::none_backwards::NoneBackwards;
 use crate::reshape_backwards::ReshapeBackwards;
 use crate::transpose_backwards::TransposeBackwards;
 use crate::{AxisType, Reshape, StridedMemory, Tensor, TensorDataType};
 use crate::identity_backwards::IdentityBackwards;
+use crate::expand_backwards::ExpandBackwards;
 
 
 impl<'a, T: TensorDataType> Reshape<T> for &'a Tensor<'a, T> {
@@ -15,7 +18,7 @@ impl<'a, T: TensorDataType> Reshape<T> for &'a Tensor<'a, T> {
     /// - Ensure the memory layout referenced by `shape`, and `stride` is valid and owned
     ///   by the original tensor.
     unsafe fn reshaped_view(self, shape: Vec<usize>, stride: Vec<usize>) -> Self::Output {
-        let requires_grad = self.requires_grad();
+        let requires_grad = is_grad_enabled() && self.requires_grad();
         let grad_fn = if requires_grad { ReshapeBackwards::new(self, self.shape()) } else { NoneBackwards::new() };
 
         let result = self.array.as_ref().reshaped_view(shape, stride);
@@ -38,7 +41,7 @@ impl<'a, T: TensorDataType> Reshape<T> for &'a Tensor<'a, T> {
     /// assert!(view.is_view())
     /// ```
     fn view(self) -> Self::Output {
-        let requires_grad = self.requires_grad();
+        let requires_grad = is_grad_enabled() && self.requires_grad();
         let grad_fn = if requires_grad { IdentityBackwards::new(self) } else { NoneBackwards::new() };
 
         let result = self.array.as_ref().view();
@@ -67,7 +70,7 @@ impl<'a, T: TensorDataType> Reshape<T> for &'a Tensor<'a, T> {
     /// assert_eq!(transposed, Tensor::new([[2.0, 10.0], [3.0, 20.0], [4.0, 30.0]]));
     /// ```
     fn transpose(self, axis1: impl AxisType, axis2: impl AxisType) -> Self::Output {
-        let requires_grad = self.requires_grad();
+        let requires_grad = is_grad_enabled() && self.requires_grad();
         let grad_fn =
             if requires_grad {
                 TransposeBackwards::new(self, axis1.isize(), axis2.isize())
@@ -97,10 +100,16 @@ impl<T: TensorDataType> Reshape<T> for Tensor<'_, T> {
     /// - Ensure the memory layout referenced by `shape`, and `stride` is valid and owned
     ///   by the original tensor.
     unsafe fn reshaped_view(self, shape: Vec<usize>, stride: Vec<usize>) -> Self::Output {
-        let requires_grad = self.requires_grad();
+        let requires_grad = is_grad_enabled() && self.requires_grad();
         let grad_fn = if requires_grad { ReshapeBackwards::new(&self, self.shape()) } else { NoneBackwards::new() };
 
-        let result = self.into_ndarray().reshaped_view(shape, stride);
+        // `shape` and `stride` describe the memory layout of `self`, so if the data is shared
+        // we take the view first and copy it afterwards rather than the other way around
+        let result = match Arc::try_unwrap(self.array) {
+            Ok(array) => array.reshaped_view(shape, stride),
+            Err(array) => array.as_ref().reshaped_view(shape, stride).clone(),
+        };
+
         Tensor::from_raw_parts(result, requires_grad, grad_fn)
     }
 
@@ -115,7 +124,7 @@ impl<T: TensorDataType> Reshape<T> for Tensor<'_, T> {
     /// assert!(view.is_view())
     /// ```
     fn view(self) -> Self::Output {
-        let requires_grad = self.requires_grad();
+        let requires_grad = is_grad_enabled() && self.requires_grad();
         let grad_fn = if requires_grad { IdentityBackwards::new(&self) } else { NoneBackwards::new() };
 
         let result = self.into_ndarray().view();
@@ -137,7 +146,7 @@ impl<T: TensorDataType> Reshape<T> for Tensor<'_, T> {
     /// assert_eq!(transposed, Tensor::new([[2.0, 10.0], [3.0, 20.0], [4.0, 30.0]]));
     /// ```
     fn transpose(self, axis1: impl AxisType, axis2: impl AxisType) -> Self::Output {
-        let requires_grad = self.requires_grad();
+        let requires_grad = is_grad_enabled() && self.requires_grad();
         let grad_fn =
             if requires_grad {
                 TransposeBackwards::new(&self, axis1.isize(), axis2.isize())
@@ -149,3 +158,17 @@ impl<T: TensorDataType> Reshape<T> for Tensor<'_, T> {
         unsafe { Tensor::from_raw_parts(result, requires_grad, grad_fn) }
     }
 }
+
+impl<T: TensorDataType> Tensor<'_, T> {
+    /// Returns a copy of the tensor broadcast to `shape`.
+    ///
+    /// Unlike `NdArray::broadcast_to`, the result owns its data so it can outlive `self`.
+    pub(crate) fn expand(&self, shape: &[usize]) -> Tensor<'static, T> {
+        let array = self.ndarray().broadcast_to(shape).clone();
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad { ExpandBackwards::new(self) } else { NoneBackwards::new() };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+}
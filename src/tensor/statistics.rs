@@ -0,0 +1,216 @@
+use crate::grad_mode::is_grad_enabled;
+use crate::cumsum_backwards::CumsumBackwards;
+use crate::ndarray::reduce::keepdims_shape;
+use crate::logsumexp_backwards::LogSumExpBackwards;
+use crate::none_backwards::NoneBackwards;
+use crate::norm_backwards::NormBackwards;
+use crate::util::to_vec::ToVec;
+use crate::var_backwards::{StdBackwards, VarBackwards};
+use crate::{AxisType, Norm, Reshape, StridedMemory, Tensor, TensorDataType};
+
+impl<T: TensorDataType> Tensor<'_, T> {
+    /// Computes the variance of all elements in the tensor.
+    ///
+    /// The divisor used is `N - ddof` where `N` is the number of elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(tensor.var(0).value(), 1.25);
+    /// ```
+    pub fn var(&self, ddof: usize) -> Tensor<'static, T> {
+        self.var_along(self.all_axes(), ddof)
+    }
+
+    /// Computes the variance along the specified axes.
+    ///
+    /// The divisor used is `N - ddof` where `N` is the number of elements reduced into each output.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[1.0, 3.0], [2.0, 6.0]]);
+    /// assert_eq!(tensor.var_along(0, 0), Tensor::new([0.25, 2.25]));
+    /// ```
+    pub fn var_along(&self, axes: impl ToVec<isize>, ddof: usize) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        let array = self.ndarray().var_along(axes.clone(), ddof);
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad {
+            VarBackwards::new(self, self.absolute_axes(&axes), ddof)
+        } else {
+            NoneBackwards::new()
+        };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+
+    /// Computes the variance along the specified axes, retaining the reduced axes with length 1.
+    pub fn var_keepdims(&self, axes: impl ToVec<isize>, ddof: usize) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        self.var_along(axes.clone(), ddof).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the standard deviation of all elements in the tensor.
+    ///
+    /// The divisor used is `N - ddof` where `N` is the number of elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    /// assert_eq!(tensor.std(0).value(), 2.0);
+    /// ```
+    pub fn std(&self, ddof: usize) -> Tensor<'static, T> {
+        self.std_along(self.all_axes(), ddof)
+    }
+
+    /// Computes the standard deviation along the specified axes.
+    ///
+    /// The divisor used is `N - ddof` where `N` is the number of elements reduced into each output.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[1.0, 3.0], [3.0, 7.0]]);
+    /// assert_eq!(tensor.std_along(0, 0), Tensor::new([1.0, 2.0]));
+    /// ```
+    pub fn std_along(&self, axes: impl ToVec<isize>, ddof: usize) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        let array = self.ndarray().std_along(axes.clone(), ddof);
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad {
+            StdBackwards::new(self, self.absolute_axes(&axes), ddof)
+        } else {
+            NoneBackwards::new()
+        };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+
+    /// Computes the standard deviation along the specified axes,
+    /// retaining the reduced axes with length 1.
+    pub fn std_keepdims(&self, axes: impl ToVec<isize>, ddof: usize) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        self.std_along(axes.clone(), ddof).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the cumulative sum of elements along the specified axis.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// assert_eq!(tensor.cumsum(1), Tensor::new([[1.0, 3.0, 6.0], [4.0, 9.0, 15.0]]));
+    /// ```
+    pub fn cumsum(&self, axis: impl AxisType) -> Tensor<'static, T> {
+        let axis = axis.as_absolute(self.ndims());
+        let array = self.ndarray().cumsum(axis as isize);
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad { CumsumBackwards::new(self, axis) } else { NoneBackwards::new() };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+
+    /// Computes `log(sum(exp(x)))` over all elements in a numerically stable way.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([0.0, 0.0]);
+    /// assert_eq!(tensor.logsumexp().value(), 2.0f64.ln());
+    /// ```
+    pub fn logsumexp(&self) -> Tensor<'static, T> {
+        self.logsumexp_along(self.all_axes())
+    }
+
+    /// Computes `log(sum(exp(x)))` along the specified axes in a numerically stable way.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[0.0, 0.0], [1.0, f64::NEG_INFINITY]]);
+    /// assert_eq!(tensor.logsumexp_along(1), Tensor::new([2.0f64.ln(), 1.0]));
+    /// ```
+    pub fn logsumexp_along(&self, axes: impl ToVec<isize>) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        let array = self.ndarray().logsumexp_along(axes.clone());
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad {
+            LogSumExpBackwards::new(self, self.absolute_axes(&axes))
+        } else {
+            NoneBackwards::new()
+        };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+
+    /// Computes `log(sum(exp(x)))` along the specified axes, retaining the reduced axes with length 1.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[0.0, 0.0], [1.0, 1.0]]);
+    /// assert_eq!(tensor.logsumexp_keepdims(-1).shape(), &[2, 1]);
+    /// ```
+    pub fn logsumexp_keepdims(&self, axes: impl ToVec<isize>) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        self.logsumexp_along(axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    /// Computes the vector norm of all elements in the tensor.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([3.0, -4.0]);
+    /// assert_eq!(tensor.norm(Norm::L2).value(), 5.0);
+    /// ```
+    pub fn norm(&self, ord: Norm<T>) -> Tensor<'static, T> {
+        self.norm_along(ord, self.all_axes())
+    }
+
+    /// Computes the vector norm along the specified axes.
+    ///
+    /// Where the norm is 0, its gradient is taken to be 0.
+    /// The gradient of the infinity norm is split evenly between all maximal elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use redstone_ml::*;
+    /// let tensor = Tensor::new([[3.0, -4.0], [1.0, 1.0]]);
+    /// assert_eq!(tensor.norm_along(Norm::L1, -1), Tensor::new([7.0, 2.0]));
+    /// ```
+    pub fn norm_along(&self, ord: Norm<T>, axes: impl ToVec<isize>) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        let array = self.ndarray().norm_along(ord, axes.clone());
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad {
+            NormBackwards::new(self, ord, self.absolute_axes(&axes))
+        } else {
+            NoneBackwards::new()
+        };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+
+    /// Computes the vector norm along the specified axes, retaining the reduced axes with length 1.
+    pub fn norm_keepdims(&self, ord: Norm<T>, axes: impl ToVec<isize>) -> Tensor<'static, T> {
+        let axes = axes.to_vec();
+        self.norm_along(ord, axes.clone()).reshape(keepdims_shape(&axes, self.shape()))
+    }
+
+    pub(super) fn absolute_axes(&self, axes: &[isize]) -> Vec<usize> {
+        axes.iter().map(|axis| axis.as_absolute(self.ndims())).collect()
+    }
+
+    pub(super) fn all_axes(&self) -> Vec<isize> {
+        (0..self.ndims() as isize).collect()
+    }
+}
@@ -0,0 +1,27 @@
+use crate::exp_backwards::ExpBackwards;
+use crate::grad_mode::is_grad_enabled;
+use crate::none_backwards::NoneBackwards;
+use crate::powf_backwards::PowfBackwards;
+use crate::{Tensor, TensorDataType};
+
+impl<T: TensorDataType> Tensor<'_, T> {
+    /// Computes `e^x` for each element `x` of the tensor.
+    pub(crate) fn exp(&self) -> Tensor<'static, T> {
+        let array = self.ndarray().map(|x| x.exp());
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad { ExpBackwards::new(self) } else { NoneBackwards::new() };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+
+    /// Raises each element of the tensor to the power of `exponent`.
+    pub(crate) fn powf(&self, exponent: T) -> Tensor<'static, T> {
+        let array = self.ndarray().map(|x| x.powf(exponent));
+
+        let requires_grad = is_grad_enabled() && self.requires_grad();
+        let grad_fn = if requires_grad { PowfBackwards::new(self, exponent) } else { NoneBackwards::new() };
+
+        unsafe { Tensor::from_raw_parts(array, requires_grad, grad_fn) }
+    }
+}
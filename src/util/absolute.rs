@@ -58,3 +58,15 @@ impl Absolute for f64 {
         num::Signed::abs(self)
     }
 }
+
+impl Absolute for half::f16 {
+    fn abs(&self) -> Self {
+        num::Float::abs(*self)
+    }
+}
+
+impl Absolute for half::bf16 {
+    fn abs(&self) -> Self {
+        num::Float::abs(*self)
+    }
+}
@@ -5,52 +5,153 @@ use crate::ops::binary_op_mul::BinaryOpMul;
 use crate::ops::binary_op_sub::BinaryOpSub;
 use crate::ops::dot_product::DotProduct;
 use crate::ops::fill::Fill;
+use crate::ops::mul_add::MulAdd;
+use crate::ops::reduce_argmax::ReduceArgMax;
+use crate::ops::reduce_argmin::ReduceArgMin;
 use crate::ops::reduce_max::ReduceMax;
 use crate::ops::reduce_max_magnitude::ReduceMaxMagnitude;
 use crate::ops::reduce_min::ReduceMin;
 use crate::ops::reduce_min_magnitude::ReduceMinMagnitude;
 use crate::ops::reduce_product::ReduceProduct;
 use crate::ops::reduce_sum::ReduceSum;
+use crate::ops::sort::SortOps;
 use crate::ops::unary_ops::UnaryOps;
 use crate::sum_of_products::SumOfProductsType;
-use num::traits::MulAdd;
 use num::{Float, NumCast, ToPrimitive};
 use rand::distributions::uniform::SampleUniform;
+use half::{bf16, f16};
+use num::complex::{Complex32, Complex64};
 use std::fmt::{Debug, Display};
 use std::iter::{Product, Sum};
 use std::ops::{Div, Neg, Sub, SubAssign};
 
 pub trait RawDataType: 'static + Default + Copy + Clone + Debug + Display + Sized
-+ PartialEq + Fill + Send + Sync {}
++ PartialEq + Fill + Send + Sync {
+    /// The runtime identifier of this datatype.
+    const DTYPE: DType;
+}
+
+/// The runtime identifier of a `RawDataType`,
+/// used to describe the contents of serialized or foreign buffers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DType {
+    U8, U16, U32, U64, U128, Usize,
+    I8, I16, I32, I64, I128, Isize,
+    F32, F64, F16, BF16,
+    Bool,
+    Complex32, Complex64,
+}
+
+impl DType {
+    /// Returns the name of the Rust type with this datatype, e.g. `"f32"` or `"bf16"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DType::U8 => "u8",
+            DType::U16 => "u16",
+            DType::U32 => "u32",
+            DType::U64 => "u64",
+            DType::U128 => "u128",
+            DType::Usize => "usize",
+            DType::I8 => "i8",
+            DType::I16 => "i16",
+            DType::I32 => "i32",
+            DType::I64 => "i64",
+            DType::I128 => "i128",
+            DType::Isize => "isize",
+            DType::F32 => "f32",
+            DType::F64 => "f64",
+            DType::F16 => "f16",
+            DType::BF16 => "bf16",
+            DType::Bool => "bool",
+            DType::Complex32 => "complex32",
+            DType::Complex64 => "complex64",
+        }
+    }
+
+    /// Returns the size of an element of this datatype in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            DType::U8 | DType::I8 | DType::Bool => 1,
+            DType::U16 | DType::I16 | DType::F16 | DType::BF16 => 2,
+            DType::U32 | DType::I32 | DType::F32 => 4,
+            DType::U64 | DType::I64 | DType::F64 | DType::Complex32 => 8,
+            DType::U128 | DType::I128 | DType::Complex64 => 16,
+            DType::Usize | DType::Isize => size_of::<usize>(),
+        }
+    }
+}
+
+macro_rules! impl_raw_dtype {
+    ($($dtype:ty => $variant:ident),*) => {
+        $(
+            impl RawDataType for $dtype {
+                const DTYPE: DType = DType::$variant;
+            }
+        )*
+    };
+}
+
+impl_raw_dtype!(u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128, usize => Usize);
+impl_raw_dtype!(i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128, isize => Isize);
+impl_raw_dtype!(f32 => F32, f64 => F64, f16 => F16, bf16 => BF16);
+impl_raw_dtype!(bool => Bool, Complex32 => Complex32, Complex64 => Complex64);
+
+/// Conversion from a `bool` where `false` maps to zero and `true` maps to one.
+///
+/// This mirrors `From<bool>` but is also implemented for `f16` and `bf16`,
+/// which do not implement `From<bool>`.
+pub trait FromBool {
+    fn from_bool(value: bool) -> Self;
+}
+
+macro_rules! impl_from_bool {
+    ($($dtype:ty),*) => {
+        $(
+            impl FromBool for $dtype {
+                fn from_bool(value: bool) -> Self {
+                    value.into()
+                }
+            }
+        )*
+    };
+}
 
-impl RawDataType for u8 {}
-impl RawDataType for u16 {}
-impl RawDataType for u32 {}
-impl RawDataType for u64 {}
-impl RawDataType for u128 {}
-impl RawDataType for usize {}
+impl_from_bool!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool);
 
-impl RawDataType for i8 {}
-impl RawDataType for i16 {}
-impl RawDataType for i32 {}
-impl RawDataType for i64 {}
-impl RawDataType for i128 {}
-impl RawDataType for isize {}
+impl FromBool for f16 {
+    fn from_bool(value: bool) -> Self {
+        if value { f16::ONE } else { f16::ZERO }
+    }
+}
 
-impl RawDataType for f32 {}
-impl RawDataType for f64 {}
+impl FromBool for bf16 {
+    fn from_bool(value: bool) -> Self {
+        if value { bf16::ONE } else { bf16::ZERO }
+    }
+}
+
+impl FromBool for Complex32 {
+    fn from_bool(value: bool) -> Self {
+        Complex32::new(value.into(), 0.0)
+    }
+}
 
-impl RawDataType for bool {}
+impl FromBool for Complex64 {
+    fn from_bool(value: bool) -> Self {
+        Complex64::new(value.into(), 0.0)
+    }
+}
 
-pub trait NumericDataType: RawDataType + ToPrimitive + NumCast + From<bool>
-+ Sum + Product + SubAssign + Sub<Output=Self> + Div<Output=Self> + MulAdd<Output=Self> + DotProduct
+pub trait NumericDataType: RawDataType + ToPrimitive + NumCast + FromBool
++ Sum + Product + SubAssign + Sub<Output=Self> + Div<Output=Self> + MulAdd + DotProduct
 + ReduceSum + ReduceProduct + ReduceMin + ReduceMax + ReduceMinMagnitude + ReduceMaxMagnitude
++ ReduceArgMin + ReduceArgMax + SortOps
 + BinaryOpAdd + BinaryOpSub + BinaryOpMul
 {
     type AsFloatType: FloatDataType;
 
     fn to_float(&self) -> Self::AsFloatType {
-        self.to_f32().unwrap().into()
+        <Self::AsFloatType as NumCast>::from(*self).unwrap()
     }
 
     fn ceil(&self) -> Self {
@@ -134,6 +235,30 @@ impl NumericDataType for f64 {
     }
 }
 
+impl NumericDataType for f16 {
+    type AsFloatType = f16;
+
+    fn ceil(&self) -> Self {
+        num::Float::ceil(*self)
+    }
+
+    fn floor(&self) -> Self {
+        num::Float::floor(*self)
+    }
+}
+
+impl NumericDataType for bf16 {
+    type AsFloatType = bf16;
+
+    fn ceil(&self) -> Self {
+        num::Float::ceil(*self)
+    }
+
+    fn floor(&self) -> Self {
+        num::Float::floor(*self)
+    }
+}
+
 pub trait IntegerDataType: NumericDataType + Ord {}
 
 impl IntegerDataType for u8 {}
@@ -150,11 +275,67 @@ impl IntegerDataType for i64 {}
 impl IntegerDataType for i128 {}
 impl IntegerDataType for isize {}
 
-pub trait FloatDataType: NumericDataType + Float + From<f32> + SampleUniform + Neg<Output=Self>
+pub trait FloatDataType: NumericDataType + Float + SampleUniform + Neg<Output=Self>
 + SumOfProductsType + MatrixOps + BinaryOpDiv + UnaryOps {}
 
 impl FloatDataType for f32 {}
 impl FloatDataType for f64 {}
+impl FloatDataType for f16 {}
+impl FloatDataType for bf16 {}
+
+pub trait ComplexDataType: RawDataType + FromBool + SumOfProductsType + MatrixOps
++ BinaryOpAdd + BinaryOpSub + BinaryOpMul + BinaryOpDiv + UnaryOps
+{
+    type RealType: FloatDataType;
+
+    fn new(re: Self::RealType, im: Self::RealType) -> Self;
+
+    fn re(&self) -> Self::RealType;
+
+    fn im(&self) -> Self::RealType;
+
+    fn conj(&self) -> Self;
+
+    fn abs(&self) -> Self::RealType;
+
+    fn angle(&self) -> Self::RealType;
+}
+
+macro_rules! impl_complex_dtype {
+    ($($dtype:ty, $real:ty);*) => {
+        $(
+            impl ComplexDataType for $dtype {
+                type RealType = $real;
+
+                fn new(re: $real, im: $real) -> Self {
+                    <$dtype>::new(re, im)
+                }
+
+                fn re(&self) -> $real {
+                    self.re
+                }
+
+                fn im(&self) -> $real {
+                    self.im
+                }
+
+                fn conj(&self) -> Self {
+                    <$dtype>::conj(self)
+                }
+
+                fn abs(&self) -> $real {
+                    self.norm()
+                }
+
+                fn angle(&self) -> $real {
+                    self.arg()
+                }
+            }
+        )*
+    };
+}
+
+impl_complex_dtype!(Complex32, f32; Complex64, f64);
 
 
 pub trait TensorDataType: FloatDataType {}
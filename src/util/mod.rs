@@ -17,3 +17,6 @@ pub use index::*;
 
 pub mod axis;
 pub use axis::*;
+
+pub mod norm;
+pub use norm::*;
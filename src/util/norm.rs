@@ -0,0 +1,15 @@
+/// The order of a vector norm computed by `NdArray::norm()` and `Tensor::norm()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Norm<T> {
+    /// The sum of absolute values.
+    L1,
+
+    /// The Euclidean norm, i.e. the square root of the sum of squares.
+    L2,
+
+    /// The p-norm, i.e. `sum(|x|^p)^(1/p)`.
+    Lp(T),
+
+    /// The maximum absolute value.
+    Inf,
+}
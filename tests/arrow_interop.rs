@@ -0,0 +1,212 @@
+#![cfg(feature = "arrow")]
+
+use arrow_array::types::{Float32Type, Int32Type};
+use arrow_array::{Array, FixedSizeListArray, Float64Array, Int32Array, RecordBatch, StringArray, UInt8Array};
+use arrow_schema::{DataType, Field};
+use redstone_ml::*;
+use std::sync::Arc;
+
+#[test]
+fn test_from_arrow_primitive() {
+    let column = Float64Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+
+    let array = NdArray::<f64>::from_arrow(&column).unwrap();
+    assert!(array.is_view());
+    assert_eq!(array.shape(), &[5]);
+    assert_eq!(array.sum(), NdArray::scalar(15.0));
+
+    // the array shares the memory of the column
+    assert_eq!(array.data_slice().as_ptr(), column.values().as_ptr());
+
+    // sliced columns start at their offset
+    let sliced = column.slice(2, 2);
+    assert_eq!(NdArray::<f64>::from_arrow(&sliced).unwrap(), NdArray::new([3.0, 4.0]));
+}
+
+#[test]
+fn test_from_arrow_fixed_size_list() {
+    let column = FixedSizeListArray::from_iter_primitive::<Int32Type, _, _>(
+        (0..4).map(|row| Some((0..3).map(move |col| Some(row * 3 + col)))),
+        3,
+    );
+
+    let array = NdArray::<i32>::from_arrow(&column).unwrap();
+    assert_eq!(array, NdArray::<i32>::arange(0, 12).reshape([4, 3]));
+    drop(array);
+
+    // nested lists become further dimensions
+    let field = Arc::new(Field::new_list_field(column.data_type().clone(), true));
+    let nested = FixedSizeListArray::new(field, 2, Arc::new(column), None);
+    let array = NdArray::<i32>::from_arrow(&nested).unwrap();
+    assert_eq!(array, NdArray::<i32>::arange(0, 12).reshape([2, 2, 3]));
+
+    let sliced = nested.slice(1, 1);
+    assert_eq!(NdArray::<i32>::from_arrow(&sliced).unwrap(), NdArray::<i32>::arange(6, 12).reshape([1, 2, 3]));
+}
+
+#[test]
+fn test_from_arrow_record_batch() {
+    let batch = RecordBatch::try_from_iter([
+        ("id", Arc::new(UInt8Array::from(vec![7, 8, 9])) as _),
+        ("score", Arc::new(Float64Array::from(vec![0.5, 0.25, 1.0])) as _),
+    ]).unwrap();
+
+    let ids = NdArray::<u8>::from_arrow(batch.column(0).as_ref()).unwrap();
+    let scores = NdArray::<f64>::from_arrow(batch.column(1).as_ref()).unwrap();
+    assert_eq!(ids, NdArray::new([7, 8, 9]));
+    assert_eq!(scores.max(), NdArray::scalar(1.0));
+
+    let result = NdArray::<f32>::from_arrow(batch.column(1).as_ref());
+    assert_eq!(result.unwrap_err(), ConversionError::DtypeMismatch { expected: DType::F32, found: "Float64".into() });
+
+    let strings = StringArray::from(vec!["a", "b"]);
+    assert!(matches!(NdArray::<u8>::from_arrow(&strings), Err(ConversionError::DtypeMismatch { .. })));
+}
+
+#[test]
+fn test_from_arrow_with_mask() {
+    let column = Int32Array::from(vec![Some(1), None, Some(3), None]);
+    assert_eq!(NdArray::<i32>::from_arrow(&column).unwrap_err(), ConversionError::NullValues);
+
+    let (array, mask) = NdArray::<i32>::from_arrow_with_mask(&column).unwrap();
+    assert_eq!(array.shape(), &[4]);
+    assert_eq!(mask, NdArray::new([false, true, false, true]));
+
+    // nulls of a list cover the whole row
+    let column = FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>([
+        Some(vec![Some(1.0), None]),
+        None,
+        Some(vec![Some(5.0), Some(6.0)]),
+    ], 2);
+
+    let (array, mask) = NdArray::<f32>::from_arrow_with_mask(&column).unwrap();
+    assert_eq!(mask, NdArray::new([[false, true], [true, true], [false, false]]));
+    assert_eq!(array.slice_along(Axis(0), 2), NdArray::new([5.0, 6.0]));
+
+    // columns without nulls have an empty mask
+    let column = Int32Array::from(vec![1, 2]);
+    let (_, mask) = NdArray::<i32>::from_arrow_with_mask(&column).unwrap();
+    assert_eq!(mask, NdArray::new([false, false]));
+}
+
+#[test]
+fn test_into_arrow() {
+    let column = NdArray::new([1.0f64, 2.0, 3.0]).into_arrow().unwrap();
+    let column = column.as_any().downcast_ref::<Float64Array>().unwrap();
+    assert_eq!(column.values().as_ref(), &[1.0, 2.0, 3.0]);
+    assert_eq!(column.null_count(), 0);
+
+    // non-contiguous arrays are copied in row-major order
+    let array = NdArray::<i32>::arange(0, 6).reshape([2, 3]).T();
+    let column = array.into_arrow().unwrap();
+    assert_eq!(column.data_type(), &DataType::FixedSizeList(Arc::new(Field::new_list_field(DataType::Int32, true)), 2));
+    assert_eq!(NdArray::<i32>::from_arrow(column.as_ref()).unwrap(), NdArray::new([[0, 3], [1, 4], [2, 5]]));
+
+    let mask = NdArray::new([[false, true], [false, false]]);
+    let column = NdArray::new([[1u8, 2], [3, 4]]).into_arrow_with_mask(&mask).unwrap();
+    let (array, round_trip) = NdArray::<u8>::from_arrow_with_mask(column.as_ref()).unwrap();
+    assert_eq!(round_trip, mask);
+    assert_eq!(array.slice_along(Axis(0), 1), NdArray::new([3, 4]));
+
+    let result = NdArray::scalar(1.0f32).into_arrow();
+    assert_eq!(result.unwrap_err(), ConversionError::Dimensionality { expected: 1, found: 0 });
+}
+
+#[test]
+fn test_arrow_tensor_ipc() {
+    let array = NdArray::<f32>::randn([3, 4, 5]);
+
+    let mut bytes = Vec::new();
+    array.write_arrow_tensor(&mut bytes).unwrap();
+    assert_eq!(bytes.len() % 8, 0);
+
+    let tensor = NdArray::<f32>::from_arrow_tensor(&bytes).unwrap();
+    assert_eq!(tensor, array);
+
+    // views are written contiguously
+    let mut bytes = Vec::new();
+    array.slice(s![.., 1, 1..4]).T().write_arrow_tensor(&mut bytes).unwrap();
+    assert_eq!(NdArray::<f32>::from_arrow_tensor(&bytes).unwrap(), array.slice(s![.., 1, 1..4]).T());
+
+    // unaligned messages are copied
+    let mut unaligned = vec![0u8];
+    unaligned.extend_from_slice(&bytes);
+    assert_eq!(NdArray::<f32>::from_arrow_tensor(&unaligned[1..]).unwrap(), array.slice(s![.., 1, 1..4]).T());
+
+    let result = NdArray::<i32>::from_arrow_tensor(&bytes);
+    assert_eq!(result.unwrap_err(), ConversionError::DtypeMismatch { expected: DType::I32, found: "f32".into() });
+
+    assert!(matches!(NdArray::<f32>::from_arrow_tensor(&bytes[..12]), Err(ConversionError::InvalidData(_))));
+    assert!(matches!(NdArray::<f32>::from_arrow_tensor(&[]), Err(ConversionError::InvalidData(_))));
+
+    let mut bytes = Vec::new();
+    NdArray::scalar(-7i64).write_arrow_tensor(&mut bytes).unwrap();
+    assert_eq!(NdArray::<i64>::from_arrow_tensor(&bytes).unwrap(), NdArray::scalar(-7));
+}
+
+/// Encapsulates an Arrow Tensor IPC message for `i32` data with explicit strides in bytes,
+/// as a producer sharing a strided tensor might write it.
+fn strided_tensor_message(data: &[i32], shape: &[i64], strides: &[i64]) -> Vec<u8> {
+    use arrow_ipc::{Buffer, Int, IntArgs, Message, MessageArgs, MessageHeader, MetadataVersion, Tensor, TensorArgs, TensorDim, TensorDimArgs, Type};
+    use flatbuffers::FlatBufferBuilder;
+
+    let body: Vec<u8> = data.iter().flat_map(|x| x.to_le_bytes()).collect();
+
+    let mut builder = FlatBufferBuilder::new();
+    let type_ = Int::create(&mut builder, &IntArgs { bitWidth: 32, is_signed: true });
+    let dims: Vec<_> = shape.iter()
+        .map(|&size| TensorDim::create(&mut builder, &TensorDimArgs { size_: size, name: None }))
+        .collect();
+    let shape = builder.create_vector(&dims);
+    let strides = builder.create_vector(strides);
+
+    let buffer = Buffer::new(0, body.len() as i64);
+    let tensor = Tensor::create(&mut builder, &TensorArgs {
+        type_type: Type::Int,
+        type_: Some(type_.as_union_value()),
+        shape: Some(shape),
+        strides: Some(strides),
+        data: Some(&buffer),
+    });
+
+    let message = Message::create(&mut builder, &MessageArgs {
+        version: MetadataVersion::V5,
+        header_type: MessageHeader::Tensor,
+        header: Some(tensor.as_union_value()),
+        bodyLength: body.len() as i64,
+        custom_metadata: None,
+    });
+    builder.finish(message, None);
+
+    let metadata = builder.finished_data();
+    let metadata_len = (metadata.len() + 8).next_multiple_of(8) - 8;
+
+    let mut bytes = vec![0xFF, 0xFF, 0xFF, 0xFF];
+    bytes.extend_from_slice(&(metadata_len as u32).to_le_bytes());
+    bytes.extend_from_slice(metadata);
+    bytes.resize(8 + metadata_len, 0);
+    bytes.extend_from_slice(&body);
+    bytes
+}
+
+#[test]
+fn test_arrow_tensor_ipc_strided() {
+    // every other element of a vector, and the top-left 2x2 block of a 3x3 matrix
+    let bytes = strided_tensor_message(&[1, 2, 3], &[2], &[8]);
+    let tensor = NdArray::<i32>::from_arrow_tensor(&bytes).unwrap();
+    assert!(tensor.is_view());
+    assert_eq!(tensor[0], 1);
+    assert_eq!(tensor[1], 3);
+
+    let bytes = strided_tensor_message(&[1, 2, 3, 4, 5, 6, 7, 8, 9], &[2, 2], &[12, 4]);
+    let tensor = NdArray::<i32>::from_arrow_tensor(&bytes).unwrap();
+    assert_eq!(tensor[[1, 1]], 5);
+    assert_eq!(tensor, NdArray::new([[1, 2], [4, 5]]));
+
+    // unaligned messages are copied into contiguous arrays
+    let mut unaligned = vec![0u8];
+    unaligned.extend_from_slice(&bytes);
+    let tensor = NdArray::<i32>::from_arrow_tensor(&unaligned[1..]).unwrap();
+    assert!(!tensor.is_view());
+    assert_eq!(tensor[[1, 0]], 4);
+}
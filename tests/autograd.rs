@@ -514,3 +514,811 @@ fn test_autograd_moved_reshape() {
     assert_eq!(mat1.gradient().unwrap(), NdArray::new([[4.0, 3.0], [4.0, 3.0]]));
     assert_eq!(mat2.gradient().unwrap(), NdArray::new([[-2.0, -3.0], [27.0, 26.0]]));
 }
+
+#[test]
+fn test_autograd_var_std() {
+    let mut a = Tensor::new([1.0, 2.0, 3.0, 4.0]);
+    a.set_requires_grad(true);
+
+    // d/dx var(x) = 2(x - mean) / (N - ddof)
+    a.var(0).backward();
+    assert_eq!(a.gradient().unwrap(), NdArray::new([-0.75, -0.25, 0.25, 0.75]));
+
+    let mut b = Tensor::new([[1.0, 3.0], [2.0, 6.0]]);
+    b.set_requires_grad(true);
+
+    b.var_along(-1, 1).backward();
+    assert_eq!(b.gradient().unwrap(), NdArray::new([[-2.0, 2.0], [-4.0, 4.0]]));
+
+    let mut c = Tensor::new([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    c.set_requires_grad(true);
+
+    // d/dx std(x) = (x - mean) / ((N - ddof) * std)
+    c.std(0).backward();
+    assert_eq!(c.gradient().unwrap(), NdArray::new([-3.0, -1.0, -1.0, -1.0, 0.0, 0.0, 2.0, 4.0]) / 16.0);
+}
+
+#[test]
+fn test_autograd_cumsum() {
+    let mut a = Tensor::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    a.set_requires_grad(true);
+
+    a.cumsum(1).backward();
+    assert_eq!(a.gradient().unwrap(), NdArray::new([[3.0, 2.0, 1.0], [3.0, 2.0, 1.0]]));
+
+    a.zero_gradient();
+    a.cumsum(0).backward_with(NdArray::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+    assert_eq!(a.gradient().unwrap(), NdArray::new([[5.0, 7.0, 9.0], [4.0, 5.0, 6.0]]));
+}
+
+#[test]
+fn test_autograd_logsumexp() {
+    let mut a = Tensor::new([[0.0, 3.0f64.ln()], [1.0, 1.0]]);
+    a.set_requires_grad(true);
+
+    // d/dx logsumexp(x) = softmax(x)
+    a.logsumexp_along(1).backward();
+    assert_almost_eq!(a.gradient().unwrap(), NdArray::new([[0.25, 0.75], [0.5, 0.5]]), 1e-12);
+    assert_almost_eq!(NdArray::new([[0.25, 0.75], [0.5, 0.5]]), a.gradient().unwrap(), 1e-12);
+
+    let mut b = Tensor::new([1000.0, 1000.0]);
+    b.set_requires_grad(true);
+
+    b.logsumexp().backward();
+    assert_almost_eq!(b.gradient().unwrap(), NdArray::new([0.5, 0.5]), 1e-10);
+}
+
+#[test]
+fn test_autograd_norm() {
+    let mut a = Tensor::new([3.0, -4.0, 0.0]);
+    a.set_requires_grad(true);
+
+    a.norm(Norm::L2).backward();
+    assert_eq!(a.gradient().unwrap(), NdArray::new([0.6, -0.8, 0.0]));
+
+    a.zero_gradient();
+    a.norm(Norm::L1).backward();
+    assert_eq!(a.gradient().unwrap(), NdArray::new([1.0, -1.0, 0.0]));
+
+    let mut b = Tensor::new([[3.0, -4.0], [-2.0, 2.0], [0.0, 0.0]]);
+    b.set_requires_grad(true);
+
+    // the gradient of the infinity norm is split between tied maxima
+    b.norm_along(Norm::Inf, 1).backward();
+    assert_eq!(b.gradient().unwrap(), NdArray::new([[0.0, -1.0], [-0.5, 0.5], [0.0, 0.0]]));
+
+    b.zero_gradient();
+    b.norm_along(Norm::L2, -1).backward();
+    let half_sqrt2 = 0.5f64.sqrt();
+    assert_almost_eq!(b.gradient().unwrap(), NdArray::new([[0.6, -0.8], [-half_sqrt2, half_sqrt2], [0.0, 0.0]]), 1e-12);
+
+    let mut c = Tensor::new([1.0f32, -2.0]);
+    c.set_requires_grad(true);
+
+    // d/dx ||x||_p = sign(x) |x|^(p-1) / ||x||_p^(p-1)
+    c.norm(Norm::Lp(3.0)).backward();
+    let norm = 9.0f32.cbrt();
+    assert_almost_eq!(c.gradient().unwrap(), NdArray::new([1.0 / (norm * norm), -4.0 / (norm * norm)]), 1e-6);
+    assert_almost_eq!(NdArray::new([1.0 / (norm * norm), -4.0 / (norm * norm)]), c.gradient().unwrap(), 1e-6);
+}
+
+#[test]
+fn test_autograd_keepdims() {
+    let mut a = Tensor::new([[0.0, 3.0f64.ln()], [1.0, 1.0]]);
+    a.set_requires_grad(true);
+
+    // d/dx sum(log_softmax(x)) = 1 - N * softmax(x)
+    let log_softmax = &a - a.logsumexp_keepdims(1);
+    assert_eq!(log_softmax.shape(), &[2, 2]);
+
+    log_softmax.backward();
+    assert_almost_eq!(a.gradient().unwrap(), NdArray::new([[0.5, -0.5], [0.0, 0.0]]), 1e-12);
+    assert_almost_eq!(NdArray::new([[0.5, -0.5], [0.0, 0.0]]), a.gradient().unwrap(), 1e-12);
+
+    let mut b = Tensor::new([[1.0, 3.0], [2.0, 6.0]]);
+    b.set_requires_grad(true);
+
+    let var = b.var_keepdims(-1, 1);
+    assert_eq!(var.shape(), &[2, 1]);
+
+    var.backward();
+    assert_eq!(b.gradient().unwrap(), NdArray::new([[-2.0, 2.0], [-4.0, 4.0]]));
+}
+
+#[test]
+fn test_autograd_shared_subgraph() {
+    let mut a = Tensor::new([1.0, 2.0f64]);
+    let mut w = Tensor::new([3.0, -1.0]);
+
+    a.set_requires_grad(true);
+    w.set_requires_grad(true);
+
+    // b is used by both branches of a residual-style connection
+    let b = &a * &w;
+    let c = &b * &b;
+    let d = &c + &b;
+    d.backward();
+
+    // d = (aw)^2 + aw
+    // dd/da = (2aw + 1) w
+    // dd/dw = (2aw + 1) a
+    assert_eq!(a.gradient().unwrap(), NdArray::new([21.0, 3.0]));
+    assert_eq!(w.gradient().unwrap(), NdArray::new([7.0, -6.0]));
+}
+
+#[test]
+fn test_autograd_repeated_reuse() {
+    let mut x = Tensor::scalar(1.0f64);
+    x.set_requires_grad(true);
+
+    // each step reuses the previous result twice; without per-node accumulation
+    // the backward pass would traverse the graph 2^60 times
+    let mut y = &x * 1.0;
+    for _ in 0..60 {
+        y = &y + &y;
+    }
+    y.backward();
+
+    assert_eq!(x.gradient().unwrap().value(), 2.0f64.powi(60));
+}
+
+#[test]
+fn test_autograd_deep_graph() {
+    // the backward pass is iterative, but dropping the graph is still recursive
+    // so we need more stack than the default test thread in debug builds
+    let test = std::thread::Builder::new().stack_size(64 * 1024 * 1024).spawn(|| {
+        let mut x = Tensor::scalar(1.0f64);
+        x.set_requires_grad(true);
+
+        let mut y = &x * 1.0;
+        for _ in 0..10000 {
+            y = &y * 1.0;
+        }
+        y.backward();
+
+        assert_eq!(x.gradient().unwrap().value(), 1.0);
+    });
+
+    test.unwrap().join().unwrap();
+}
+
+#[test]
+fn test_autograd_backward_accumulates() {
+    let mut a = Tensor::new([1.0, 2.0f32]);
+    a.set_requires_grad(true);
+
+    let b = &a * &a;
+    b.backward();
+    b.backward();
+
+    assert_eq!(a.gradient().unwrap(), NdArray::new([4.0, 8.0]));
+}
+
+#[test]
+fn test_autograd_create_graph() {
+    let mut x = Tensor::new([1.0f64, -2.0, 3.0]);
+    x.set_requires_grad(true);
+
+    let y = (&x * &x * &x).sum();
+    y.backward_create_graph();
+
+    // dy/dx = 3x^2
+    let gradient = x.gradient_tensor().unwrap();
+    assert_eq!(gradient, Tensor::new([3.0, 12.0, 27.0]));
+    assert!(gradient.requires_grad());
+
+    // d(sum(3x^2))/dx = 6x
+    x.zero_gradient();
+    gradient.sum().backward();
+    assert_eq!(x.gradient().unwrap(), NdArray::new([6.0, -12.0, 18.0]));
+    assert!(!x.gradient_tensor().unwrap().requires_grad());
+}
+
+#[test]
+fn test_gradient_outlives_backward() {
+    let mut a = Tensor::new([1.0f64, 2.0]);
+    a.set_requires_grad(true);
+
+    (&a * 3.0).sum().backward();
+    let gradient = a.gradient().unwrap();
+
+    // accumulating into a gradient shared with a tensor copies it
+    let alias = a.gradient_tensor().unwrap();
+    (&a * 777.0).sum().backward();
+    drop(alias);
+
+    assert_eq!(gradient, NdArray::new([3.0, 3.0]));
+    assert_eq!(a.gradient().unwrap(), NdArray::new([780.0, 780.0]));
+}
+
+#[test]
+fn test_grad() {
+    let mut x = Tensor::new([1.0f64, 2.0]);
+    let mut w = Tensor::new([3.0, -1.0]);
+    let mut unused = Tensor::scalar(5.0);
+
+    x.set_requires_grad(true);
+    w.set_requires_grad(true);
+    unused.set_requires_grad(true);
+
+    let h = &x * &w;
+    let y = h.dot(&h);
+
+    let grads = grad(&[&y], &[&x, &h, &unused], false);
+    assert_eq!(grads[0], Tensor::new([18.0, 4.0]));  // 2 w^2 x
+    assert_eq!(grads[1], Tensor::new([6.0, -4.0]));  // 2h
+    assert_eq!(grads[2], Tensor::scalar(0.0));
+    assert!(!grads[0].requires_grad());
+
+    // leaf gradients are untouched
+    assert_eq!(x.gradient().unwrap(), NdArray::new([0.0, 0.0]));
+    assert_eq!(w.gradient().unwrap(), NdArray::new([0.0, 0.0]));
+}
+
+#[test]
+fn test_grad_gradient_penalty() {
+    let mut x = Tensor::new([1.0f64, 2.0]);
+    let mut w = Tensor::new([3.0, 4.0]);
+
+    x.set_requires_grad(true);
+    w.set_requires_grad(true);
+
+    // critic(x) = w . x^2, so d(critic)/dx = 2 w x
+    let critic = w.dot(&x * &x);
+    let dx = grad(&[&critic], &[&x], true).remove(0);
+    assert_eq!(dx, Tensor::new([6.0, 16.0]));
+
+    // penalty = ||2 w x||^2 = 4 sum(w^2 x^2), so d(penalty)/dw = 8 w x^2
+    let penalty = dx.dot(&dx);
+    penalty.backward();
+
+    assert_eq!(w.gradient().unwrap(), NdArray::new([24.0, 128.0]));
+    assert_eq!(x.gradient().unwrap(), NdArray::new([72.0, 256.0]));  // 8 w^2 x
+}
+
+#[test]
+fn test_jacobian() {
+    let a = Tensor::new([[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let mut x = Tensor::new([1.0, -1.0, 2.0]);
+    x.set_requires_grad(true);
+
+    assert_eq!(jacobian(&a.matmul(&x), &x), *a.ndarray());
+
+    let y = x.cumsum(0);
+    assert_eq!(jacobian(&y, &x), NdArray::new([[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [1.0, 1.0, 1.0]]));
+}
+
+#[test]
+fn test_hessian() {
+    let a = Tensor::new([[1.0f64, 2.0], [3.0, 4.0]]);
+    let mut x = Tensor::new([1.0, -1.0]);
+    x.set_requires_grad(true);
+
+    // the Hessian of x^T A x is A + A^T
+    let y = x.dot(a.matmul(&x));
+    assert_eq!(hessian(&y, &x), NdArray::new([[2.0, 5.0], [5.0, 8.0]]));
+
+    // the Hessian of sum((x / 2)^3) is diag(3x / 4)
+    let half = &x / 2.0;
+    let y = (&half * &half * &half).sum();
+    assert_eq!(hessian(&y, &x), NdArray::new([[0.75, 0.0], [0.0, -0.75]]));
+
+    // the Hessian of a linear function is 0
+    let y = x.sum();
+    assert_eq!(hessian(&y, &x), NdArray::new([[0.0, 0.0], [0.0, 0.0]]));
+}
+
+#[test]
+fn test_hessian_reductions() {
+    let values = [0.5f64, -1.0, 2.0];
+    let mut x = Tensor::new(values);
+    x.set_requires_grad(true);
+
+    // the Hessian of logsumexp(x) is diag(s) - s s^T where s = softmax(x)
+    let total: f64 = values.iter().map(|x| x.exp()).sum();
+    let s: Vec<f64> = values.iter().map(|x| x.exp() / total).collect();
+
+    let expected: Vec<f64> = (0..9)
+        .map(|i| if i / 3 == i % 3 { s[i / 3] } else { 0.0 } - s[i / 3] * s[i % 3])
+        .collect();
+    let expected = NdArray::new(expected).reshape([3, 3]);
+
+    assert_almost_eq!(hessian(&x.logsumexp(), &x), expected);
+
+    // the Hessian of ||x|| is (I - u u^T) / ||x|| where u = x / ||x||
+    let norm = values.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let expected: Vec<f64> = (0..9)
+        .map(|i| (if i / 3 == i % 3 { 1.0 } else { 0.0 } - values[i / 3] * values[i % 3] / (norm * norm)) / norm)
+        .collect();
+    let expected = NdArray::new(expected).reshape([3, 3]);
+
+    assert_almost_eq!(hessian(&x.norm(Norm::L2), &x), expected);
+
+    // the Hessian of var(x) is 2 (I - 1/N) / (N - ddof)
+    let expected: Vec<f64> = (0..9)
+        .map(|i| 2.0 * (if i / 3 == i % 3 { 1.0 } else { 0.0 } - 1.0 / 3.0) / 2.0)
+        .collect();
+    let expected = NdArray::new(expected).reshape([3, 3]);
+
+    assert_almost_eq!(hessian(&x.var(1), &x), expected);
+}
+
+#[test]
+fn test_hessian_vector_product() {
+    let mut x = Tensor::new([[1.0f64, 2.0], [-1.0, 0.5]]);
+    x.set_requires_grad(true);
+
+    let y = x.matmul(&x).norm(Norm::L2);
+    let v = NdArray::new([[0.5, -1.0], [2.0, 1.0]]);
+
+    let hessian = hessian(&y, &x).reshape([4, 4]);
+    let expected = hessian.matmul((&v).reshape([4])).reshape([2, 2]);
+
+    assert_almost_eq!(hessian_vector_product(&y, &x, &v), expected);
+}
+
+#[test]
+fn test_functional_grad() {
+    let x = NdArray::new([[1.0f64, 2.0], [3.0, 4.0]]);
+    let w = NdArray::new([0.5, -1.0]);
+
+    // f(x, w) = sum(x w)
+    let grads = functional::grad(|inputs| inputs[0].matmul(&inputs[1]).sum(), &[&x, &w]);
+    assert_eq!(grads[0], NdArray::new([[0.5, -1.0], [0.5, -1.0]]));
+    assert_eq!(grads[1], NdArray::new([4.0, 6.0]));
+
+    // unused inputs have a gradient of 0
+    let grads = functional::grad(|inputs| (&inputs[0] * &inputs[0]).sum(), &[&w, &x]);
+    assert_eq!(grads[0], NdArray::new([1.0, -2.0]));
+    assert_eq!(grads[1], NdArray::zeros([2, 2]));
+}
+
+#[test]
+fn test_functional_does_not_mutate_leaves() {
+    let mut a = Tensor::new([1.0f64, 2.0]);
+    a.set_requires_grad(true);
+
+    let x = NdArray::new([3.0, 4.0]);
+    let dx = functional::grad(|x| a.dot(&x[0]), &[&x]).remove(0);
+
+    assert_eq!(dx, NdArray::new([1.0, 2.0]));
+    assert_eq!(a.gradient().unwrap(), NdArray::new([0.0, 0.0]));
+
+    // the transforms also work when gradients are disabled
+    let dx = no_grad(|| functional::grad(|x| a.dot(&x[0]), &[&x]).remove(0));
+    assert_eq!(dx, NdArray::new([1.0, 2.0]));
+}
+
+#[test]
+#[should_panic(expected = "inference_mode")]
+fn test_functional_grad_in_inference_mode() {
+    let x = NdArray::new([3.0f64, 4.0]);
+    inference_mode(|| functional::grad(|x| x[0].sum(), &[&x]));
+}
+
+#[test]
+fn test_functional_jvp_in_inference_mode() {
+    // forward mode records no graph, so it is unaffected by inference mode
+    let x = NdArray::new([3.0f64, 4.0]);
+    let t = NdArray::new([1.0, 2.0]);
+
+    let (_, jvp) = inference_mode(|| functional::jvp(|x| (&x * &x).sum(), &x, &t));
+    assert_eq!(jvp, NdArray::scalar(22.0));
+}
+
+#[test]
+fn test_functional_vjp_jvp() {
+    let a = Tensor::new([[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let x = NdArray::new([1.0, -1.0, 2.0]);
+    let f = |x: Tensor<'static, f64>| {
+        let y = a.matmul(&x);
+        &y * &y
+    };
+
+    let jacobian = functional::jacobian(f, &x);
+    assert_eq!(jacobian.shape(), &[2, 3]);
+
+    let v = NdArray::new([1.0, -2.0]);
+    let (y, vjp) = functional::vjp(f, &x, &v);
+    assert_eq!(y, NdArray::new([25.0, 121.0]));
+    assert_almost_eq!(vjp, (&jacobian).T().matmul(&v));
+
+    let dual_a = DualTensor::constant(a.ndarray().clone());
+    let dual_f = |x: DualTensor<'static, f64>| {
+        let y = dual_a.matmul(&x);
+        &y * &y
+    };
+
+    let t = NdArray::new([0.5, 1.0, -1.0]);
+    let (y, jvp) = functional::jvp(dual_f, &x, &t);
+    assert_eq!(y, NdArray::new([25.0, 121.0]));
+    assert_almost_eq!(jvp, jacobian.matmul(&t));
+
+    // the Jacobian-vector product of the identity is the vector
+    let (_, jvp) = functional::jvp(|x| x, &x, &t);
+    assert_eq!(jvp, t);
+}
+
+#[test]
+fn test_autograd_reshape_transposed() {
+    let mut x = Tensor::new([[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    x.set_requires_grad(true);
+
+    // the gradient of the reshape is a transposed view
+    let weights = Tensor::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let y = ((&x).reshape([3, 2]).T() * &weights).sum();
+    y.backward();
+
+    assert_eq!(x.gradient().unwrap(), NdArray::new([[1.0, 4.0, 2.0], [5.0, 3.0, 6.0]]));
+}
+
+fn map(array: &NdArray<f64>, func: impl Fn(f64) -> f64) -> NdArray<'static, f64> {
+    NdArray::new(array.flatiter().map(func).collect::<Vec<_>>()).reshape(array.shape())
+}
+
+struct Relu;
+
+impl CustomFunction<f64> for Relu {
+    fn forward(&self, ctx: &mut FunctionContext<f64>, inputs: &[&Tensor<f64>]) -> Tensor<'static, f64> {
+        ctx.save_for_backward(inputs);
+        Tensor::from(map(inputs[0].ndarray(), |x| x.max(0.0)))
+    }
+
+    fn backward(&self, ctx: &FunctionContext<f64>, grad: Tensor<'static, f64>) -> Vec<Option<Tensor<'static, f64>>> {
+        let mask = map(ctx.saved_tensors()[0].ndarray(), |x| if x > 0.0 { 1.0 } else { 0.0 });
+        vec![Some(grad * Tensor::from(mask))]
+    }
+}
+
+/// Rounds its input, but passes gradients through unchanged.
+struct StraightThroughRound;
+
+impl CustomFunction<f64> for StraightThroughRound {
+    fn forward(&self, _: &mut FunctionContext<f64>, inputs: &[&Tensor<f64>]) -> Tensor<'static, f64> {
+        Tensor::from(map(inputs[0].ndarray(), f64::round))
+    }
+
+    fn backward(&self, _: &FunctionContext<f64>, grad: Tensor<'static, f64>) -> Vec<Option<Tensor<'static, f64>>> {
+        vec![Some(grad)]
+    }
+}
+
+/// Computes `x * scale + bias` where `bias` is broadcast and `scale` is not differentiable.
+struct ScaleShift;
+
+impl CustomFunction<f64> for ScaleShift {
+    fn forward(&self, ctx: &mut FunctionContext<f64>, inputs: &[&Tensor<f64>]) -> Tensor<'static, f64> {
+        ctx.save_for_backward(&[inputs[1]]);
+        inputs[0] * inputs[1] + inputs[2]
+    }
+
+    fn backward(&self, ctx: &FunctionContext<f64>, grad: Tensor<'static, f64>) -> Vec<Option<Tensor<'static, f64>>> {
+        assert!(ctx.needs_input_grad(0));
+        assert!(!ctx.needs_input_grad(1));
+
+        let scale = &ctx.saved_tensors()[0];
+        vec![Some(&grad * scale), None, Some(grad)]
+    }
+}
+
+#[test]
+fn test_custom_function() {
+    let mut x = Tensor::new([-1.0, 2.0, 0.5]);
+    x.set_requires_grad(true);
+
+    let y = Relu.apply(&[&x]);
+    assert_eq!(y, Tensor::new([0.0, 2.0, 0.5]));
+    assert!(y.requires_grad());
+
+    (&y * &y).sum().backward();
+    assert_eq!(x.gradient().unwrap(), NdArray::new([0.0, 4.0, 1.0]));
+
+    x.zero_gradient();
+    let y = StraightThroughRound.apply(&[&x]) * &x;
+    assert_eq!(y, Tensor::new([1.0, 4.0, 0.5]));
+
+    y.backward();
+    assert_eq!(x.gradient().unwrap(), NdArray::new([-2.0, 4.0, 1.5]));
+
+    // not recorded if no inputs require gradients
+    let y = Relu.apply(&[&Tensor::new([1.0, -1.0])]);
+    assert!(!y.requires_grad());
+
+    let y = no_grad(|| Relu.apply(&[&x]));
+    assert!(!y.requires_grad());
+}
+
+#[test]
+fn test_custom_function_broadcast() {
+    let mut x = Tensor::new([[1.0, 2.0], [3.0, 4.0]]);
+    let scale = Tensor::new([[2.0, 3.0], [4.0, 5.0]]);
+    let mut bias = Tensor::new([1.0, -1.0]);
+
+    x.set_requires_grad(true);
+    bias.set_requires_grad(true);
+
+    let y = ScaleShift.apply(&[&x, &scale, &bias]);
+    assert_eq!(y, Tensor::new([[3.0, 5.0], [13.0, 19.0]]));
+
+    y.backward();
+    assert_eq!(x.gradient().unwrap(), *scale.ndarray());
+    assert_eq!(bias.gradient().unwrap(), NdArray::new([2.0, 2.0]));
+}
+
+#[test]
+fn test_custom_function_higher_order() {
+    let mut x = Tensor::new([1.0, -2.0]);
+    x.set_requires_grad(true);
+
+    // the backward pass of Relu is differentiable with respect to its gradient
+    let y = Relu.apply(&[&(&x * &x * &x)]);
+    let dx = grad(&[&y.sum()], &[&x], true).remove(0);
+    assert_eq!(dx, Tensor::new([3.0, 0.0]));
+
+    assert_eq!(hessian(&y.sum(), &x), NdArray::new([[6.0, 0.0], [0.0, 0.0]]));
+}
+
+#[test]
+#[should_panic]
+fn test_custom_function_wrong_gradient_count() {
+    struct Broken;
+
+    impl CustomFunction<f64> for Broken {
+        fn forward(&self, _: &mut FunctionContext<f64>, inputs: &[&Tensor<f64>]) -> Tensor<'static, f64> {
+            inputs[0] + inputs[1]
+        }
+
+        fn backward(&self, _: &FunctionContext<f64>, grad: Tensor<'static, f64>) -> Vec<Option<Tensor<'static, f64>>> {
+            vec![Some(grad)]
+        }
+    }
+
+    let mut x = Tensor::new([1.0, 2.0]);
+    x.set_requires_grad(true);
+
+    Broken.apply(&[&x, &x]).backward();
+}
+
+#[test]
+fn test_retain_grad() {
+    let mut a = Tensor::new([1.0, 2.0]);
+    a.set_requires_grad(true);
+
+    let b = &a * &a;
+    let c = b.sum();
+    assert!(b.gradient().is_none());
+
+    b.retain_grad();
+    c.retain_grad();
+    assert!(b.retains_grad());
+
+    // b is used twice, so its gradient is the total of both uses
+    (&c * 2.0 + (&b * 3.0).sum()).backward();
+    assert_eq!(b.gradient().unwrap(), NdArray::new([5.0, 5.0]));
+    assert_eq!(c.gradient().unwrap(), NdArray::scalar(2.0));
+    assert_eq!(a.gradient().unwrap(), NdArray::new([10.0, 20.0]));
+
+    // retained gradients accumulate like leaf gradients
+    c.backward();
+    assert_eq!(b.gradient().unwrap(), NdArray::new([6.0, 6.0]));
+
+    let gradient = b.gradient().unwrap();
+    b.zero_gradient();
+    assert_eq!(b.gradient().unwrap(), NdArray::new([0.0, 0.0]));
+    assert_eq!(gradient, NdArray::new([6.0, 6.0]));
+
+    // but are not modified by grad()
+    let _ = grad(&[&c], &[&a], false);
+    assert_eq!(b.gradient().unwrap(), NdArray::new([0.0, 0.0]));
+
+    // retained gradients computed with a graph are detached when zeroed
+    (&b * &b).sum().backward_create_graph();
+    let gradient = b.gradient_tensor().unwrap();
+    assert!(gradient.requires_grad());
+
+    b.zero_gradient();
+    assert!(!b.gradient_tensor().unwrap().requires_grad());
+    assert_eq!(b.gradient().unwrap(), NdArray::new([0.0, 0.0]));
+    assert_eq!(gradient, Tensor::new([2.0, 8.0]));
+
+    // leaves always accumulate their gradients
+    a.retain_grad();
+    assert!(!a.retains_grad());
+}
+
+#[test]
+fn test_gradient_hooks() {
+    use std::sync::{Arc, Mutex};
+
+    let mut a = Tensor::new([1.0, -2.0]);
+    a.set_requires_grad(true);
+
+    let b = &a * 2.0;
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let seen_clone = seen.clone();
+    b.register_hook(move |grad| {
+        seen_clone.lock().unwrap().push(grad.clone());
+        None
+    });
+
+    // hooks run in order, each seeing the gradient returned by the previous hook
+    b.register_hook(|grad| Some(grad * 10.0));
+    let seen_clone = seen.clone();
+    b.register_hook(move |grad| {
+        seen_clone.lock().unwrap().push(grad.clone());
+        None
+    });
+
+    b.sum().backward();
+    assert_eq!(*seen.lock().unwrap(), vec![NdArray::new([1.0, 1.0]), NdArray::new([10.0, 10.0])]);
+    assert_eq!(a.gradient().unwrap(), NdArray::new([20.0, 20.0]));
+
+    // hooks on leaves modify the gradient before it is accumulated
+    a.zero_gradient();
+    a.register_hook(|grad| Some(grad * -1.0));
+    b.sum().backward();
+    assert_eq!(a.gradient().unwrap(), NdArray::new([-20.0, -20.0]));
+
+    // hooks also apply to grad()
+    let da = grad(&[&b.sum()], &[&a], false).remove(0);
+    assert_eq!(da, Tensor::new([-20.0, -20.0]));
+}
+
+#[test]
+#[should_panic]
+fn test_gradient_hook_shape_mismatch() {
+    let mut a = Tensor::new([1.0, 2.0]);
+    a.set_requires_grad(true);
+
+    let b = &a * 2.0;
+    b.register_hook(|_| Some(NdArray::new([1.0, 2.0, 3.0])));
+    b.sum().backward();
+}
+
+#[test]
+fn test_post_accumulate_grad_hook() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let mut a = Tensor::new([1.0, 2.0]);
+    a.set_requires_grad(true);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = calls.clone();
+
+    a.register_post_accumulate_grad_hook(move |gradient| {
+        calls_clone.fetch_add(1, Ordering::Relaxed);
+        *gradient *= 0.5;
+    });
+
+    // the hook runs once per backward pass with the total accumulated gradient
+    let b = &a * 2.0 + &a * 4.0;
+    b.sum().backward();
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    assert_eq!(a.gradient().unwrap(), NdArray::new([3.0, 3.0]));
+
+    b.sum().backward();
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+    assert_eq!(a.gradient().unwrap(), NdArray::new([4.5, 4.5]));
+
+    // grad() does not accumulate into the leaves
+    let _ = grad(&[&b.sum()], &[&a], false);
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+#[should_panic]
+fn test_post_accumulate_grad_hook_non_leaf() {
+    let mut a = Tensor::new([1.0, 2.0]);
+    a.set_requires_grad(true);
+
+    (&a * 2.0).register_post_accumulate_grad_hook(|_| {});
+}
+
+#[test]
+fn test_tensor_is_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Tensor<f32>>();
+    assert_send_sync::<Tensor<f64>>();
+}
+
+#[test]
+fn test_autograd_concurrent() {
+    let tests: [fn(); 12] = [
+        test_autograd1, test_autograd2, test_autograd3, test_autograd4, test_autograd5, test_autograd6,
+        test_autograd_matmul_ops, test_autograd_bmm_ops, test_autograd_shared_subgraph,
+        test_autograd_create_graph, test_grad, test_gradient_hooks,
+    ];
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            for test in tests {
+                s.spawn(test);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_autograd_shared_parameters() {
+    let mut w = Tensor::new([[1.0, 2.0], [3.0, 4.0]]);
+    w.set_requires_grad(true);
+
+    // each worker differentiates its own graph through the shared parameters
+    std::thread::scope(|s| {
+        for i in 0..8 {
+            let w = &w;
+            s.spawn(move || {
+                let x = Tensor::new([1.0, i as f64]);
+                w.matmul(&x).sum().backward();
+            });
+        }
+    });
+
+    // d(sum(Wx))/dW = [1, 1]^T x summed over each worker's x
+    assert_eq!(w.gradient().unwrap(), NdArray::new([[8.0, 28.0], [8.0, 28.0]]));
+
+    // tensors and their graphs can be moved to other threads
+    let y = (&w * &w).sum();
+    std::thread::spawn(move || y.backward()).join().unwrap();
+    assert_eq!(w.gradient().unwrap(), NdArray::new([[10.0, 32.0], [14.0, 36.0]]));
+}
+
+#[test]
+fn test_autograd_concurrent_gradient_reads() {
+    let mut w = Tensor::new([1.0, 2.0]);
+    w.set_requires_grad(true);
+    w.register_post_accumulate_grad_hook(|gradient| *gradient *= 1.0);
+
+    // reading the gradient while backward passes accumulate into it and run hooks must not deadlock
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            let w = &w;
+            s.spawn(move || {
+                for _ in 0..100 {
+                    (w * 2.0).sum().backward();
+                }
+            });
+            s.spawn(move || {
+                for _ in 0..100 {
+                    let _ = w.gradient();
+                    let _ = w.gradient_tensor();
+                }
+            });
+        }
+    });
+
+    assert_eq!(w.gradient().unwrap(), NdArray::new([800.0, 800.0]));
+}
+
+#[test]
+fn test_graph_dot() {
+    let mut a = Tensor::new([[1.0f32, 2.0], [3.0, 4.0]]);
+    a.set_requires_grad(true);
+    let b = Tensor::new([1.0f32, 2.0]);
+
+    // b is broadcast and detached from the graph
+    let c = (&a * &b).sum();
+    assert_eq!(c.graph_dot(), "\
+digraph {
+    node [shape=box];
+    tensor [label=\"tensor\\n[] f32\", shape=ellipse];
+    tensor -> 0;
+    0 [label=\"SumBackwards\"];
+    0 -> 1;
+    1 [label=\"MulBackwards\\nlhs: [2, 2] f32\\nrhs: [2] f32\"];
+    1 -> 2;
+    1 -> 3;
+    3 [label=\"NoneBackwards\", style=dashed];
+    2 [label=\"AccumulateGrad\\ngradient: [2, 2] f32\"];
+}
+");
+
+    // shared nodes appear once
+    let d = &a + &a;
+    let dot = d.graph_dot();
+    assert_eq!(dot.matches("AccumulateGrad").count(), 1);
+    assert_eq!(dot.matches("0 -> 1;").count(), 2);
+}
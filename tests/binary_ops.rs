@@ -749,6 +749,20 @@ fn test_isub() {
     assert_eq!(tensor1, correct);
 }
 
+#[test]
+fn test_iadd_non_contiguous() {
+    let mut tensor1 = NdArray::new([[1, 2], [3, 4]]).transpose(0, 1);
+    let tensor2 = NdArray::new([[10, 20], [30, 40]]);
+
+    let correct = NdArray::new([[11, 23], [32, 44]]);
+    tensor1 += &tensor2;
+    assert_eq!(tensor1, correct);
+
+    let correct = NdArray::new([[22, 46], [64, 88]]);
+    tensor1 *= 2;
+    assert_eq!(tensor1, correct);
+}
+
 #[test]
 #[should_panic]
 fn test_broadcast_panic() {
@@ -0,0 +1,140 @@
+use redstone_ml::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// counts the bytes allocated by each thread so that tests running in parallel don't interfere
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + layout.size() as isize));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() - layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocated() -> isize {
+    ALLOCATED.with(Cell::get)
+}
+
+fn deep_chain(x: &Tensor<f64>, w: &Tensor<f64>) -> Tensor<'static, f64> {
+    let mut y = x * w + 0.5;
+    for _ in 1..16 {
+        y = &y * w + 0.5;
+    }
+    y
+}
+
+fn affine_product(x: &Tensor<f64>, w: &Tensor<f64>, b: &Tensor<f64>) -> Tensor<'static, f64> {
+    (x * w + b) * x
+}
+
+#[test]
+fn test_checkpoint_gradients() {
+    let mut x = Tensor::new([[1.0, 2.0, 3.0], [-1.0, 0.5, 2.0]]);
+    let mut w = Tensor::new([0.5, -1.0, 2.0]);
+    let b = Tensor::new([1.0, 1.0, 1.0]);
+    x.set_requires_grad(true);
+    w.set_requires_grad(true);
+
+    let expected = affine_product(&x, &w, &b);
+    expected.sum().backward();
+    let x_grad = x.gradient().unwrap().clone();
+    let w_grad = w.gradient().unwrap().clone();
+
+    x.zero_gradient();
+    w.zero_gradient();
+
+    let y = checkpoint(|inputs| affine_product(&inputs[0], &inputs[1], &inputs[2]), &[&x, &w, &b]);
+    assert!(y.requires_grad());
+    assert_eq!(y, expected);
+
+    y.sum().backward();
+    assert_eq!(x.gradient().unwrap(), x_grad);
+    assert_eq!(w.gradient().unwrap(), w_grad);
+    assert!(b.gradient().is_none());
+}
+
+#[test]
+fn test_checkpoint_within_graph() {
+    let mut x = Tensor::new([1.0, 2.0]);
+    x.set_requires_grad(true);
+
+    // checkpointed segments compose with the rest of the graph
+    let h = &x * 2.0;
+    let y = checkpoint(|inputs| &inputs[0] * &inputs[0], &[&h]);
+    let z = (&y * &x).sum();
+    z.backward();
+
+    // z = 4x^3
+    assert_eq!(x.gradient().unwrap(), NdArray::new([12.0, 48.0]));
+
+    let dx = grad(&[&z], &[&x], false).remove(0);
+    assert_eq!(dx, Tensor::new([12.0, 48.0]));
+}
+
+#[test]
+fn test_checkpoint_no_grad() {
+    let mut x = Tensor::new([1.0, 2.0]);
+    x.set_requires_grad(true);
+
+    let _guard = NoGradGuard::new();
+    let y = checkpoint(|inputs| &inputs[0] * 3.0, &[&x]);
+
+    assert!(!y.requires_grad());
+    assert_eq!(y, Tensor::new([3.0, 6.0]));
+}
+
+#[test]
+#[should_panic]
+fn test_checkpoint_create_graph() {
+    let mut x = Tensor::new([1.0, 2.0]);
+    x.set_requires_grad(true);
+
+    let y = checkpoint(|inputs| &inputs[0] * &inputs[0], &[&x]);
+    y.sum().backward_create_graph();
+}
+
+#[test]
+fn test_checkpoint_memory() {
+    let mut x = Tensor::<f64>::ones([10_000]);
+    let mut w = Tensor::full(0.5, [10_000]);
+    x.set_requires_grad(true);
+    w.set_requires_grad(true);
+
+    let before = allocated();
+    let y = deep_chain(&x, &w);
+    let saved_without_checkpoint = allocated() - before;
+
+    y.sum().backward();
+    let expected_x_grad = x.gradient().unwrap().clone();
+    let expected_w_grad = w.gradient().unwrap().clone();
+    drop(y);
+
+    x.zero_gradient();
+    w.zero_gradient();
+
+    let before = allocated();
+    let y = checkpoint(|inputs| deep_chain(&inputs[0], &inputs[1]), &[&x, &w]);
+    let saved_with_checkpoint = allocated() - before;
+
+    // only the output is kept alive rather than every intermediate of the chain
+    let output_size = (10_000 * size_of::<f64>()) as isize;
+    assert!(saved_without_checkpoint > 16 * output_size);
+    assert!(saved_with_checkpoint < 2 * output_size);
+
+    y.sum().backward();
+    assert_eq!(x.gradient().unwrap(), expected_x_grad);
+    assert_eq!(w.gradient().unwrap(), expected_w_grad);
+}
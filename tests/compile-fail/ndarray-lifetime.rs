@@ -0,0 +1,8 @@
+use redstone_ml::*;
+
+fn main() {
+    let _array = {
+        let a = Tensor::new([1.0f32, 2.0, 3.0]);
+        a.ndarray()
+    };
+}
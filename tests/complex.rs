@@ -0,0 +1,133 @@
+use paste::paste;
+use redstone_ml::*;
+
+test_for_complex_dtypes!(test_complex_arithmetic, {
+    let a = NdArray::new([T::new(1.0, 2.0), T::new(3.0, -1.0)]);
+    let b = NdArray::new([T::new(0.0, 1.0), T::new(2.0, 2.0)]);
+
+    assert_eq!(&a + &b, NdArray::new([T::new(1.0, 3.0), T::new(5.0, 1.0)]));
+    assert_eq!(&a - &b, NdArray::new([T::new(1.0, 1.0), T::new(1.0, -3.0)]));
+    assert_eq!(&a * &b, NdArray::new([T::new(-2.0, 1.0), T::new(8.0, 4.0)]));
+    assert_eq!(&(&a * &b) / &b, a);
+    assert_eq!(-&a, NdArray::new([T::new(-1.0, -2.0), T::new(-3.0, 1.0)]));
+    assert_eq!(&a * T::new(0.0, 1.0), NdArray::new([T::new(-2.0, 1.0), T::new(1.0, 3.0)]));
+});
+
+test_for_complex_dtypes!(test_complex_broadcast, {
+    let a = NdArray::new([[T::new(1.0, 1.0)], [T::new(2.0, 0.0)]]);
+    let b = NdArray::new([T::new(0.0, 1.0), T::new(1.0, 0.0)]);
+
+    assert_eq!(&a * &b, NdArray::new([
+        [T::new(-1.0, 1.0), T::new(1.0, 1.0)],
+        [T::new(0.0, 2.0), T::new(2.0, 0.0)],
+    ]));
+});
+
+test_for_complex_dtypes!(test_complex_conj, {
+    let a = NdArray::new([[T::new(1.0, 2.0), T::new(-3.0, 0.0)], [T::new(0.0, -4.0), T::new(5.0, 6.0)]]);
+
+    assert_eq!(a.conj(), NdArray::new([[T::new(1.0, -2.0), T::new(-3.0, 0.0)], [T::new(0.0, 4.0), T::new(5.0, -6.0)]]));
+    assert_eq!(a.conj().conj(), a);
+});
+
+test_for_complex_dtypes!(test_complex_real_imag, {
+    let a = NdArray::new([[T::new(1.0, 2.0), T::new(3.0, 4.0)], [T::new(5.0, 6.0), T::new(7.0, 8.0)]]);
+
+    let real = a.real();
+    let imag = a.imag();
+    assert!(real.is_view());
+    assert_eq!(real, NdArray::new([[1.0, 3.0], [5.0, 7.0]]));
+    assert_eq!(imag, NdArray::new([[2.0, 4.0], [6.0, 8.0]]));
+
+    let column = a.slice(s![.., 1]);
+    assert_eq!(column.real(), NdArray::new([3.0, 7.0]));
+    assert_eq!(column.imag(), NdArray::new([4.0, 8.0]));
+
+    let transposed = (&a).T();
+    assert_eq!(transposed.imag(), NdArray::new([[2.0, 6.0], [4.0, 8.0]]));
+    assert_eq!(transposed.imag().sum().value(), 20.0);
+
+    // every element of the views can be indexed and written
+    assert_eq!(real[[1, 1]], 7.0);
+    assert_eq!(imag[[1, 0]], 6.0);
+    assert_eq!(column.imag()[1], 8.0);
+    assert_eq!(transposed.real()[[0, 1]], 5.0);
+
+    let a = NdArray::new([T::new(1.0, 2.0), T::new(3.0, 4.0)]);
+    assert_eq!(a.real()[1], 3.0);
+    assert_eq!(a.imag()[1], 4.0);
+
+    a.imag().fill(0.0);
+    assert_eq!(a, NdArray::new([T::new(1.0, 0.0), T::new(3.0, 0.0)]));
+
+    let mut real = a.real();
+    real[1] = -3.0;
+    assert_eq!(a, NdArray::new([T::new(1.0, 0.0), T::new(-3.0, 0.0)]));
+});
+
+test_for_complex_dtypes!(test_complex_abs_angle, {
+    let a = NdArray::new([T::new(3.0, 4.0), T::new(0.0, -2.0), T::new(-1.0, 0.0)]);
+    let pi = std::f64::consts::PI;
+
+    assert_eq!(a.abs(), NdArray::new([5.0, 2.0, 1.0]));
+    assert_eq!(a.angle().astype::<f64>(), NdArray::new([0.9272952180016122, -pi / 2.0, pi]).astype::<<T as ComplexDataType>::RealType>().astype::<f64>());
+});
+
+test_for_complex_dtypes!(test_complex_astype, {
+    let a = NdArray::new([[1.0f32, -2.5], [0.0, 4.0]]);
+    assert_eq!(a.astype::<T>(), NdArray::new([[T::new(1.0, 0.0), T::new(-2.5, 0.0)], [T::new(0.0, 0.0), T::new(4.0, 0.0)]]));
+
+    let b = NdArray::new([3, -7]);
+    assert_eq!(b.astype::<T>(), NdArray::new([T::new(3.0, 0.0), T::new(-7.0, 0.0)]));
+});
+
+test_for_complex_dtypes!(test_complex_constructors, {
+    assert_eq!(NdArray::<T>::zeros([2]), NdArray::new([T::new(0.0, 0.0), T::new(0.0, 0.0)]));
+    assert_eq!(NdArray::<T>::ones([2]), NdArray::new([T::new(1.0, 0.0), T::new(1.0, 0.0)]));
+});
+
+test_for_complex_dtypes!(test_complex_dot_vdot, {
+    let a = NdArray::new([T::new(1.0, 1.0), T::new(0.0, 2.0)]);
+    let b = NdArray::new([T::new(2.0, 0.0), T::new(1.0, 1.0)]);
+
+    // (1 + i)(2) + (2i)(1 + i) = 2 + 2i + 2i - 2
+    assert_eq!(a.dot(&b).value(), T::new(0.0, 4.0));
+    assert_eq!(a.vdot(&b).value(), T::new(4.0, -4.0));
+
+    // <a, a> is the squared norm
+    assert_eq!(a.vdot(&a).value(), T::new(6.0, 0.0));
+
+    let strided = NdArray::new([[T::new(1.0, 1.0), T::new(9.0, 9.0)], [T::new(0.0, 2.0), T::new(9.0, 9.0)]]);
+    let strided = strided.slice(s![.., 0]);
+    assert_eq!(strided.vdot(&b).value(), T::new(4.0, -4.0));
+});
+
+test_for_complex_dtypes!(test_complex_matmul, {
+    let i = T::new(0.0, 1.0);
+    let one = T::new(1.0, 0.0);
+    let zero = T::new(0.0, 0.0);
+
+    // Pauli matrices
+    let x = NdArray::new([[zero, one], [one, zero]]);
+    let y = NdArray::new([[zero, -i], [i, zero]]);
+    let z = NdArray::new([[one, zero], [zero, -one]]);
+
+    // XY = iZ
+    assert_eq!(x.matmul(&y), &z * i);
+    assert_eq!(y.matmul(&y), NdArray::new([[one, zero], [zero, one]]));
+
+    let v = NdArray::new([one, i]);
+    assert_eq!(y.matmul(&v), NdArray::new([one, i]));
+
+    let batch = NdArray::new([[[zero, one], [one, zero]], [[one, zero], [zero, -one]]]);
+    assert_eq!(batch.bmm(&batch), NdArray::new([[[one, zero], [zero, one]], [[one, zero], [zero, one]]]));
+});
+
+test_for_complex_dtypes!(test_complex_einsum, {
+    let a = NdArray::new([[T::new(1.0, 1.0), T::new(2.0, 0.0)], [T::new(0.0, -1.0), T::new(3.0, 2.0)]]);
+    let b = NdArray::new([T::new(1.0, 0.0), T::new(0.0, 1.0)]);
+
+    assert_eq!(einsum([&a, &b], (["ij", "j"], "i")), a.matmul(&b));
+    assert_eq!(einsum([&a], (["ii"], "")).value(), T::new(4.0, 3.0));
+    assert_eq!(einsum([&a, &a], (["ij", "ij"], "")).value(), T::new(8.0, 14.0));
+});
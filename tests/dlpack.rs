@@ -0,0 +1,180 @@
+use num::complex::Complex32;
+use redstone_ml::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn test_to_dlpack() {
+    let array = NdArray::new([[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]]).T();
+    let managed = array.to_dlpack();
+
+    let tensor = unsafe { &(*managed).dl_tensor };
+    assert_eq!(tensor.device, DLDevice { device_type: 1, device_id: 0 });
+    assert_eq!(tensor.dtype, DLDataType { code: 2, bits: 64, lanes: 1 });
+    assert_eq!(tensor.ndim, 2);
+    assert_eq!(unsafe { std::slice::from_raw_parts(tensor.shape, 2) }, &[3, 2]);
+    assert_eq!(unsafe { std::slice::from_raw_parts(tensor.strides, 2) }, &[1, 3]);
+    assert_eq!(unsafe { *(tensor.data as *const f64).add(3) }, 4.0);
+
+    unsafe { ((*managed).deleter.unwrap())(managed) };
+}
+
+#[test]
+fn test_dlpack_dtypes() {
+    fn dtype_of<T: RawDataType>(array: NdArray<'static, T>) -> DLDataType {
+        let tensor = unsafe { DLPackTensor::from_raw(array.to_dlpack()) };
+        tensor.dl_tensor().dtype
+    }
+
+    assert_eq!(dtype_of(NdArray::new([1u8])), DLDataType { code: 1, bits: 8, lanes: 1 });
+    assert_eq!(dtype_of(NdArray::new([1i16])), DLDataType { code: 0, bits: 16, lanes: 1 });
+    assert_eq!(dtype_of(NdArray::new([1u128])), DLDataType { code: 1, bits: 128, lanes: 1 });
+    assert_eq!(dtype_of(NdArray::new([half::f16::ONE])), DLDataType { code: 2, bits: 16, lanes: 1 });
+    assert_eq!(dtype_of(NdArray::new([half::bf16::ONE])), DLDataType { code: 4, bits: 16, lanes: 1 });
+    assert_eq!(dtype_of(NdArray::new([Complex32::new(1.0, 2.0)])), DLDataType { code: 5, bits: 64, lanes: 1 });
+    assert_eq!(dtype_of(NdArray::new([true])), DLDataType { code: 6, bits: 8, lanes: 1 });
+}
+
+#[test]
+fn test_dlpack_round_trip() {
+    let array = NdArray::<i64>::arange(0, 24).reshape([2, 3, 4]);
+    let expected = array.clone();
+
+    let mut tensor = unsafe { DLPackTensor::from_raw(array.to_dlpack()) };
+    let view = NdArray::<i64>::from_dlpack(&tensor).unwrap();
+    assert!(view.is_view());
+    assert_eq!(view, expected);
+    assert_eq!(view.sum_along(2), expected.sum_along(2));
+    drop(view);
+
+    let mut view = NdArray::<i64>::from_dlpack_mut(&mut tensor).unwrap();
+    view += 1;
+    drop(view);
+    assert_eq!(NdArray::<i64>::from_dlpack(&tensor).unwrap(), expected + 1);
+
+    let result = NdArray::<i32>::from_dlpack(&tensor);
+    assert_eq!(result.unwrap_err(), ConversionError::DtypeMismatch { expected: DType::I32, found: "int64".into() });
+
+    // scalars have no dimensions
+    let tensor = unsafe { DLPackTensor::from_raw(NdArray::scalar(2.5f32).to_dlpack()) };
+    assert_eq!(NdArray::<f32>::from_dlpack(&tensor).unwrap(), NdArray::scalar(2.5));
+}
+
+static DELETED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn count_deletions(_: *mut DLManagedTensor) {
+    DELETED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn test_from_dlpack_foreign() {
+    // a column-major matrix with an offset, as a foreign producer might share it
+    let mut data = [0u16, 1, 4, 2, 5, 3, 6];
+    let mut shape = [2i64, 3];
+    let mut strides = [1i64, 2];
+
+    let mut managed = DLManagedTensor {
+        dl_tensor: DLTensor {
+            data: data.as_mut_ptr() as *mut _,
+            device: DLDevice { device_type: 1, device_id: 0 },
+            ndim: 2,
+            dtype: DLDataType { code: 1, bits: 16, lanes: 1 },
+            shape: shape.as_mut_ptr(),
+            strides: strides.as_mut_ptr(),
+            byte_offset: 2,
+        },
+        manager_ctx: std::ptr::null_mut(),
+        deleter: Some(count_deletions),
+    };
+
+    let managed: *mut DLManagedTensor = &mut managed;
+    let tensor = unsafe { DLPackTensor::from_raw(managed) };
+    let view = NdArray::<u16>::from_dlpack(&tensor).unwrap();
+    assert_eq!(view, NdArray::new([[1, 2, 3], [4, 5, 6]]));
+    drop(view);
+
+    drop(tensor);
+    assert_eq!(DELETED.load(Ordering::SeqCst), 1);
+
+    // contiguous tensors may omit their strides
+    unsafe { (*managed).dl_tensor.strides = std::ptr::null_mut() };
+    let tensor = unsafe { DLPackTensor::from_raw(managed) };
+    assert_eq!(NdArray::<u16>::from_dlpack(&tensor).unwrap(), NdArray::new([[1, 4, 2], [5, 3, 6]]));
+
+    // unaligned data
+    unsafe { (*managed).dl_tensor.byte_offset = 1 };
+    assert_eq!(NdArray::<u16>::from_dlpack(&tensor).unwrap_err(), ConversionError::IncompatibleLayout);
+    unsafe { (*managed).dl_tensor.byte_offset = 2 };
+
+    // device memory
+    unsafe { (*managed).dl_tensor.device.device_type = 2 };
+    assert_eq!(NdArray::<u16>::from_dlpack(&tensor).unwrap_err(), ConversionError::UnsupportedDevice(2));
+
+    // ownership can be released without deleting the tensor
+    assert_eq!(tensor.into_raw(), managed);
+    assert_eq!(DELETED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_from_dlpack_strided() {
+    // every other element of a vector
+    let mut data = [1i32, 2, 3];
+    let mut shape = [2i64];
+    let mut strides = [2i64];
+
+    let mut managed = DLManagedTensor {
+        dl_tensor: DLTensor {
+            data: data.as_mut_ptr() as *mut _,
+            device: DLDevice { device_type: 1, device_id: 0 },
+            ndim: 1,
+            dtype: DLDataType { code: 0, bits: 32, lanes: 1 },
+            shape: shape.as_mut_ptr(),
+            strides: strides.as_mut_ptr(),
+            byte_offset: 0,
+        },
+        manager_ctx: std::ptr::null_mut(),
+        deleter: None,
+    };
+
+    let mut tensor = unsafe { DLPackTensor::from_raw(&mut managed) };
+    let view = NdArray::<i32>::from_dlpack(&tensor).unwrap();
+    assert_eq!(view[0], 1);
+    assert_eq!(view[1], 3);
+    drop(view);
+
+    let mut view = NdArray::<i32>::from_dlpack_mut(&mut tensor).unwrap();
+    view[1] = 30;
+    drop(view);
+    assert_eq!(NdArray::<i32>::from_dlpack(&tensor).unwrap(), NdArray::new([1, 30]));
+
+    drop(tensor);
+    assert_eq!(data, [1, 2, 30]);
+}
+
+#[test]
+fn test_from_dlpack_invalid_bool() {
+    let mut data = [0u8, 1, 2];
+    let mut shape = [3i64];
+
+    let mut managed = DLManagedTensor {
+        dl_tensor: DLTensor {
+            data: data.as_mut_ptr() as *mut _,
+            device: DLDevice { device_type: 1, device_id: 0 },
+            ndim: 1,
+            dtype: DLDataType { code: 6, bits: 8, lanes: 1 },
+            shape: shape.as_mut_ptr(),
+            strides: std::ptr::null_mut(),
+            byte_offset: 0,
+        },
+        manager_ctx: std::ptr::null_mut(),
+        deleter: None,
+    };
+
+    let tensor = unsafe { DLPackTensor::from_raw(&mut managed) };
+    assert!(matches!(NdArray::<bool>::from_dlpack(&tensor), Err(ConversionError::InvalidData(_))));
+    drop(tensor);
+
+    let mut shape = [2i64];
+    managed.dl_tensor.shape = shape.as_mut_ptr();
+    let tensor = unsafe { DLPackTensor::from_raw(&mut managed) };
+    assert_eq!(NdArray::<bool>::from_dlpack(&tensor).unwrap(), NdArray::new([false, true]));
+}
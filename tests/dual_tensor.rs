@@ -0,0 +1,129 @@
+use redstone_ml::*;
+
+/// Checks that the forward-mode result of `dual_func` matches the reverse-mode result of `func`.
+fn check_jvp(func: impl Fn(&Tensor<'static, f64>) -> Tensor<'static, f64>,
+             dual_func: impl Fn(&DualTensor<'static, f64>) -> DualTensor<'static, f64>,
+             input: NdArray<'static, f64>,
+             tangent: NdArray<'static, f64>) {
+    let expected_primal = func(&Tensor::from(input.clone())).into_ndarray();
+
+    // the Jacobian is shaped like the output followed by the input
+    let jacobian = functional::jacobian(|x| func(&x), &input);
+    let expected_jvp = (&jacobian).reshape([expected_primal.size(), input.size()])
+                               .matmul((&tangent).reshape([input.size()]))
+                               .reshape(expected_primal.shape());
+
+    let (primal, jvp) = dual_func(&DualTensor::new(input, tangent)).into_parts();
+
+    assert_almost_eq!(primal, expected_primal);
+    assert_almost_eq!(jvp, expected_jvp);
+}
+
+fn input() -> NdArray<'static, f64> {
+    NdArray::new([[1.0, -2.0, 3.0], [0.5, 4.0, -1.5]])
+}
+
+fn tangent() -> NdArray<'static, f64> {
+    NdArray::new([[0.5, 1.0, -1.0], [2.0, 0.0, 1.5]])
+}
+
+#[test]
+fn test_dual_constructors() {
+    let x = DualTensor::new(NdArray::new([1.0, 2.0]), NdArray::new([3.0, 4.0]));
+    assert_eq!(x.primal(), &NdArray::new([1.0, 2.0]));
+    assert_eq!(x.tangent(), &NdArray::new([3.0, 4.0]));
+
+    let c = DualTensor::constant(NdArray::new([[1.0f32, 2.0]]));
+    assert_eq!(c.shape(), &[1, 2]);
+    assert_eq!(c.tangent(), &NdArray::zeros([1, 2]));
+
+    // the primal and tangent are given the same layout
+    let primal = NdArray::new([[1.0, 2.0], [3.0, 4.0]]);
+    let x = DualTensor::new(primal.T(), NdArray::new([[1.0, 0.0], [0.0, 0.0]]));
+    assert_eq!(x.stride(), x.tangent().stride());
+
+    let x = x.reshape([4]);
+    assert_eq!(x.primal(), &NdArray::new([1.0, 3.0, 2.0, 4.0]));
+    assert_eq!(x.tangent(), &NdArray::new([1.0, 0.0, 0.0, 0.0]));
+}
+
+#[test]
+#[should_panic]
+fn test_dual_shape_mismatch() {
+    let _ = DualTensor::new(NdArray::new([1.0, 2.0]), NdArray::new([1.0, 2.0, 3.0]));
+}
+
+#[test]
+fn test_dual_binary_ops() {
+    check_jvp(
+        |x| {
+            let c = Tensor::new([2.0, -1.0, 0.5]);
+            (x * x + x / (x * 2.0 + 10.0)) * &c - x + 3.0
+        },
+        |x| {
+            let c = DualTensor::constant(NdArray::new([2.0, -1.0, 0.5]));
+            (x * x + x / (x * 2.0 + 10.0)) * &c - x + 3.0
+        },
+        input(), tangent(),
+    );
+
+    check_jvp(|x| -(x / 4.0) - 1.0, |x| -(x / 4.0) - 1.0, input(), tangent());
+    check_jvp(|x| Tensor::scalar(2.0) / x, |x| DualTensor::constant(NdArray::scalar(2.0)) / x, input(), tangent());
+}
+
+#[test]
+fn test_dual_matrix_ops() {
+    let a = [[1.0, 2.0], [-3.0, 0.5], [2.0, 1.0]];
+    check_jvp(|x| x.matmul(Tensor::new(a)), |x| x.matmul(DualTensor::constant(NdArray::new(a))), input(), tangent());
+    check_jvp(|x| x.matmul(x.T()), |x| x.matmul(x.T()), input(), tangent());
+    check_jvp(|x| x.T().matmul(x.reshape([6]).reshape([2, 3])), |x| x.T().matmul(x.reshape([6]).reshape([2, 3])), input(), tangent());
+
+    let v = [1.0, -1.0, 2.0];
+    check_jvp(|x| x.matmul(Tensor::new(v)), |x| x.matmul(DualTensor::constant(NdArray::new(v))), input(), tangent());
+
+    let x = NdArray::new([1.0, 2.0, -1.0]);
+    let t = NdArray::new([0.5, 0.0, 1.0]);
+    check_jvp(|x| x.dot(x), |x| x.dot(x), x, t);
+
+    let x = NdArray::new([[[1.0, 2.0], [3.0, 4.0]], [[-1.0, 0.5], [2.0, -2.0]]]);
+    let t = NdArray::new([[[0.5, 0.0], [1.0, 1.0]], [[2.0, 0.5], [-1.0, 0.0]]]);
+    check_jvp(|x| x.bmm(x.transpose(1, 2)), |x| x.bmm(x.transpose(1, 2)), x, t);
+}
+
+#[test]
+fn test_dual_reductions() {
+    check_jvp(|x| x.sum(), |x| x.sum(), input(), tangent());
+    check_jvp(|x| x.sum_along(0) * x.sum_along(1).sum(), |x| x.sum_along(0) * x.sum_along(1).sum(), input(), tangent());
+    check_jvp(|x| x * x.sum_keepdims(1), |x| x * x.sum_keepdims(1), input(), tangent());
+    check_jvp(|x| x.cumsum(1) * x, |x| x.cumsum(1) * x, input(), tangent());
+    check_jvp(|x| x.cumsum(-2), |x| x.cumsum(-2), input(), tangent());
+}
+
+#[test]
+fn test_dual_statistics() {
+    check_jvp(|x| x.var(1), |x| x.var(1), input(), tangent());
+    check_jvp(|x| x.var_along(1, 0), |x| x.var_along(1, 0), input(), tangent());
+    check_jvp(|x| x.var_keepdims(0, 0) * x, |x| x.var_keepdims(0, 0) * x, input(), tangent());
+    check_jvp(|x| x.std(0), |x| x.std(0), input(), tangent());
+    check_jvp(|x| x.std_along(0, 1), |x| x.std_along(0, 1), input(), tangent());
+    check_jvp(|x| x.std_keepdims(1, 0) + x, |x| x.std_keepdims(1, 0) + x, input(), tangent());
+
+    check_jvp(|x| x.logsumexp(), |x| x.logsumexp(), input(), tangent());
+    check_jvp(|x| x.logsumexp_along(1), |x| x.logsumexp_along(1), input(), tangent());
+    check_jvp(|x| x - x.logsumexp_keepdims(0), |x| x - x.logsumexp_keepdims(0), input(), tangent());
+}
+
+#[test]
+fn test_dual_norms() {
+    for ord in [Norm::L1, Norm::L2, Norm::Lp(3.0), Norm::Inf] {
+        check_jvp(|x| x.norm(ord), |x| x.norm(ord), input(), tangent());
+        check_jvp(|x| x.norm_along(ord, 1), |x| x.norm_along(ord, 1), input(), tangent());
+        check_jvp(|x| x / x.norm_keepdims(ord, 0), |x| x / x.norm_keepdims(ord, 0), input(), tangent());
+    }
+
+    // ties in the infinity norm and zero norms
+    let x = NdArray::new([[2.0, -2.0, 1.0], [0.0, 0.0, 0.0]]);
+    for ord in [Norm::L1, Norm::L2, Norm::Lp(3.0), Norm::Inf] {
+        check_jvp(|x| x.norm_along(ord, 1), |x| x.norm_along(ord, 1), x.clone(), tangent());
+    }
+}
@@ -0,0 +1,118 @@
+use num::NumCast;
+use paste::paste;
+use redstone_ml::*;
+
+test_for_half_dtypes!(test_half_astype, {
+    let a = NdArray::new([0.5f32, -1.25, 3.0, 1024.0]);
+
+    let b = a.astype::<T>();
+    assert_eq!(b.astype::<f32>(), a);
+    assert_eq!(b.astype::<i32>(), NdArray::new([0, -1, 3, 1024]));
+
+    let c = NdArray::new([1u8, 2, 3]).astype::<T>();
+    assert_eq!(c.astype::<f64>(), NdArray::new([1.0, 2.0, 3.0]));
+});
+
+test_for_half_dtypes!(test_half_constructors, {
+    let zeros = NdArray::<T>::zeros([2, 3]);
+    let ones = NdArray::<T>::ones([2, 3]);
+
+    assert_eq!(zeros.astype::<f32>(), NdArray::<f32>::zeros([2, 3]));
+    assert_eq!(ones.astype::<f32>(), NdArray::<f32>::ones([2, 3]));
+});
+
+test_for_half_dtypes!(test_half_binary_ops, {
+    let a = NdArray::new([[1.0f32, 2.0], [3.0, 4.0]]).astype::<T>();
+    let b = NdArray::new([0.5f32, 2.0]).astype::<T>();
+
+    assert_eq!((&a + &b).astype::<f32>(), NdArray::new([[1.5, 4.0], [3.5, 6.0]]));
+    assert_eq!((&a - &b).astype::<f32>(), NdArray::new([[0.5, 0.0], [2.5, 2.0]]));
+    assert_eq!((&a * &b).astype::<f32>(), NdArray::new([[0.5, 4.0], [1.5, 8.0]]));
+    assert_eq!((&a / &b).astype::<f32>(), NdArray::new([[2.0, 1.0], [6.0, 2.0]]));
+    assert_eq!((-&a).astype::<f32>(), NdArray::new([[-1.0, -2.0], [-3.0, -4.0]]));
+
+    let two: T = NumCast::from(2.0).unwrap();
+    assert_eq!((&a * two).astype::<f32>(), NdArray::new([[2.0, 4.0], [6.0, 8.0]]));
+});
+
+test_for_half_dtypes!(test_half_reductions, {
+    let a = NdArray::new([[1.0f32, -5.0, 3.0], [4.0, 2.0, -6.0]]).astype::<T>();
+
+    assert_eq!(a.sum().astype::<f32>().value(), -1.0);
+    assert_eq!(a.sum_along(0).astype::<f32>(), NdArray::new([5.0, -3.0, -3.0]));
+    assert_eq!(a.product().astype::<f32>().value(), 720.0);
+    assert_eq!(a.min().astype::<f32>().value(), -6.0);
+    assert_eq!(a.max().astype::<f32>().value(), 4.0);
+    assert_eq!(a.max_magnitude().astype::<f32>().value(), 6.0);
+    assert_eq!(a.mean().value(), <T as NumCast>::from(-1.0 / 6.0f32).unwrap());
+    assert_eq!(a.argmax().value(), 3);
+    assert_eq!(a.argmin_along(1), NdArray::new([1, 2]));
+});
+
+test_for_half_dtypes!(test_half_sum_accumulates_in_f32, {
+    // 4096 ones cannot be summed exactly when accumulating in f16 or bf16
+    let a = NdArray::<T>::ones([4096]);
+    assert_eq!(a.sum().astype::<f32>().value(), 4096.0);
+});
+
+test_for_half_dtypes!(test_half_reductions_along_axes_accumulate_in_f32, {
+    // 4096 ones cannot be summed exactly when accumulating in f16 or bf16
+    let ones = NdArray::<T>::ones([2, 4096]);
+
+    assert_eq!(ones.sum_along(1).astype::<f32>(), NdArray::new([4096.0, 4096.0]));
+    assert_eq!(ones.mean_along(1).astype::<f32>(), NdArray::new([1.0, 1.0]));
+    assert_eq!(ones.mean().astype::<f32>().value(), 1.0);
+    assert_eq!(ones.T().sum_along(0).astype::<f32>(), NdArray::new([4096.0, 4096.0]));
+
+    // the partial products overflow f16 but not f32
+    let a = NdArray::new([[256.0f32, 256.0, 1.0 / 256.0, 1.0 / 256.0]]).astype::<T>();
+    assert_eq!(a.product_along(1).astype::<f32>(), NdArray::new([1.0]));
+
+    let a = NdArray::new([1.0f32, 3.0]).astype::<T>().broadcast_to(&[2048, 2]).clone().reshape([4096]);
+    assert_eq!(a.var_along(0, 0).astype::<f32>().value(), 1.0);
+});
+
+test_for_half_dtypes!(test_half_sort, {
+    let a = NdArray::new([3.0f32, f32::NAN, -1.0, 2.0]).astype::<T>();
+    let sorted = a.sort(0).astype::<f32>();
+
+    assert_eq!(sorted.slice(s![0..3]), NdArray::new([-1.0, 2.0, 3.0]));
+    assert!(sorted[3].is_nan());
+});
+
+test_for_half_dtypes!(test_half_matmul, {
+    let a = NdArray::new([[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0]]).astype::<T>();
+    let b = NdArray::new([[7.0f32, 8.0], [9.0, 10.0], [11.0, 12.0]]).astype::<T>();
+    let v = NdArray::new([1.0f32, 0.5, 2.0]).astype::<T>();
+
+    assert_eq!(a.matmul(&b).astype::<f32>(), NdArray::new([[58.0, 64.0], [139.0, 154.0]]));
+    assert_eq!(a.matmul(&v).astype::<f32>(), NdArray::new([8.0, 18.5]));
+    assert_eq!(v.dot(&v).astype::<f32>().value(), 5.25);
+
+    let batch = NdArray::new([[[1.0f32, 2.0], [3.0, 4.0]], [[0.5, 0.0], [0.0, 2.0]]]).astype::<T>();
+    assert_eq!(batch.bmm(&batch).astype::<f32>(),
+               NdArray::new([[[7.0, 10.0], [15.0, 22.0]], [[0.25, 0.0], [0.0, 4.0]]]));
+});
+
+test_for_half_dtypes!(test_half_matmul_accumulates_in_f32, {
+    let a = NdArray::<T>::ones([2, 4096]);
+    let b = NdArray::<T>::ones([4096, 3]);
+
+    assert_eq!(a.matmul(&b).astype::<f32>(), NdArray::full(4096.0, [2, 3]));
+    assert_eq!(a.slice(s![0]).dot(b.slice(s![.., 0])).astype::<f32>().value(), 4096.0);
+});
+
+test_for_half_dtypes!(test_half_autograd, {
+    let x = Tensor::new([[1.0f32, 2.0], [3.0, 4.0]].map(|row| row.map(|v| <T as NumCast>::from(v).unwrap())));
+    let mut w = Tensor::new([0.5f32, -1.0].map(|v| <T as NumCast>::from(v).unwrap()));
+    w.set_requires_grad(true);
+
+    // loss = sum(x @ w) via a dot product with a vector of ones
+    let y = x.matmul(&w);
+    let ones = Tensor::<T>::ones([2]);
+    let loss = y.dot(&ones);
+    loss.backward();
+
+    assert_eq!(loss.ndarray().astype::<f32>().value(), -4.0);
+    assert_eq!(w.gradient().unwrap().astype::<f32>(), NdArray::new([4.0, 6.0]));
+});
@@ -0,0 +1,155 @@
+use redstone_ml::init::*;
+use redstone_ml::*;
+
+fn mean(tensor: &Tensor<f64>) -> f64 {
+    tensor.ndarray().flatiter().sum::<f64>() / tensor.size() as f64
+}
+
+fn std(tensor: &Tensor<f64>) -> f64 {
+    let mean = mean(tensor);
+    (tensor.ndarray().flatiter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / tensor.size() as f64).sqrt()
+}
+
+fn assert_orthonormal_columns(matrix: &Tensor<f64>, scale: f64) {
+    let product = matrix.T().matmul(matrix);
+    let n = product.shape()[0];
+
+    for (i, value) in product.ndarray().flatiter().enumerate() {
+        let expected = if i / n == i % n { scale * scale } else { 0.0 };
+        assert!((value - expected).abs() < 1e-10, "{value} != {expected}");
+    }
+}
+
+#[test]
+fn test_calculate_fans() {
+    assert_eq!(calculate_fans(&[3, 5]), (5, 3));
+    assert_eq!(calculate_fans(&[16, 3, 5, 5]), (75, 400));
+    assert_eq!(calculate_fans(&[4, 2, 7]), (14, 28));
+}
+
+#[test]
+#[should_panic]
+fn test_calculate_fans_vector() {
+    calculate_fans(&[5]);
+}
+
+#[test]
+fn test_calculate_gain() {
+    assert_eq!(calculate_gain::<f64>(Nonlinearity::Linear), 1.0);
+    assert_eq!(calculate_gain::<f64>(Nonlinearity::Sigmoid), 1.0);
+    assert_eq!(calculate_gain::<f64>(Nonlinearity::Tanh), 5.0 / 3.0);
+    assert_eq!(calculate_gain::<f64>(Nonlinearity::Relu), 2.0f64.sqrt());
+    assert_eq!(calculate_gain::<f64>(Nonlinearity::LeakyRelu(0.2)), (2.0 / 1.04f64).sqrt());
+    assert_eq!(calculate_gain::<f64>(Nonlinearity::Selu), 0.75);
+}
+
+#[test]
+fn test_xavier() {
+    let mut generator = Generator::new(0);
+    let mut tensor = Tensor::<f64>::zeros([300, 200]);
+
+    xavier_uniform_with_(&mut generator, &mut tensor, 2.0);
+    let bound = 2.0 * (6.0 / 500.0f64).sqrt();
+    assert!(tensor.ndarray().flatiter().all(|x| x.abs() <= bound));
+    assert!((std(&tensor) - bound / 3.0f64.sqrt()).abs() < 0.005);
+
+    xavier_normal_with_(&mut generator, &mut tensor, 1.0);
+    assert!(mean(&tensor).abs() < 0.005);
+    assert!((std(&tensor) - (2.0 / 500.0f64).sqrt()).abs() < 0.002);
+}
+
+#[test]
+fn test_kaiming() {
+    let mut generator = Generator::new(1);
+    let mut tensor = Tensor::<f64>::zeros([64, 32, 3, 3]);
+
+    kaiming_uniform_with_(&mut generator, &mut tensor, FanMode::FanIn, Nonlinearity::Relu);
+    let bound = (2.0f64 * 3.0 / 288.0).sqrt();
+    assert!(tensor.ndarray().flatiter().all(|x| x.abs() <= bound));
+    assert!((std(&tensor) - bound / 3.0f64.sqrt()).abs() < 0.002);
+
+    kaiming_normal_with_(&mut generator, &mut tensor, FanMode::FanOut, Nonlinearity::Linear);
+    assert!(mean(&tensor).abs() < 0.002);
+    assert!((std(&tensor) - 1.0 / 576.0f64.sqrt()).abs() < 0.001);
+}
+
+#[test]
+fn test_orthogonal() {
+    let mut generator = Generator::new(2);
+
+    let mut tall = Tensor::<f64>::zeros([6, 4]);
+    orthogonal_with_(&mut generator, &mut tall, 1.0);
+    assert_orthonormal_columns(&tall, 1.0);
+
+    // wide matrices have orthonormal rows
+    let mut wide = Tensor::<f64>::zeros([3, 7]);
+    orthogonal_with_(&mut generator, &mut wide, 2.0);
+    assert_orthonormal_columns(&wide.T(), 2.0);
+
+    // trailing dimensions are flattened
+    let mut conv = Tensor::<f64>::zeros([4, 2, 3]);
+    orthogonal_with_(&mut generator, &mut conv, 1.0);
+    assert_orthonormal_columns(&conv.reshape([4, 6]).T(), 1.0);
+}
+
+#[test]
+fn test_trunc_normal() {
+    let mut generator = Generator::new(3);
+    let mut tensor = Tensor::<f32>::zeros([1000]);
+
+    trunc_normal_with_(&mut generator, &mut tensor, 1.0, 2.0, 0.5, 1.5);
+    assert!(tensor.ndarray().flatiter().all(|x| (0.5..=1.5).contains(&x)));
+
+    // bounds many standard deviations from the mean
+    trunc_normal_with_(&mut generator, &mut tensor, 0.0, 0.02, 0.2, 0.22);
+    assert!(tensor.ndarray().flatiter().all(|x| (0.2..=0.22).contains(&x)));
+
+    trunc_normal_with_(&mut generator, &mut tensor, 0.0, 1.0, -9.0, -8.0);
+    assert!(tensor.ndarray().flatiter().all(|x| (-9.0..=-8.0).contains(&x)));
+}
+
+#[test]
+fn test_constant_and_eye() {
+    let mut tensor = Tensor::<f32>::zeros([2, 3]);
+    constant_(&mut tensor, 0.5);
+    assert_eq!(tensor, Tensor::new([[0.5, 0.5, 0.5], [0.5, 0.5, 0.5]]));
+
+    let mut tensor = Tensor::<f64>::ones([3, 2]);
+    eye_(&mut tensor);
+    assert_eq!(tensor, Tensor::new([[1.0, 0.0], [0.0, 1.0], [0.0, 0.0]]));
+}
+
+#[test]
+#[should_panic]
+fn test_eye_not_matrix() {
+    eye_(&mut Tensor::<f64>::ones([2, 2, 2]));
+}
+
+#[test]
+fn test_init_reproducible() {
+    let mut a = Tensor::<f32>::zeros([5, 4]);
+    let mut b = Tensor::<f32>::zeros([5, 4]);
+
+    xavier_normal_with_(&mut Generator::new(9), &mut a, 1.0);
+    xavier_normal_with_(&mut Generator::new(9), &mut b, 1.0);
+    assert_eq!(a, b);
+
+    orthogonal_with_(&mut Generator::new(9), &mut a, 1.0);
+    orthogonal_with_(&mut Generator::new(9), &mut b, 1.0);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_init_not_tracked() {
+    let mut weight = Tensor::<f64>::zeros([4, 3]);
+    weight.set_requires_grad(true);
+
+    kaiming_uniform_with_(&mut Generator::new(4), &mut weight, FanMode::FanIn, Nonlinearity::Relu);
+    assert!(weight.requires_grad());
+    assert!(weight.is_leaf());
+
+    // the initialized parameter receives gradients as usual
+    let output = weight.sum();
+    output.backward();
+    assert_eq!(weight.gradient().unwrap(), NdArray::ones([4, 3]));
+}
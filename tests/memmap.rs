@@ -0,0 +1,125 @@
+use redstone_ml::*;
+use std::path::PathBuf;
+
+fn temp_file(name: &str, bytes: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("redstone_test_{}_{name}.bin", std::process::id()));
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+fn f64_bytes(values: impl IntoIterator<Item=f64>) -> Vec<u8> {
+    values.into_iter().flat_map(f64::to_ne_bytes).collect()
+}
+
+#[test]
+fn test_memmap_read_only() {
+    let path = temp_file("read_only", &f64_bytes((0..24).map(|x| x as f64)));
+    let file = MemmapFile::open(&path, MmapMode::ReadOnly).unwrap();
+    assert_eq!(file.len(), 24 * 8);
+    assert_eq!(file.mode(), MmapMode::ReadOnly);
+
+    let array = NdArray::<f64>::memmap(&file, 0, [2, 3, 4]).unwrap();
+    assert!(array.is_view());
+    assert_eq!(array, NdArray::<f64>::arange(0.0, 24.0).reshape([2, 3, 4]));
+
+    // views of the mapping behave like any other array
+    assert_eq!(array.slice(s![1, .., 0]), NdArray::new([12.0, 16.0, 20.0]));
+    assert_eq!(array.sum_along(0), NdArray::<f64>::arange(0.0, 12.0).reshape([3, 4]) * 2.0 + 12.0);
+    assert_eq!(array.flatiter().filter(|&x| x >= 20.0).count(), 4);
+    assert_eq!(array.max(), NdArray::scalar(23.0));
+
+    // arrays can start partway through the file
+    let tail = NdArray::<f64>::memmap(&file, 8 * 20, [2, 2]).unwrap();
+    assert_eq!(tail, NdArray::new([[20.0, 21.0], [22.0, 23.0]]));
+
+    drop((array, tail));
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_memmap_read_only_is_not_writeable() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let path = temp_file("not_writeable", &f64_bytes([1.0, 2.0, 3.0, 4.0]));
+    let file = MemmapFile::open(&path, MmapMode::ReadOnly).unwrap();
+    let mut array = NdArray::<f64>::memmap(&file, 0, [2, 2]).unwrap();
+
+    // writing to a read-only mapping would fault, so every in-place operation panics instead
+    let mut assert_panics = |mutate: fn(&mut NdArray<f64>)| {
+        assert!(catch_unwind(AssertUnwindSafe(|| mutate(&mut array))).is_err());
+    };
+
+    assert_panics(|array| array[[0, 0]] = 9.0);
+    assert_panics(|array| array.fill(9.0));
+    assert_panics(|array| array.zero());
+    assert_panics(|array| array.shuffle_along(0));
+    assert_panics(|array| *array += 1.0);
+    assert_panics(|array| *array *= &NdArray::new([2.0, 2.0]));
+    assert_eq!(array, NdArray::new([[1.0, 2.0], [3.0, 4.0]]));
+
+    drop(array);
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_memmap_copy_on_write() {
+    let path = temp_file("copy_on_write", &f64_bytes([1.0, 2.0, 3.0, 4.0]));
+    let mut file = MemmapFile::open(&path, MmapMode::CopyOnWrite).unwrap();
+
+    let mut array = NdArray::<f64>::memmap_mut(&mut file, 0, [4]).unwrap();
+    array *= 2.0;
+    array -= NdArray::new([3.0, 0.0, 0.0, 0.0]);
+    assert_eq!(array, NdArray::new([-1.0, 4.0, 6.0, 8.0]));
+    drop(array);
+
+    // the changes are visible through the mapping but not in the file
+    assert_eq!(NdArray::<f64>::memmap(&file, 0, [4]).unwrap(), NdArray::new([-1.0, 4.0, 6.0, 8.0]));
+    assert_eq!(std::fs::read(&path).unwrap(), f64_bytes([1.0, 2.0, 3.0, 4.0]));
+
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_memmap_errors() {
+    let path = temp_file("errors", &[0, 1, 2, 3, 4, 5, 6, 7]);
+    let mut file = MemmapFile::open(&path, MmapMode::ReadOnly).unwrap();
+
+    // too large for the file
+    let result = NdArray::<u32>::memmap(&file, 4, [2]);
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    assert!(NdArray::<u8>::memmap(&file, 0, [usize::MAX, 2]).is_err());
+
+    // misaligned
+    assert!(NdArray::<u32>::memmap(&file, 1, [1]).is_err());
+    assert_eq!(NdArray::<u8>::memmap(&file, 1, [7]).unwrap().sum(), NdArray::scalar(28));
+
+    // invalid booleans
+    assert!(NdArray::<bool>::memmap(&file, 0, [2]).is_ok());
+    assert!(NdArray::<bool>::memmap(&file, 0, [3]).is_err());
+
+    // read-only files cannot be viewed mutably
+    let result = NdArray::<u8>::memmap_mut(&mut file, 0, [8]);
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::PermissionDenied);
+
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+    assert!(MemmapFile::open(&path, MmapMode::ReadOnly).is_err());
+}
+
+#[test]
+fn test_memmap_empty() {
+    let path = temp_file("empty", &[]);
+    let file = MemmapFile::open(&path, MmapMode::ReadOnly).unwrap();
+    assert!(file.is_empty());
+
+    let array = NdArray::<f32>::memmap(&file, 0, [0, 3]).unwrap();
+    assert_eq!(array.shape(), &[0usize, 3]);
+    assert!(NdArray::<f32>::memmap(&file, 0, [1]).is_err());
+
+    drop(array);
+    drop(file);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "nalgebra")]
+
+use nalgebra::{dmatrix, DMatrix, DMatrixView, DMatrixViewMut, Dyn};
+use redstone_ml::*;
+
+#[test]
+fn test_ndarray_to_matrix_view() {
+    let array = NdArray::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+
+    let matrix = DMatrixView::try_from(&array).unwrap();
+    assert_eq!(matrix, dmatrix![1.0, 2.0, 3.0; 4.0, 5.0, 6.0]);
+    assert_eq!(matrix.strides(), (3, 1));
+
+    // strided views keep their layout
+    let transposed = (&array).T();
+    let matrix = transposed.as_matrix_view().unwrap();
+    assert_eq!(matrix, dmatrix![1.0, 4.0; 2.0, 5.0; 3.0, 6.0]);
+    assert_eq!(matrix.strides(), (1, 3));
+
+    let product = array.as_matrix_view().unwrap().into_owned() * transposed.as_matrix_view().unwrap().into_owned();
+    assert_eq!(NdArray::from(product), array.matmul(&transposed));
+
+    let vector = NdArray::new([1.0, 2.0]);
+    assert_eq!(vector.as_matrix_view().unwrap_err(), ConversionError::Dimensionality { expected: 2, found: 1 });
+}
+
+#[test]
+fn test_ndarray_to_matrix_view_mut() {
+    let mut array = NdArray::<f32>::zeros([2, 2]);
+
+    let mut matrix = DMatrixViewMut::try_from(&mut array).unwrap();
+    matrix.fill_with_identity();
+    matrix[(0, 1)] = 5.0;
+    assert_eq!(array, NdArray::new([[1.0, 5.0], [0.0, 1.0]]));
+
+    let source = NdArray::new([1.0f32, 2.0]);
+    let mut broadcast = source.broadcast_to(&[2, 2]);
+    assert_eq!(broadcast.as_matrix_view_mut().unwrap_err(), ConversionError::ReadOnly);
+}
+
+#[test]
+fn test_matrix_into_ndarray() {
+    let matrix = dmatrix![1, 2, 3; 4, 5, 6];
+
+    // borrowed matrices are viewed in column-major order
+    let view = NdArray::from(&matrix);
+    assert!(view.is_view());
+    assert_eq!(view.stride(), &[1, 2]);
+    assert_eq!(view, NdArray::new([[1, 2, 3], [4, 5, 6]]));
+
+    let column = NdArray::from(matrix.column(2));
+    assert_eq!(column, NdArray::new([[3], [6]]));
+
+    // owned matrices are moved without copying
+    let owned = NdArray::from(matrix.clone());
+    assert!(!owned.is_view());
+    assert_eq!(owned.sum(), NdArray::scalar(21));
+    assert_eq!(DMatrix::try_from(owned).unwrap(), matrix);
+
+    let empty = NdArray::from(DMatrix::<f64>::zeros(0, 3));
+    assert_eq!(empty.shape(), &[0, 3]);
+}
+
+#[test]
+fn test_matrix_view_mut_into_ndarray() {
+    let mut matrix = DMatrix::<i64>::zeros(3, 3);
+
+    let mut block = NdArray::from(matrix.view_mut((1, 1), (2, 2)));
+    block += 7;
+    block[[1, 1]] = 8;
+    drop(block);
+    assert_eq!(matrix, dmatrix![0, 0, 0; 0, 7, 7; 0, 7, 8]);
+
+    // submatrices have gaps between their columns but can be indexed throughout
+    let block = NdArray::from(matrix.view((0, 0), (2, 2)));
+    assert_eq!(block.stride(), &[1, 3]);
+    assert_eq!(block[[1, 1]], 7);
+    assert_eq!(block[[0, 1]], 0);
+    drop(block);
+
+    let view: DMatrixView<i64, Dyn, Dyn> = matrix.view_with_steps((0, 0), (3, 3), (0, 0));
+    let array = NdArray::from(view).T().sum_along(0);
+    assert_eq!(array, NdArray::new([0, 14, 15]));
+}
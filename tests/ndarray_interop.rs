@@ -0,0 +1,109 @@
+#![cfg(feature = "ndarray")]
+
+use ndarray_rs::{array, s as nd_s, Array, ArrayD, ArrayViewD, ArrayViewMutD, Axis as NdAxis, ShapeBuilder};
+use redstone_ml::*;
+
+#[test]
+fn test_ndarray_to_array_view() {
+    let array = NdArray::<f64>::arange(0.0, 24.0).reshape([2, 3, 4]);
+
+    let view = ArrayViewD::from(&array);
+    assert_eq!(view.shape(), &[2, 3, 4]);
+    assert_eq!(view.as_ptr(), array.data_slice().as_ptr());
+    assert_eq!(view[[1, 2, 3]], 23.0);
+
+    // strided views share their strides
+    let transposed = array.transpose(0, 2);
+    let view = transposed.as_array_view();
+    assert_eq!(view.shape(), &[4, 3, 2]);
+    assert_eq!(view.strides(), &[1, 4, 12]);
+    assert_eq!(view.sum_axis(NdAxis(2)), transposed.sum_along(2).as_array_view());
+
+    // broadcast arrays have zero strides
+    let source = NdArray::new([1, 2, 3]);
+    let broadcast = source.broadcast_to(&[2, 3]);
+    let view = broadcast.as_array_view();
+    assert_eq!(view, array![[1, 2, 3], [1, 2, 3]].into_dyn());
+}
+
+#[test]
+fn test_ndarray_to_array_view_mut() {
+    let mut array = NdArray::new([[1, 2], [3, 4]]);
+
+    let mut view = ArrayViewMutD::try_from(&mut array).unwrap();
+    view[[0, 1]] = 20;
+    view.slice_mut(nd_s![1, ..]).fill(0);
+    assert_eq!(array, NdArray::new([[1, 20], [0, 0]]));
+
+    let source = NdArray::new([1, 2]);
+    let mut broadcast = source.broadcast_to(&[2, 2]);
+    assert_eq!(broadcast.as_array_view_mut().unwrap_err(), ConversionError::ReadOnly);
+}
+
+#[test]
+fn test_ndarray_into_owned_array() {
+    let array = ArrayD::from(NdArray::new([[1, 2, 3], [4, 5, 6]]));
+    assert_eq!(array, array![[1, 2, 3], [4, 5, 6]].into_dyn());
+
+    let transposed = ArrayD::from(NdArray::new([[1, 2, 3], [4, 5, 6]]).T());
+    assert_eq!(transposed, array![[1, 4], [2, 5], [3, 6]].into_dyn());
+
+    let source = NdArray::new([1.0, 2.0, 3.0]);
+    assert_eq!(ArrayD::from(source.slice(s![1..])), array![2.0, 3.0].into_dyn());
+}
+
+#[test]
+fn test_owned_array_into_ndarray() {
+    let array = NdArray::from(array![[1.0f32, 2.0], [3.0, 4.0]]);
+    assert!(!array.is_view());
+    assert_eq!(array, NdArray::new([[1.0, 2.0], [3.0, 4.0]]));
+
+    // column-major arrays are copied into row-major order
+    let fortran = Array::from_shape_vec((2, 3).f(), vec![1, 4, 2, 5, 3, 6]).unwrap();
+    assert_eq!(NdArray::from(fortran), NdArray::new([[1, 2, 3], [4, 5, 6]]));
+
+    // arrays which do not start their buffer
+    let mut sliced = array![1, 2, 3, 4];
+    sliced.slice_collapse(nd_s![1..3]);
+    assert_eq!(NdArray::from(sliced), NdArray::new([2, 3]));
+
+    assert_eq!(NdArray::from(array![7u8].into_shape_with_order(()).unwrap()), NdArray::scalar(7));
+}
+
+#[test]
+fn test_array_view_into_ndarray() {
+    let source = Array::from_shape_vec((3, 4), (0..12).collect()).unwrap();
+
+    let column = NdArray::from(source.column(1));
+    assert!(column.is_view());
+    assert_eq!(column, NdArray::new([1, 5, 9]));
+    assert_eq!(column.stride(), &[4]);
+    assert_eq!(column[2], 9);
+
+    // views with gaps between their elements can be indexed throughout
+    let stepped = NdArray::from(source.slice(nd_s![..;2, 1..;2]));
+    assert_eq!(stepped.stride(), &[8, 2]);
+    assert_eq!(stepped[[1, 1]], 11);
+    assert_eq!(stepped[[0, 1]], 3);
+
+    let vector = array![1, 2, 3];
+    let every_other = NdArray::from(vector.slice(nd_s![..;2]));
+    assert_eq!(every_other[1], 3);
+
+    // negative strides are copied
+    let reversed = source.slice(nd_s![..;-1, ..]);
+    let array = NdArray::from(reversed);
+    assert!(!array.is_view());
+    assert_eq!(array.slice_along(Axis(0), 0), NdArray::new([8, 9, 10, 11]));
+    drop((column, stepped, every_other, array));
+
+    let mut source = source;
+    let mut view = NdArray::try_from(source.slice_mut(nd_s![.., 3])).unwrap();
+    view[2] = 11;
+    view += 100;
+    drop(view);
+    assert_eq!(source.column(3).to_vec(), vec![103, 107, 111]);
+
+    let result = NdArray::try_from(source.slice_mut(nd_s![..;-1, 0]));
+    assert_eq!(result.unwrap_err(), ConversionError::IncompatibleLayout);
+}
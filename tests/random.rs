@@ -0,0 +1,234 @@
+use redstone_ml::*;
+use rand::distributions::{Distribution, Uniform};
+
+// the default generator is shared by the whole process,
+// so only one test in this binary may use it
+
+#[test]
+fn test_generator_reproducible() {
+    let mut generator1 = Generator::new(42);
+    let mut generator2 = Generator::new(42);
+
+    assert_eq!(NdArray::<f64>::randn_with(&mut generator1, [3, 4]), NdArray::<f64>::randn_with(&mut generator2, [3, 4]));
+    assert_eq!(Tensor::<f32>::rand_with(&mut generator1, [5]), Tensor::<f32>::rand_with(&mut generator2, [5]));
+    assert_eq!(NdArray::<i32>::randint_with(&mut generator1, [8], -3, 3), NdArray::<i32>::randint_with(&mut generator2, [8], -3, 3));
+
+    // different seeds give different numbers
+    let mut generator3 = Generator::new(43);
+    assert_ne!(NdArray::<f64>::rand_with(&mut generator1, [4]), NdArray::<f64>::rand_with(&mut generator3, [4]));
+    assert_eq!(generator3.initial_seed(), 43);
+}
+
+#[test]
+fn test_generator_golden_values() {
+    // the sequence for a seed must not change across runs, platforms or releases
+    let mut generator = Generator::new(0);
+
+    assert_eq!(NdArray::<f64>::rand_with(&mut generator, [3]),
+               NdArray::new([0.7311134158637045, 0.773460184353238, 0.025844634233354924]));
+    assert_eq!(NdArray::<f32>::randn_with(&mut generator, [3]),
+               NdArray::new([0.16334426, -1.2750102, 1.287171]));
+    assert_eq!(NdArray::<i32>::randint_with(&mut generator, [6], -5, 5),
+               NdArray::new([-4, -3, 3, 3, 1, 2]));
+}
+
+#[test]
+fn test_generator_state() {
+    let mut generator = Generator::new(3);
+    let _ = NdArray::<f64>::randn_with(&mut generator, [7]);
+
+    let state = generator.get_state();
+    let a = NdArray::<f64>::uniform_with(&mut generator, [10], -2.0, 2.0);
+
+    generator.set_state(&state);
+    let b = NdArray::<f64>::uniform_with(&mut generator, [10], -2.0, 2.0);
+    assert_eq!(a, b);
+
+    // states survive a round trip through bytes and can be restored into any generator
+    let restored = GeneratorState::from_bytes(&state.to_bytes());
+    assert_eq!(restored, state);
+
+    let mut other = Generator::new(100);
+    other.set_state(&restored);
+    assert_eq!(other.initial_seed(), 3);
+    assert_eq!(NdArray::<f64>::uniform_with(&mut other, [10], -2.0, 2.0), a);
+
+    // reseeding restarts the sequence
+    generator.manual_seed(3);
+    let c = NdArray::<f64>::randn_with(&mut generator, [7]);
+    generator.manual_seed(3);
+    assert_eq!(NdArray::<f64>::randn_with(&mut generator, [7]), c);
+}
+
+#[test]
+fn test_manual_seed() {
+    manual_seed(1234);
+    assert_eq!(initial_seed(), 1234);
+    let a = NdArray::<f32>::randn([2, 3]);
+    let b = Tensor::<f64>::rand([4]);
+
+    manual_seed(1234);
+    assert_eq!(NdArray::<f32>::randn([2, 3]), a);
+    assert_eq!(Tensor::<f64>::rand([4]), b);
+
+    // the default generator matches an explicit generator with the same seed
+    let mut generator = Generator::new(1234);
+    assert_eq!(NdArray::<f32>::randn_with(&mut generator, [2, 3]), a);
+
+    let state = get_rng_state();
+    let c = NdArray::<i64>::randint([5], 0, 100);
+    set_rng_state(&state);
+    assert_eq!(NdArray::<i64>::randint([5], 0, 100), c);
+}
+
+fn mean(array: &NdArray<f64>) -> f64 {
+    array.flatiter().sum::<f64>() / array.size() as f64
+}
+
+fn variance(array: &NdArray<f64>) -> f64 {
+    let mean = mean(array);
+    array.flatiter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / array.size() as f64
+}
+
+#[test]
+fn test_continuous_distributions() {
+    let mut generator = Generator::new(0);
+    let n = 100_000;
+
+    let normal = NdArray::<f64>::normal_with(&mut generator, [n], 3.0, 2.0);
+    assert!((mean(&normal) - 3.0).abs() < 0.05);
+    assert!((variance(&normal) - 4.0).abs() < 0.1);
+
+    let truncated = NdArray::<f64>::truncated_normal_with(&mut generator, [n], 0.0, 1.0, -1.0, 2.0);
+    assert!(truncated.flatiter().all(|x| (-1.0..=2.0).contains(&x)));
+    assert!((mean(&truncated) - 0.2296).abs() < 0.01);
+
+    // bounds far in either tail do not need many redraws
+    let truncated = NdArray::<f64>::truncated_normal_with(&mut generator, [n], 5.0, 2.0, 25.0, 27.0);
+    assert!(truncated.flatiter().all(|x| (25.0..=27.0).contains(&x)));
+    assert!((mean(&truncated) - 25.196).abs() < 0.01);
+
+    let truncated = NdArray::<f64>::truncated_normal_with(&mut generator, [n], 0.0, 1.0, -11.0, -10.0);
+    assert!(truncated.flatiter().all(|x| (-11.0..=-10.0).contains(&x)));
+    assert!((mean(&truncated) + 10.098).abs() < 0.005);
+
+    let exponential = NdArray::<f64>::exponential_with(&mut generator, [n], 4.0);
+    assert!(exponential.flatiter().all(|x| x >= 0.0));
+    assert!((mean(&exponential) - 0.25).abs() < 0.01);
+
+    // mean = k theta, variance = k theta^2
+    let gamma = NdArray::<f64>::gamma_with(&mut generator, [n], 3.0, 2.0);
+    assert!((mean(&gamma) - 6.0).abs() < 0.1);
+    assert!((variance(&gamma) - 12.0).abs() < 0.5);
+
+    // mean = a / (a + b)
+    let beta = NdArray::<f64>::beta_with(&mut generator, [n], 2.0, 6.0);
+    assert!(beta.flatiter().all(|x| (0.0..=1.0).contains(&x)));
+    assert!((mean(&beta) - 0.25).abs() < 0.01);
+
+    let normal = Tensor::<f32>::normal_with(&mut generator, [4, 5], 0.0, 1.0);
+    assert_eq!(normal.shape(), &[4, 5]);
+    assert!(!normal.requires_grad());
+}
+
+#[test]
+fn test_discrete_distributions() {
+    let mut generator = Generator::new(1);
+    let n = 100_000;
+
+    let bernoulli = NdArray::<f64>::bernoulli_with(&mut generator, [n], 0.3);
+    assert!(bernoulli.flatiter().all(|x| x == 0.0 || x == 1.0));
+    assert!((mean(&bernoulli) - 0.3).abs() < 0.01);
+
+    let mask = NdArray::<bool>::bernoulli_with(&mut generator, [10], 1.0);
+    assert_eq!(mask, NdArray::new([true; 10]));
+
+    let poisson = NdArray::<u32>::poisson_with(&mut generator, [n], 5.0);
+    let poisson = NdArray::new(poisson.flatiter().map(|x| x as f64).collect::<Vec<_>>());
+    assert!((mean(&poisson) - 5.0).abs() < 0.05);
+    assert!((variance(&poisson) - 5.0).abs() < 0.15);
+
+    let permutation = NdArray::<i64>::permutation_with(&mut generator, 100);
+    assert_eq!(permutation.sort(0), NdArray::<i64>::arange(0, 100));
+    assert_ne!(permutation, NdArray::<i64>::arange(0, 100));
+}
+
+#[test]
+fn test_multinomial() {
+    let mut generator = Generator::new(2);
+
+    let probabilities = NdArray::new([[1.0, 2.0, 0.0, 7.0], [0.0, 0.0, 5.0, 0.0]]);
+    let samples = probabilities.multinomial_with(&mut generator, 10_000, true);
+    assert_eq!(samples.shape(), &[2, 10_000]);
+
+    let mut counts = [0; 4];
+    for index in samples.slice_along(Axis(0), 0).flatiter() {
+        counts[index] += 1;
+    }
+    assert_eq!(counts[2], 0);
+    assert!((counts[3] as f64 / 10_000.0 - 0.7).abs() < 0.02);
+    assert!(samples.slice_along(Axis(0), 1).flatiter().all(|index| index == 2));
+
+    // without replacement each index is drawn at most once
+    let samples = probabilities.slice_along(Axis(0), 0).multinomial_with(&mut generator, 3, false);
+    let mut samples: Vec<_> = samples.flatiter().collect();
+    samples.sort();
+    assert_eq!(samples, vec![0, 1, 3]);
+
+    // the heaviest category is usually drawn first
+    let probabilities = NdArray::new([1.0, 1.0, 98.0]);
+    let first = (0..1000)
+        .filter(|_| probabilities.multinomial_with(&mut generator, 2, false).flatiter().next() == Some(2))
+        .count();
+    assert!(first > 950);
+}
+
+#[test]
+#[should_panic]
+fn test_multinomial_too_many_samples() {
+    let mut generator = Generator::new(0);
+    NdArray::new([1.0, 0.0, 1.0]).multinomial_with(&mut generator, 3, false);
+}
+
+#[test]
+fn test_choice_and_shuffle() {
+    let mut generator = Generator::new(3);
+
+    let array = NdArray::new([1, 2, 3, 4, 5]);
+    let samples = array.choice_with(&mut generator, 1000, true);
+    assert!(samples.flatiter().all(|x| (1..=5).contains(&x)));
+
+    // indices are drawn as u64 on every platform, so seeded samples are reproducible everywhere
+    let samples = array.choice_with(&mut Generator::new(7), 20, true);
+    let indices = Uniform::new(0u64, 5).sample_iter(Generator::new(7)).take(20);
+    assert!(samples.flatiter().eq(indices.map(|i| array[i as usize])));
+
+    let mut samples: Vec<_> = array.choice_with(&mut generator, 5, false).flatiter().collect();
+    samples.sort();
+    assert_eq!(samples, vec![1, 2, 3, 4, 5]);
+
+    // shuffling along the middle axis keeps the other axes intact
+    let mut array = NdArray::<i32>::arange(0, 24).reshape([2, 3, 4]);
+    array.shuffle_along_with(&mut generator, 1);
+
+    for outer in 0..2 {
+        let block = array.slice_along(Axis(0), outer);
+        let rows: Vec<i32> = block.slice_along(Axis(1), 0).flatiter().collect();
+
+        let mut sorted = rows.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![outer as i32 * 12, outer as i32 * 12 + 4, outer as i32 * 12 + 8]);
+
+        for (i, first) in rows.into_iter().enumerate() {
+            let row = block.slice_along(Axis(0), i);
+            assert_eq!(row, NdArray::new([first, first + 1, first + 2, first + 3]));
+        }
+    }
+
+    // non-contiguous arrays are shuffled in place
+    let mut array = NdArray::<i32>::arange(0, 6).reshape([2, 3]).transpose(0, 1);
+    array.shuffle_along_with(&mut generator, 0);
+
+    let column: Vec<i32> = array.slice_along(Axis(1), 0).flatiter().collect();
+    assert_eq!(array.slice_along(Axis(1), 1), NdArray::new(column.iter().map(|x| x + 3).collect::<Vec<_>>()));
+}
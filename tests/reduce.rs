@@ -1,5 +1,5 @@
 use redstone_ml::*;
-use num::NumCast;
+use num::{Float, NumCast};
 use paste::paste;
 
 #[test]
@@ -445,6 +445,126 @@ test_for_float_dtypes!(
     }
 );
 
+test_for_common_numeric_dtypes!(
+    test_keepdims, {
+        let tensor = NdArray::new([[[1, 5, 3], [2, 9, 4]], [[2, 6, 4], [3, 8, 3]]]).astype::<T>();
+
+        let output = tensor.sum_keepdims(1);
+        assert_eq!(output, NdArray::new([[[3, 14, 7]], [[5, 14, 7]]]).astype::<T>());
+        assert_eq!(output, tensor.sum_along(1).reshape([2, 1, 3]));
+
+        let output = tensor.max_keepdims([0, -1]);
+        assert_eq!(output, NdArray::new([[[6], [9]]]).astype::<T>());
+
+        assert_eq!(tensor.min_keepdims(2).shape(), &[2, 2, 1]);
+        assert_eq!(tensor.product_keepdims([0, 1, 2]).shape(), &[1, 1, 1]);
+        assert_eq!(tensor.min_magnitude_keepdims(0), tensor.min_magnitude_along(0).reshape([1, 2, 3]));
+        assert_eq!(tensor.max_magnitude_keepdims(0), tensor.max_magnitude_along(0).reshape([1, 2, 3]));
+        assert_eq!(tensor.sum_keepdims([]), tensor);
+
+        assert_eq!(tensor.argmax_keepdims(-1), NdArray::new([[[1], [1]], [[1], [1]]]));
+        assert_eq!(tensor.argmin_keepdims(Axis(0)), NdArray::new([[[0, 0, 0], [0, 1, 1]]]));
+
+        // non-contiguous
+        let slice = tensor.slice(s![.., .., 0..=1]);
+        assert_eq!(slice.sum_keepdims(2), NdArray::new([[[6], [11]], [[8], [11]]]).astype::<T>());
+    }
+);
+
+test_for_float_dtypes!(
+    test_mean_keepdims, {
+        let tensor = NdArray::<T>::new([[1.0, 3.0], [2.0, 4.0], [3.0, 5.0]]);
+
+        let output = tensor.mean_keepdims(1);
+        assert_eq!(output, NdArray::<T>::new([[2.0], [3.0], [4.0]]));
+
+        let centered = &tensor - tensor.mean_keepdims(0);
+        assert_eq!(centered, NdArray::<T>::new([[-1.0, -1.0], [0.0, 0.0], [1.0, 1.0]]));
+    }
+);
+
+#[test]
+fn test_all_any_keepdims() {
+    let array = NdArray::new([[true, false, true], [true, false, false]]);
+
+    assert_eq!(array.all_keepdims(0), NdArray::new([[true, false, false]]));
+    assert_eq!(array.any_keepdims(-1), NdArray::new([[true], [true]]));
+}
+
+test_for_all_numeric_dtypes!(
+    test_argmax, {
+        let tensor = NdArray::new([[1, 5, 3], [4, 2, 6]]).astype::<T>();
+
+        let output = tensor.argmax();
+        assert_eq!(output, NdArray::scalar(5));
+
+        let output = tensor.argmax_along(0);
+        assert_eq!(output, NdArray::new([1, 0, 1]));
+
+        let output = tensor.argmax_along(Axis(-1));
+        assert_eq!(output, NdArray::new([1, 2]));
+
+        // first occurrence is returned
+        let tensor = NdArray::new([2, 7, 7, 1, 7]).astype::<T>();
+        assert_eq!(tensor.argmax(), NdArray::scalar(1));
+    }
+);
+
+test_for_all_numeric_dtypes!(
+    test_argmin, {
+        let tensor = NdArray::new([[1, 5, 3], [4, 0, 6]]).astype::<T>();
+
+        let output = tensor.argmin();
+        assert_eq!(output, NdArray::scalar(4));
+
+        let output = tensor.argmin_along(0);
+        assert_eq!(output, NdArray::new([0, 1, 0]));
+
+        let output = tensor.argmin_along(Axis(-1));
+        assert_eq!(output, NdArray::new([0, 1]));
+
+        // first occurrence is returned
+        let tensor = NdArray::new([2, 1, 7, 1, 7]).astype::<T>();
+        assert_eq!(tensor.argmin(), NdArray::scalar(1));
+    }
+);
+
+test_for_common_numeric_dtypes!(
+    test_argmax_argmin_slice, {
+        let tensor = NdArray::new([
+            [[1, 5, 3], [2, 9, 4]],
+            [[2, 6, 4], [3, 8, 3]],
+            [[3, 7, 5], [4, 7, 2]],
+            [[4, 8, 6], [5, 6, 1]]
+        ]).astype::<T>();
+
+        // non-uniform stride and non-contiguous
+        let slice = tensor.slice(s![1..3, .., 0..=1]);  // [[[2, 6], [3, 8]], [[3, 7], [4, 7]]]
+
+        assert_eq!(slice.argmax(), NdArray::scalar(3));
+        assert_eq!(slice.argmin(), NdArray::scalar(0));
+        assert_eq!(slice.argmax_along(1), NdArray::new([[1, 1], [1, 0]]));
+        assert_eq!(slice.argmin_along(2), NdArray::new([[0, 0], [0, 0]]));
+
+        // uniform stride but non-contiguous
+        let slice = tensor.slice(s![.., .., 2]);  // [[3, 4], [4, 3], [5, 2], [6, 1]]
+
+        assert_eq!(slice.argmax(), NdArray::scalar(6));
+        assert_eq!(slice.argmin(), NdArray::scalar(7));
+        assert_eq!(slice.argmax_along(0), NdArray::new([3, 0]));
+        assert_eq!(slice.argmin_along(0), NdArray::new([0, 3]));
+    }
+);
+
+test_for_float_dtypes!(
+    test_argmax_argmin_nan, {
+        let tensor = NdArray::<T>::new([T::nan(), 2.0, -1.0, T::nan(), 3.0]);
+
+        assert_eq!(tensor.argmax(), NdArray::scalar(4));
+        assert_eq!(tensor.argmin(), NdArray::scalar(2));
+    }
+);
+
 // ChatGPT generated
 #[test]
 fn test_tensor_operations() {
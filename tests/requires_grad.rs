@@ -327,3 +327,114 @@ test_for_float_dtypes!(
         assert!(!c.is_leaf());
     }
 );
+
+#[test]
+fn test_no_grad() {
+    let mut a = Tensor::new([1.0f32, 2.0, 3.0]);
+    a.set_requires_grad(true);
+
+    let (b, c) = no_grad(|| {
+        assert!(!is_grad_enabled());
+        assert!(a.requires_grad());
+
+        let b = &a * 2.0;
+        let c = (&a + &b).reshape([3, 1]).T().matmul(&a);
+        (b, c)
+    });
+
+    assert!(is_grad_enabled());
+    assert!(!b.requires_grad());
+    assert!(b.is_leaf());
+    assert!(!c.requires_grad());
+    assert!(c.is_leaf());
+
+    let d = &a * &b;
+    assert!(d.requires_grad());
+}
+
+#[test]
+fn test_no_grad_guard() {
+    let mut a = Tensor::new([1.0f64, 2.0]);
+    a.set_requires_grad(true);
+
+    {
+        let _guard = NoGradGuard::new();
+        assert!(!(&a - 1.0).requires_grad());
+
+        {
+            let _guard = NoGradGuard::new();
+            assert!(!(&a - 1.0).requires_grad());
+        }
+
+        // dropping the inner guard restores the outer scope's mode
+        assert!(!is_grad_enabled());
+    }
+
+    assert!(is_grad_enabled());
+    assert!((&a - 1.0).requires_grad());
+}
+
+#[test]
+fn test_enable_grad() {
+    let mut a = Tensor::new([1.0f32, 2.0]);
+    a.set_requires_grad(true);
+
+    let (b, c) = no_grad(|| {
+        let b = enable_grad(|| &a * &a);
+        let c = &a * &a;
+        (b, c)
+    });
+
+    assert!(b.requires_grad());
+    assert!(!c.requires_grad());
+
+    b.backward();
+    assert_eq!(a.gradient().unwrap(), NdArray::new([2.0, 4.0]));
+}
+
+#[test]
+fn test_inference_mode() {
+    let mut a = Tensor::new([1.0f32, 2.0]);
+    a.set_requires_grad(true);
+
+    let b = inference_mode(|| {
+        assert!(is_inference_mode_enabled());
+        assert!(!is_grad_enabled());
+
+        // enable_grad cannot override inference mode
+        enable_grad(|| {
+            assert!(!is_grad_enabled());
+            &a * &a
+        })
+    });
+
+    assert!(!is_inference_mode_enabled());
+    assert!(is_grad_enabled());
+    assert!(!b.requires_grad());
+    assert_eq!(b, Tensor::new([1.0, 4.0]));
+}
+
+#[test]
+fn test_no_grad_weight_update() {
+    let mut w = Tensor::new([1.0f64, -2.0]);
+    w.set_requires_grad(true);
+
+    let loss = (&w * &w).dot(Tensor::ones([2]));
+    loss.backward();
+
+    assert_eq!(w.gradient().unwrap(), NdArray::new([2.0, -4.0]));
+
+    // an SGD step with a learning rate of 0.25 using the gradient 2w
+    let updated = no_grad(|| &w - (&w * 2.0) * 0.25);
+    assert!(!updated.requires_grad());
+    assert!(updated.is_leaf());
+    assert_eq!(updated, Tensor::new([0.5, -1.0]));
+}
+
+#[test]
+fn test_no_grad_is_thread_local() {
+    let _guard = NoGradGuard::new();
+    assert!(!is_grad_enabled());
+
+    std::thread::spawn(|| assert!(is_grad_enabled())).join().unwrap();
+}
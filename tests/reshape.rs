@@ -211,3 +211,15 @@ fn test_reshape_invalid_shape() {
 
     tensor.reshape([4 * 2]);
 }
+
+#[test]
+fn reshape_scalar() {
+    let a = NdArray::scalar(5);
+
+    let b = (&a).reshape([1, 1]);
+    assert_eq!(b.shape(), &[1, 1]);
+    assert_eq!(b, NdArray::new([[5]]));
+
+    let c = NdArray::scalar(5).reshape([1]);
+    assert_eq!(c, NdArray::new([5]));
+}
@@ -0,0 +1,164 @@
+use redstone_ml::*;
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("redstone_test_{}_{name}.safetensors", std::process::id()))
+}
+
+#[test]
+fn test_state_dict_roundtrip() {
+    let path = temp_path("roundtrip");
+
+    let weight = Tensor::<f64>::randn([3, 5]);
+    let transposed = Tensor::<f64>::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]).T();
+    let scalar = Tensor::scalar(7.0);
+
+    save_state_dict(&path, &[("layer.weight", &weight), ("transposed", &transposed), ("scalar", &scalar)]).unwrap();
+
+    let state_dict = load_state_dict(&path).unwrap();
+    assert_eq!(state_dict.keys(), vec!["layer.weight", "transposed", "scalar"]);
+    assert_eq!(state_dict.dtype("layer.weight"), Some(DType::F64));
+    assert_eq!(state_dict.shape("transposed"), Some(&[3, 2][..]));
+    assert_eq!(state_dict.shape("missing"), None);
+
+    // the arrays are views into the mapped file
+    let loaded = state_dict.get::<f64>("layer.weight").unwrap();
+    assert!(loaded.is_view());
+    assert_eq!(&loaded, weight.ndarray());
+
+    assert_eq!(state_dict.get::<f64>("transposed").unwrap(), NdArray::new([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]));
+    assert_eq!(state_dict.get::<f64>("scalar").unwrap(), NdArray::scalar(7.0));
+
+    drop(loaded);
+    drop(state_dict);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_save_ndarrays_dtypes() {
+    let path = temp_path("dtypes");
+
+    save_ndarrays(&path, &[("a", &NdArray::new([f16::from_f32(1.5), f16::from_f32(-2.0)]))]).unwrap();
+    assert_eq!(load_state_dict(&path).unwrap().get::<f16>("a").unwrap(), NdArray::new([f16::from_f32(1.5), f16::from_f32(-2.0)]));
+
+    save_ndarrays(&path, &[("mask", &NdArray::new([true, false, true]))]).unwrap();
+    assert_eq!(load_state_dict(&path).unwrap().get::<bool>("mask").unwrap(), NdArray::new([true, false, true]));
+
+    let result = save_ndarrays(&path, &[("z", &NdArray::new([Complex64::new(1.0, 0.0)]))]);
+    assert!(matches!(result, Err(StateDictError::UnsupportedDtype(DType::Complex64))));
+
+    let result = save_ndarrays(&path, &[("a", &NdArray::new([1])), ("a", &NdArray::new([2]))]);
+    assert!(matches!(result, Err(StateDictError::DuplicateKey(name)) if name == "a"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_load_into() {
+    let path = temp_path("load_into");
+
+    let weight = Tensor::<f32>::new([[1.0, 2.0], [3.0, 4.0]]);
+    let bias = Tensor::<f32>::new([0.5, -0.5]);
+    save_state_dict(&path, &[("weight", &weight), ("bias", &bias), ("extra", &bias)]).unwrap();
+
+    let state_dict = load_state_dict(&path).unwrap();
+
+    let mut new_weight = Tensor::<f32>::zeros([2, 2]);
+    new_weight.set_requires_grad(true);
+    let mut other = Tensor::<f32>::zeros([3]);
+
+    // strict loading fails without modifying any tensor
+    let result = state_dict.load_into(&mut [("weight", &mut new_weight), ("other", &mut other)], true);
+    let Err(StateDictError::IncompatibleKeys(keys)) = result else { panic!("expected incompatible keys") };
+    assert_eq!(keys.missing_keys, vec!["other"]);
+    assert_eq!(keys.unexpected_keys, vec!["bias", "extra"]);
+    assert_eq!(new_weight, Tensor::zeros([2, 2]));
+
+    // non-strict loading reports the keys and loads the rest
+    let keys = state_dict.load_into(&mut [("weight", &mut new_weight), ("other", &mut other)], false).unwrap();
+    assert_eq!(keys.missing_keys, vec!["other"]);
+    assert_eq!(new_weight, weight);
+    assert_eq!(other, Tensor::zeros([3]));
+
+    // loading is not recorded in the graph
+    assert!(new_weight.requires_grad());
+    assert!(new_weight.is_leaf());
+
+    // the loaded tensor does not alias the file
+    drop(state_dict);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(new_weight, weight);
+}
+
+#[test]
+fn test_load_into_mismatches() {
+    let path = temp_path("mismatches");
+    save_state_dict(&path, &[("weight", &Tensor::<f32>::ones([2, 3]))]).unwrap();
+
+    let state_dict = load_state_dict(&path).unwrap();
+
+    let mut wrong_shape = Tensor::<f32>::zeros([3, 2]);
+    let result = state_dict.load_into(&mut [("weight", &mut wrong_shape)], true);
+    assert!(matches!(result, Err(StateDictError::ShapeMismatch { expected, found, .. })
+        if expected == vec![3, 2] && found == vec![2, 3]));
+
+    let mut wrong_dtype = Tensor::<f64>::zeros([2, 3]);
+    let result = state_dict.load_into(&mut [("weight", &mut wrong_dtype)], true);
+    assert!(matches!(result, Err(StateDictError::DtypeMismatch { expected: DType::F64, found: DType::F32, .. })));
+
+    assert!(matches!(state_dict.get::<f32>("bias"), Err(StateDictError::MissingKey(_))));
+
+    drop(state_dict);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_load_external_file() {
+    // a file laid out as other safetensors writers produce it, with metadata and an unaligned tensor
+    let header = r#"{"__metadata__":{"format":"pt"},"b":{"dtype":"I16","shape":[1],"data_offsets":[8,10]},"a":{"dtype":"F32","shape":[2],"data_offsets":[0,8]}}"#;
+
+    let mut file = (header.len() as u64).to_le_bytes().to_vec();
+    file.extend_from_slice(header.as_bytes());
+    file.extend_from_slice(&1.5f32.to_le_bytes());
+    file.extend_from_slice(&(-2.0f32).to_le_bytes());
+    file.extend_from_slice(&(-3i16).to_le_bytes());
+
+    let path = temp_path("external");
+    std::fs::write(&path, &file).unwrap();
+
+    let state_dict = load_state_dict(&path).unwrap();
+    assert_eq!(state_dict.keys(), vec!["a", "b"]);
+    assert_eq!(state_dict.get::<f32>("a").unwrap(), NdArray::new([1.5, -2.0]));
+    assert_eq!(state_dict.get::<i16>("b").unwrap(), NdArray::new([-3]));
+
+    drop(state_dict);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_load_invalid_files() {
+    let path = temp_path("invalid");
+
+    let invalid_files: [&[u8]; 4] = [
+        b"\x02\x00",
+        b"\xff\x00\x00\x00\x00\x00\x00\x00{}",
+        b"\x02\x00\x00\x00\x00\x00\x00\x00{]",
+        b"\x3e\x00\x00\x00\x00\x00\x00\x00{\"a\":{\"dtype\":\"F32\",\"shape\":[2],\"data_offsets\":[0,8]}}",
+    ];
+
+    for file in invalid_files {
+        std::fs::write(&path, file).unwrap();
+        assert!(matches!(load_state_dict(&path), Err(StateDictError::InvalidFile(_))));
+    }
+
+    // deeply nested headers are rejected instead of overflowing the stack
+    let header = "[".repeat(1_000_000);
+    let mut file = (header.len() as u64).to_le_bytes().to_vec();
+    file.extend_from_slice(header.as_bytes());
+
+    std::fs::write(&path, &file).unwrap();
+    assert!(matches!(load_state_dict(&path), Err(StateDictError::InvalidFile(_))));
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(load_state_dict(&path), Err(StateDictError::Io(_))));
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "serde")]
+
+use redstone_ml::*;
+
+#[test]
+fn test_serialize_ndarray() {
+    let array = NdArray::new([[1, 2, 3], [4, 5, 6]]);
+    let json = serde_json::to_string(&array).unwrap();
+    assert_eq!(json, r#"{"dtype":"i32","shape":[2,3],"data":[1,2,3,4,5,6]}"#);
+
+    // views serialize their logical contents
+    let view = array.T();
+    let json = serde_json::to_string(&view).unwrap();
+    assert_eq!(json, r#"{"dtype":"i32","shape":[3,2],"data":[1,4,2,5,3,6]}"#);
+
+    let scalar = serde_json::to_string(&NdArray::scalar(true)).unwrap();
+    assert_eq!(scalar, r#"{"dtype":"bool","shape":[],"data":[true]}"#);
+}
+
+#[test]
+fn test_deserialize_ndarray() {
+    let array: NdArray<f64> = serde_json::from_str(r#"{"dtype":"f64","shape":[2,2],"data":[1.0,2.0,3.0,4.0]}"#).unwrap();
+    assert_eq!(array, NdArray::new([[1.0, 2.0], [3.0, 4.0]]));
+
+    let view = NdArray::<u8>::arange(0, 12).reshape([3, 4]);
+    let view = view.slice_along(Axis(1), 1..3);
+    let roundtrip: NdArray<u8> = serde_json::from_str(&serde_json::to_string(&view).unwrap()).unwrap();
+    assert_eq!(roundtrip, view);
+    assert!(!roundtrip.is_view());
+
+    let complex = NdArray::new([Complex64::new(1.0, -1.0), Complex64::new(0.5, 2.0)]);
+    let roundtrip: NdArray<Complex64> = serde_json::from_str(&serde_json::to_string(&complex).unwrap()).unwrap();
+    assert_eq!(roundtrip, complex);
+
+    let half = NdArray::new([bf16::from_f32(1.5), bf16::from_f32(-3.0)]);
+    let roundtrip: NdArray<bf16> = serde_json::from_str(&serde_json::to_string(&half).unwrap()).unwrap();
+    assert_eq!(roundtrip, half);
+}
+
+#[test]
+fn test_deserialize_invalid() {
+    let result = serde_json::from_str::<NdArray<f32>>(r#"{"dtype":"f32","shape":[2,2],"data":[1.0,2.0,3.0]}"#);
+    assert!(result.unwrap_err().to_string().contains("cannot have 3 elements"));
+
+    let result = serde_json::from_str::<NdArray<f32>>(r#"{"dtype":"f64","shape":[1],"data":[1.0]}"#);
+    assert!(result.unwrap_err().to_string().contains("dtype f32"));
+
+    let overflowing = format!(r#"{{"dtype":"f32","shape":[{},4],"data":[]}}"#, usize::MAX);
+    assert!(serde_json::from_str::<NdArray<f32>>(&overflowing).is_err());
+
+    assert!(serde_json::from_str::<NdArray<f32>>(r#"{"dtype":"f32","data":[1.0]}"#).is_err());
+}
+
+#[test]
+fn test_serialize_tensor() {
+    let mut tensor = Tensor::new([1.0f32, 2.0]);
+    tensor.set_requires_grad(true);
+
+    let json = serde_json::to_string(&tensor).unwrap();
+    assert_eq!(json, r#"{"dtype":"f32","shape":[2],"data":[1.0,2.0],"requires_grad":true}"#);
+
+    let roundtrip: Tensor<f32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(roundtrip, tensor);
+    assert!(roundtrip.requires_grad());
+    assert!(roundtrip.is_leaf());
+
+    // serialized arrays can be loaded as tensors which do not require gradients
+    let json = serde_json::to_string(&NdArray::new([3.0f32])).unwrap();
+    let tensor: Tensor<f32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(tensor, Tensor::new([3.0]));
+    assert!(!tensor.requires_grad());
+}
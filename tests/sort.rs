@@ -0,0 +1,128 @@
+use redstone_ml::*;
+use num::Float;
+use paste::paste;
+
+
+test_for_all_numeric_dtypes!(
+    test_sort, {
+        let tensor = NdArray::new([[3, 1, 2], [0, 5, 4]]).astype::<T>();
+
+        let correct = NdArray::new([[1, 2, 3], [0, 4, 5]]).astype::<T>();
+        assert_eq!(tensor.sort(1), correct);
+        assert_eq!(tensor.sort_unstable(-1), correct);
+
+        let correct = NdArray::new([[0, 1, 2], [3, 5, 4]]).astype::<T>();
+        assert_eq!(tensor.sort(0), correct);
+        assert_eq!(tensor.sort_unstable(0), correct);
+    }
+);
+
+test_for_common_numeric_dtypes!(
+    test_sort_slice, {
+        let tensor = NdArray::new([
+            [[1, 5, 3], [2, 9, 4]],
+            [[2, 6, 4], [3, 8, 3]],
+            [[3, 7, 5], [4, 7, 2]],
+            [[4, 8, 6], [5, 6, 1]]
+        ]).astype::<T>();
+
+        let slice = tensor.slice(s![.., 1, ..]);  // [[2, 9, 4], [3, 8, 3], [4, 7, 2], [5, 6, 1]]
+
+        let correct = NdArray::new([[2, 4, 9], [3, 3, 8], [2, 4, 7], [1, 5, 6]]).astype::<T>();
+        assert_eq!(slice.sort(-1), correct);
+
+        let correct = NdArray::new([[2, 6, 1], [3, 7, 2], [4, 8, 3], [5, 9, 4]]).astype::<T>();
+        assert_eq!(slice.sort(0), correct);
+
+        let correct = NdArray::new([[0, 2, 1], [0, 2, 1], [2, 0, 1], [2, 0, 1]]);
+        assert_eq!(slice.argsort(-1), correct);
+    }
+);
+
+test_for_all_numeric_dtypes!(
+    test_argsort, {
+        let tensor = NdArray::new([[3, 1, 2, 1], [0, 5, 0, 4]]).astype::<T>();
+
+        // stable sort keeps equal elements in their original order
+        let correct = NdArray::new([[1, 3, 2, 0], [0, 2, 3, 1]]);
+        assert_eq!(tensor.argsort(1), correct);
+
+        let correct = NdArray::new([[1, 0, 1, 0], [0, 1, 0, 1]]);
+        assert_eq!(tensor.argsort(0), correct);
+
+        let output = tensor.argsort_unstable(1);
+        for (row, indices) in tensor.iter().zip(output.iter()) {
+            let sorted: Vec<T> = indices.flatiter().map(|i| row[[i]]).collect();
+            assert_eq!(NdArray::new(sorted), row.sort(0));
+        }
+    }
+);
+
+test_for_float_dtypes!(
+    test_sort_nan, {
+        let tensor = NdArray::<T>::new([2.0, T::nan(), -1.0, 0.5]);
+
+        let output = tensor.sort(0);
+        assert_eq!(output.slice(s![..3]), NdArray::new([-1.0, 0.5, 2.0]));
+        assert!(output[[3]].is_nan());
+
+        assert_eq!(tensor.argsort(0), NdArray::new([2, 3, 0, 1]));
+    }
+);
+
+test_for_all_numeric_dtypes!(
+    test_topk, {
+        let tensor = NdArray::new([[1, 9, 3, 7], [8, 2, 6, 4]]).astype::<T>();
+
+        let (values, indices) = tensor.topk(2, 1, true);
+        assert_eq!(values, NdArray::new([[9, 7], [8, 6]]).astype::<T>());
+        assert_eq!(indices, NdArray::new([[1, 3], [0, 2]]));
+
+        let (values, indices) = tensor.topk(3, -1, false);
+        assert_eq!(values, NdArray::new([[1, 3, 7], [2, 4, 6]]).astype::<T>());
+        assert_eq!(indices, NdArray::new([[0, 2, 3], [1, 3, 2]]));
+
+        let (values, indices) = tensor.topk(1, 0, true);
+        assert_eq!(values, NdArray::new([[8, 9, 6, 7]]).astype::<T>());
+        assert_eq!(indices, NdArray::new([[1, 0, 1, 0]]));
+
+        // ties are broken by the lower index
+        let tensor = NdArray::new([5, 1, 5, 5]).astype::<T>();
+        let (_, indices) = tensor.topk(2, 0, true);
+        assert_eq!(indices, NdArray::new([0, 2]));
+    }
+);
+
+#[test]
+#[should_panic]
+fn test_topk_panic() {
+    let tensor = NdArray::new([1, 2, 3]);
+    tensor.topk(4, 0, true);
+}
+
+test_for_all_numeric_dtypes!(
+    test_unique, {
+        let tensor = NdArray::new([[3, 1, 3], [2, 1, 5]]).astype::<T>();
+        assert_eq!(tensor.unique(), NdArray::new([1, 2, 3, 5]).astype::<T>());
+
+        let slice = tensor.slice(s![.., 1]);
+        assert_eq!(slice.unique(), NdArray::new([1]).astype::<T>());
+    }
+);
+
+test_for_all_numeric_dtypes!(
+    test_searchsorted, {
+        let tensor = NdArray::new([1, 2, 3, 3, 5]).astype::<T>();
+        let values = NdArray::new([[3, 0], [6, 2]]).astype::<T>();
+
+        assert_eq!(tensor.searchsorted(&values), NdArray::new([[2, 0], [5, 1]]));
+        assert_eq!(tensor.searchsorted_right(&values), NdArray::new([[4, 0], [5, 2]]));
+    }
+);
+
+#[test]
+#[should_panic]
+fn test_searchsorted_panic() {
+    let tensor = NdArray::new([[1, 2], [3, 4]]);
+    tensor.searchsorted(&NdArray::new([1]));
+}
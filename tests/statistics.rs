@@ -0,0 +1,220 @@
+use redstone_ml::*;
+use num::{Float, NumCast};
+use paste::paste;
+
+test_for_float_dtypes!(
+    test_var, {
+        let array = NdArray::new([[1.0, 3.0], [2.0, 6.0]]).astype::<T>();
+
+        assert_eq!(array.var(0), NdArray::scalar(3.5).astype::<T>());
+        assert_eq!(array.var_along(0, 0), NdArray::new([0.25, 2.25]).astype::<T>());
+        assert_eq!(array.var_along(-1, 1), NdArray::new([2.0, 8.0]).astype::<T>());
+        assert_eq!(array.var_along([0, 1], 2), NdArray::scalar(7.0).astype::<T>());
+
+        let std = array.std_along(0, 0);
+        assert_eq!(std, NdArray::new([0.5, 1.5]).astype::<T>());
+    }
+);
+
+test_for_float_dtypes!(
+    test_var_ddof_too_large, {
+        let array = NdArray::new([[1.0, 3.0], [2.0, 6.0]]).astype::<T>();
+
+        assert!(array.var_along(1, 2).flatiter().all(|x| x.is_nan()));
+        assert!(array.std(4).value().is_nan());
+    }
+);
+
+test_for_float_dtypes!(
+    test_var_slice, {
+        let array = NdArray::new([
+            [[1.0, 5.0, 3.0], [2.0, 9.0, 4.0]],
+            [[2.0, 6.0, 4.0], [3.0, 8.0, 3.0]],
+            [[3.0, 7.0, 5.0], [4.0, 7.0, 2.0]],
+        ]).astype::<T>();
+
+        // non-uniform stride and non-contiguous
+        let slice = array.slice(s![.., .., 0..=1]);
+
+        let correct = NdArray::new([[4.0, 12.25], [4.0, 6.25], [4.0, 2.25]]).astype::<T>();
+        assert_eq!(slice.var_along(2, 0), correct);
+        assert_eq!(slice.var_along(2, 0), slice.clone().var_along(2, 0));
+        assert_eq!(slice.var_along([0, 1], 1), slice.clone().var_along([0, 1], 1));
+    }
+);
+
+#[test]
+fn test_var_welford_stability() {
+    let array = NdArray::new([1.0, 2.0, 3.0, 4.0]) + 1e9;
+    assert!((array.var(0).value() - 1.25).abs() < 1e-6);
+    assert!((array.std(1).value() - (5.0f64 / 3.0).sqrt()).abs() < 1e-6);
+}
+
+test_for_all_numeric_dtypes!(
+    test_cumsum, {
+        let array = NdArray::new([[1, 2, 3], [4, 5, 6]]).astype::<T>();
+
+        assert_eq!(array.cumsum(1), NdArray::new([[1, 3, 6], [4, 9, 15]]).astype::<T>());
+        assert_eq!(array.cumsum(-2), NdArray::new([[1, 2, 3], [5, 7, 9]]).astype::<T>());
+
+        let transposed = array.T();
+        assert_eq!(transposed.cumsum(0), NdArray::new([[1, 4], [3, 9], [6, 15]]).astype::<T>());
+    }
+);
+
+test_for_all_numeric_dtypes!(
+    test_cumprod, {
+        let array = NdArray::new([[1, 2, 3], [4, 5, 2]]).astype::<T>();
+
+        assert_eq!(array.cumprod(1), NdArray::new([[1, 2, 6], [4, 20, 40]]).astype::<T>());
+        assert_eq!(array.cumprod(0), NdArray::new([[1, 2, 3], [4, 10, 6]]).astype::<T>());
+
+        let slice = array.slice(s![.., 1..]);
+        assert_eq!(slice.cumprod(-1), NdArray::new([[2, 6], [5, 10]]).astype::<T>());
+    }
+);
+
+test_for_float_dtypes!(
+    test_logsumexp, {
+        let array = NdArray::new([[0.0, 0.0], [1.0, 1.0]]).astype::<T>();
+        let ln2 = T::ln(<T as NumCast>::from(2.0).unwrap());
+
+        assert_eq!(array.logsumexp_along(1), NdArray::new([ln2, <T as NumCast>::from(1.0).unwrap() + ln2]));
+
+        let array = NdArray::new([1000.0, 1000.0, T::neg_infinity()]).astype::<T>();
+        assert_eq!(array.logsumexp().value(), <T as NumCast>::from(1000.0).unwrap() + ln2);
+
+        let array = NdArray::new([T::neg_infinity(), T::neg_infinity()]);
+        assert_eq!(array.logsumexp().value(), T::neg_infinity());
+    }
+);
+
+test_for_float_dtypes!(
+    test_norm, {
+        let array = NdArray::new([[3.0, -4.0], [-1.0, 0.0]]).astype::<T>();
+
+        assert_eq!(array.norm_along(Norm::L1, 1), NdArray::new([7.0, 1.0]).astype::<T>());
+        assert_eq!(array.norm_along(Norm::L2, 1), NdArray::new([5.0, 1.0]).astype::<T>());
+        assert_eq!(array.norm_along(Norm::Inf, 0), NdArray::new([3.0, 4.0]).astype::<T>());
+        assert_eq!(array.norm_along(Norm::Lp(<T as NumCast>::from(1.0).unwrap()), -1), array.norm_along(Norm::L1, -1));
+
+        let lp = array.norm(Norm::Lp(<T as NumCast>::from(3.0).unwrap())).value();
+        assert!((lp - <T as NumCast>::from(92.0).unwrap().cbrt()).abs() < <T as NumCast>::from(1e-5).unwrap());
+
+        assert_eq!(array.norm(Norm::Inf).value(), <T as NumCast>::from(4.0).unwrap());
+    }
+);
+
+test_for_float_dtypes!(
+    test_median, {
+        let array = NdArray::new([[3.0, 1.0, 2.0], [4.0, 6.0, 5.0]]).astype::<T>();
+
+        assert_eq!(array.median().value(), <T as NumCast>::from(3.5).unwrap());
+        assert_eq!(array.median_along(1), NdArray::new([2.0, 5.0]).astype::<T>());
+        assert_eq!(array.median_along(0), NdArray::new([3.5, 3.5, 3.5]).astype::<T>());
+
+        let array = NdArray::new([1.0, T::nan(), 2.0]);
+        assert!(array.median().value().is_nan());
+    }
+);
+
+test_for_float_dtypes!(
+    test_quantile, {
+        let array = NdArray::new([[1.0, 10.0], [2.0, 20.0], [4.0, 40.0], [3.0, 30.0]]).astype::<T>();
+
+        assert_eq!(array.quantile_along(<T as NumCast>::from(0.0).unwrap(), 0), NdArray::new([1.0, 10.0]).astype::<T>());
+        assert_eq!(array.quantile_along(<T as NumCast>::from(1.0).unwrap(), 0), NdArray::new([4.0, 40.0]).astype::<T>());
+        assert_eq!(array.quantile_along(<T as NumCast>::from(0.5).unwrap(), 0), NdArray::new([2.5, 25.0]).astype::<T>());
+        assert_eq!(array.slice(s![.., 0]).quantile(<T as NumCast>::from(0.25).unwrap()).value(), <T as NumCast>::from(1.75).unwrap());
+    }
+);
+
+#[test]
+#[should_panic]
+fn test_quantile_panic() {
+    let array = NdArray::new([1.0, 2.0, 3.0]);
+    array.quantile(1.5);
+}
+
+#[test]
+fn test_all_any() {
+    let array = NdArray::new([[true, false, true], [true, false, false]]);
+
+    assert!(!array.all().value());
+    assert!(array.any().value());
+
+    assert_eq!(array.all_along(0), NdArray::new([true, false, false]));
+    assert_eq!(array.any_along(-1), NdArray::new([true, true]));
+    assert_eq!(array.any_along(0), NdArray::new([true, false, true]));
+
+    let slice = array.slice(s![.., 0]);
+    assert!(slice.all().value());
+}
+
+test_for_float_dtypes!(
+    test_statistics_keepdims, {
+        let array = NdArray::new([[1.0, 3.0, 2.0], [3.0, 7.0, 5.0]]).astype::<T>();
+
+        assert_eq!(array.var_keepdims(1, 0), array.var_along(1, 0).reshape([2, 1]));
+        assert_eq!(array.std_keepdims(0, 1), array.std_along(0, 1).reshape([1, 3]));
+        assert_eq!(array.logsumexp_keepdims([0, 1]), array.logsumexp().reshape([1, 1]));
+        assert_eq!(array.norm_keepdims(Norm::L1, -1), NdArray::new([[6.0], [15.0]]).astype::<T>());
+        assert_eq!(array.median_keepdims(-1), NdArray::new([[2.0], [5.0]]).astype::<T>());
+
+        let q = <T as NumCast>::from(0.5).unwrap();
+        assert_eq!(array.quantile_keepdims(q, 0), NdArray::new([[2.0, 5.0, 3.5]]).astype::<T>());
+    }
+);
+
+test_for_all_numeric_dtypes!(
+    test_sum_where, {
+        let array = NdArray::new([[1, 2, 3], [4, 5, 6]]).astype::<T>();
+        let mask = NdArray::new([[true, false, true], [false, false, true]]);
+
+        assert_eq!(array.sum_where(&mask), NdArray::scalar(10).astype::<T>());
+        assert_eq!(array.sum_where_along(&mask, 1), NdArray::new([4, 6]).astype::<T>());
+        assert_eq!(array.sum_where_keepdims(&mask, 0), NdArray::new([[1, 0, 9]]).astype::<T>());
+
+        // broadcast mask
+        let mask = NdArray::new([true, true, false]);
+        assert_eq!(array.sum_where_along(&mask, -1), NdArray::new([3, 9]).astype::<T>());
+    }
+);
+
+#[test]
+#[should_panic]
+fn test_sum_where_panic() {
+    let array = NdArray::new([[1, 2, 3], [4, 5, 6]]);
+    array.sum_where(&NdArray::new([true, false]));
+}
+
+test_for_float_dtypes!(
+    test_mean_where, {
+        // a padded batch of sequences with lengths 3, 1 and 0
+        let batch = NdArray::new([[1.0, 2.0, 6.0], [4.0, 0.0, 0.0], [0.0, 0.0, 0.0]]).astype::<T>();
+        let mask = NdArray::new([[true, true, true], [true, false, false], [false, false, false]]);
+
+        let output = batch.mean_where_along(&mask, 1);
+        assert_eq!(output.slice(s![0..2]), NdArray::new([3.0, 4.0]).astype::<T>());
+        assert!(output[[2]].is_nan());
+
+        assert_eq!(batch.mean_where(&mask), NdArray::scalar(3.25).astype::<T>());
+        assert_eq!(batch.mean_where_keepdims(&mask, 0).shape(), &[1, 3]);
+    }
+);
+
+test_for_float_dtypes!(
+    test_average, {
+        let array = NdArray::new([[1.0, 2.0], [3.0, 4.0]]).astype::<T>();
+
+        let weights = NdArray::new([[1.0, 1.0], [1.0, 1.0]]).astype::<T>();
+        assert_eq!(array.average(&weights), array.mean());
+
+        let weights = NdArray::new([3.0, 1.0]).astype::<T>();
+        assert_eq!(array.average_along(&weights, 1), NdArray::new([1.25, 3.25]).astype::<T>());
+        assert_eq!(array.average_keepdims(&weights, -1), NdArray::new([[1.25], [3.25]]).astype::<T>());
+
+        let weights = NdArray::new([[1.0], [0.0]]).astype::<T>();
+        assert_eq!(array.average_along(&weights, 0), NdArray::new([1.0, 2.0]).astype::<T>());
+    }
+);
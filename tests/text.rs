@@ -0,0 +1,116 @@
+use redstone_ml::*;
+use std::path::PathBuf;
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("redstone_test_{}_{name}.txt", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_from_csv() {
+    let path = temp_file("from_csv", "a,b,c\n1, 2,3\n\n4,5 ,6\n# trailing comment\n");
+
+    let array = NdArray::<i64>::from_csv(&path, ',', 1).unwrap();
+    assert_eq!(array, NdArray::new([[1, 2, 3], [4, 5, 6]]));
+    assert!(!array.is_view());
+
+    let array = NdArray::<f32>::from_csv(&path, ',', 1).unwrap();
+    assert_eq!(array, NdArray::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+
+    // the header cannot be parsed
+    let result = NdArray::<i64>::from_csv(&path, ',', 0);
+    assert!(matches!(result, Err(TextError::Parse { line: 1, column: 1, value }) if value == "a"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_loadtxt() {
+    let path = temp_file("loadtxt", "1.5\t-2\n  3e2    4\n");
+    assert_eq!(NdArray::<f64>::loadtxt(&path, None, 0).unwrap(), NdArray::new([[1.5, -2.0], [300.0, 4.0]]));
+    std::fs::remove_file(&path).unwrap();
+
+    let path = temp_file("loadtxt_column", "true\nfalse\n");
+    assert_eq!(NdArray::<bool>::loadtxt(&path, None, 0).unwrap(), NdArray::new([[true], [false]]));
+    std::fs::remove_file(&path).unwrap();
+
+    let path = temp_file("loadtxt_empty", "# only a comment\n");
+    assert_eq!(NdArray::<f64>::loadtxt(&path, None, 0).unwrap().shape(), &[0usize, 0]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_loadtxt_errors() {
+    let path = temp_file("loadtxt_errors", "1;2;3\n4;5\n");
+    let result = NdArray::<u8>::loadtxt(&path, Some(';'), 0);
+    assert!(matches!(result, Err(TextError::RaggedRow { line: 2, expected: 3, found: 2 })));
+    std::fs::remove_file(&path).unwrap();
+
+    let path = temp_file("loadtxt_missing", "1;;3\n");
+    let result = NdArray::<u8>::loadtxt(&path, Some(';'), 0);
+    assert!(matches!(result, Err(TextError::Parse { line: 1, column: 2, .. })));
+    std::fs::remove_file(&path).unwrap();
+
+    let path = temp_file("loadtxt_overflow", "1;300\n");
+    let result = NdArray::<u8>::loadtxt(&path, Some(';'), 0);
+    assert_eq!(result.unwrap_err().to_string(), "line 1, column 2: could not parse '300'");
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(NdArray::<u8>::loadtxt(&path, None, 0), Err(TextError::Io(_))));
+}
+
+#[test]
+fn test_genfromtxt() {
+    let path = temp_file("genfromtxt", "x,y\n1,?\n,4\n5,6\n");
+
+    let (array, missing) = NdArray::<i32>::genfromtxt(&path, Some(','), 1, &["?"], -1).unwrap();
+    assert_eq!(array, NdArray::new([[1, -1], [-1, 4], [5, 6]]));
+    assert_eq!(missing, NdArray::new([[false, true], [true, false], [false, false]]));
+
+    // unlisted markers are still parse errors
+    let result = NdArray::<i32>::genfromtxt(&path, Some(','), 1, &[], -1);
+    assert!(matches!(result, Err(TextError::Parse { line: 2, column: 2, .. })));
+
+    std::fs::remove_file(&path).unwrap();
+
+    // empty fields at the edges of tab-delimited rows are missing values
+    let path = temp_file("genfromtxt_tabs_leading", "\t2\t3\n\t5\t6\n");
+    let (array, missing) = NdArray::<i32>::genfromtxt(&path, Some('\t'), 0, &[], 0).unwrap();
+    assert_eq!(array, NdArray::new([[0, 2, 3], [0, 5, 6]]));
+    assert_eq!(missing, NdArray::new([[true, false, false], [true, false, false]]));
+    std::fs::remove_file(&path).unwrap();
+
+    let path = temp_file("genfromtxt_tabs_trailing", "1\t2\t3\n4\t5\t\n");
+    let (array, missing) = NdArray::<i32>::genfromtxt(&path, Some('\t'), 0, &[], 0).unwrap();
+    assert_eq!(array, NdArray::new([[1, 2, 3], [4, 5, 0]]));
+    assert_eq!(missing, NdArray::new([[false, false, false], [false, false, true]]));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_savetxt() {
+    let path = temp_file("savetxt", "");
+
+    let array = NdArray::new([[1i32, 2, 3], [4, 5, 6]]);
+    array.savetxt(&path, ", ", Some("first\nsecond"), |x| x.to_string()).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "# first\n# second\n1, 2, 3\n4, 5, 6\n");
+    assert_eq!(NdArray::<i32>::loadtxt(&path, Some(','), 0).unwrap(), array);
+
+    // views are written in logical order
+    array.T().savetxt(&path, ",", None, |x| format!("{x:02}")).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "01,04\n02,05\n03,06\n");
+
+    NdArray::new([0.5f64, 1.0]).savetxt(&path, ",", None, |x| x.to_string()).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "0.5\n1\n");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn test_savetxt_3d() {
+    let path = std::env::temp_dir().join("redstone_test_savetxt_3d.txt");
+    let _ = NdArray::<f32>::zeros([2, 2, 2]).savetxt(&path, ",", None, |x| x.to_string());
+}